@@ -0,0 +1,53 @@
+//! Compares the traversals against straightforward implementations on matrices that do not fit
+//! in L2 cache. Run with `cargo bench`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Collects every column by walking down the whole matrix once per column.
+fn column_walk<Row: AsRef<[T]>, T>(matrix: &[Row]) -> Vec<Vec<&T>> {
+    let cols = matrix.first().map_or(0, |row| row.as_ref().len());
+    (0..cols)
+        .map(|y| matrix.iter().map(|row| &row.as_ref()[y]).collect())
+        .collect()
+}
+
+/// Best of five runs, to keep noise from other processes out of the comparison.
+fn time<R>(mut f: impl FnMut() -> R) -> Duration {
+    (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    for (rows, cols) in [(4096, 4096), (65536, 256), (262144, 64), (256, 65536)] {
+        let matrix: Vec<Vec<u32>> = (0..rows)
+            .map(|r| (0..cols).map(|c| (r * cols + c) as u32).collect())
+            .collect();
+        assert_eq!(diagonal::straight_y(&matrix), column_walk(&matrix));
+
+        println!("{rows}x{cols} u32:");
+        println!("  column walk        {:?}", time(|| column_walk(&matrix)));
+        println!(
+            "  straight_y         {:?}",
+            time(|| diagonal::straight_y(&matrix))
+        );
+        println!(
+            "  straight_x         {:?}",
+            time(|| diagonal::straight_x(&matrix))
+        );
+        println!(
+            "  diagonal_pos_pos   {:?}",
+            time(|| diagonal::diagonal_pos_pos(&matrix))
+        );
+        println!(
+            "  diagonal_pos_neg   {:?}",
+            time(|| diagonal::diagonal_pos_neg(&matrix))
+        );
+    }
+}
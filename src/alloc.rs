@@ -0,0 +1,215 @@
+use std::alloc::Allocator;
+
+use crate::{lines_iter, Direction};
+
+/// Traverses the lines of a matrix in the given direction and stores them in `alloc`.
+///
+/// Both the outer vector and every line are allocated from `alloc`, with exactly the capacity
+/// they need, so pools, arenas and tracking allocators see every allocation the traversal
+/// makes.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `direction` - The traversal to perform.
+/// * `alloc` - The allocator to allocate the result from.
+///
+/// # Returns
+///
+/// The lines, in the same order as the function named by `direction` returns them.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::Global;
+/// use diagonal::{lines_in, Direction};
+///
+/// let matrix = [[1, 2], [3, 4]];
+///
+/// let result = lines_in(&matrix, Direction::StraightY, Global);
+/// assert_eq!(result, [vec![&1, &3], vec![&2, &4]]);
+/// ```
+pub fn lines_in<'a, Matrix, Row, T, A>(
+    matrix: &'a Matrix,
+    direction: Direction,
+    alloc: A,
+) -> Vec<Vec<&'a T, A>, A>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    A: Allocator + Clone,
+{
+    let lines = lines_iter(matrix, direction);
+    let mut result = Vec::with_capacity_in(lines.len(), alloc.clone());
+    for line in lines {
+        let mut collected = Vec::with_capacity_in(line.len(), alloc.clone());
+        collected.extend(line);
+        result.push(collected);
+    }
+    result
+}
+
+/// Extracts diagonals with positive slope, like [`diagonal_pos_pos`](crate::diagonal_pos_pos),
+/// allocating them from `alloc` (see [`lines_in`]).
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::Global;
+/// use diagonal::diagonal_pos_pos_in;
+///
+/// let matrix = [[1, 2], [3, 4]];
+/// assert_eq!(diagonal_pos_pos_in(&matrix, Global), [vec![&3], vec![&1, &4], vec![&2]]);
+/// ```
+pub fn diagonal_pos_pos_in<'a, Matrix, Row, T, A>(
+    matrix: &'a Matrix,
+    alloc: A,
+) -> Vec<Vec<&'a T, A>, A>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    A: Allocator + Clone,
+{
+    lines_in(matrix, Direction::DiagonalPosPos, alloc)
+}
+
+/// Extracts diagonals with positive and negative slope, like
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg), allocating them from `alloc` (see
+/// [`lines_in`]).
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::Global;
+/// use diagonal::diagonal_pos_neg_in;
+///
+/// let matrix = [[1, 2], [3, 4]];
+/// assert_eq!(diagonal_pos_neg_in(&matrix, Global), [vec![&1], vec![&2, &3], vec![&4]]);
+/// ```
+pub fn diagonal_pos_neg_in<'a, Matrix, Row, T, A>(
+    matrix: &'a Matrix,
+    alloc: A,
+) -> Vec<Vec<&'a T, A>, A>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    A: Allocator + Clone,
+{
+    lines_in(matrix, Direction::DiagonalPosNeg, alloc)
+}
+
+/// Extracts the rows, like [`straight_x`](crate::straight_x), allocating them from `alloc`
+/// (see [`lines_in`]).
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::Global;
+/// use diagonal::straight_x_in;
+///
+/// let matrix = [[1, 2], [3, 4]];
+/// assert_eq!(straight_x_in(&matrix, Global), [vec![&1, &2], vec![&3, &4]]);
+/// ```
+pub fn straight_x_in<'a, Matrix, Row, T, A>(matrix: &'a Matrix, alloc: A) -> Vec<Vec<&'a T, A>, A>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    A: Allocator + Clone,
+{
+    lines_in(matrix, Direction::StraightX, alloc)
+}
+
+/// Extracts the columns, like [`straight_y`](crate::straight_y), allocating them from `alloc`
+/// (see [`lines_in`]).
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::Global;
+/// use diagonal::straight_y_in;
+///
+/// let matrix = [[1, 2], [3, 4]];
+/// assert_eq!(straight_y_in(&matrix, Global), [vec![&1, &3], vec![&2, &4]]);
+/// ```
+pub fn straight_y_in<'a, Matrix, Row, T, A>(matrix: &'a Matrix, alloc: A) -> Vec<Vec<&'a T, A>, A>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    A: Allocator + Clone,
+{
+    lines_in(matrix, Direction::StraightY, alloc)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::alloc::{AllocError, Global, Layout};
+    use std::cell::Cell;
+    use std::ptr::NonNull;
+
+    use super::*;
+
+    /// Forwards to the global allocator and counts the live allocations.
+    #[derive(Clone, Copy)]
+    struct Counting<'c>(&'c Cell<usize>);
+
+    unsafe impl Allocator for Counting<'_> {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.0.set(self.0.get() + 1);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.0.set(self.0.get() - 1);
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+
+    #[test]
+    fn matches_traversals() {
+        let matrix: Vec<Vec<usize>> = (0..3)
+            .map(|r| (0..5).map(|c| r * 5 + c).collect())
+            .collect();
+        for direction in Direction::ALL {
+            let expected: Vec<Vec<&usize>> = lines_iter(&matrix, direction)
+                .map(Iterator::collect)
+                .collect();
+            assert_eq!(lines_in(&matrix, direction, Global), expected);
+        }
+    }
+
+    #[cfg(feature = "bumpalo")]
+    #[test]
+    fn allocates_from_bump() {
+        let bump = bumpalo::Bump::new();
+        let matrix = [[1, 2, 3], [4, 5, 6]];
+        let result = diagonal_pos_neg_in(&matrix, &bump);
+        assert_eq!(result, [vec![&1], vec![&2, &4], vec![&3, &5], vec![&6]]);
+        assert!(bump.allocated_bytes() > 0);
+    }
+
+    #[test]
+    fn allocates_from_allocator() {
+        let live = Cell::new(0);
+        let matrix = [[1, 2, 3], [4, 5, 6]];
+
+        let diagonals = diagonal_pos_neg_in(&matrix, Counting(&live));
+        assert_eq!(live.get(), 1 + diagonals.len());
+        for diagonal in &diagonals {
+            assert_eq!(diagonal.capacity(), diagonal.len());
+        }
+
+        drop(diagonals);
+        assert_eq!(live.get(), 0);
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{checked, index, DiagonalError};
+
+/// Both diagonal traversals of one matrix, as returned by [`batch_diagonals`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagonals<'a, T> {
+    /// The diagonals with positive slope, as returned by
+    /// [`diagonal_pos_pos`](crate::diagonal_pos_pos).
+    pub pos_pos: Vec<Vec<&'a T>>,
+    /// The diagonals with positive and negative slope, as returned by
+    /// [`diagonal_pos_neg`](crate::diagonal_pos_neg).
+    pub pos_neg: Vec<Vec<&'a T>>,
+}
+
+/// The coordinates of every diagonal of a shape, computed once per batch.
+struct Plan {
+    pos_pos: Vec<Vec<(usize, usize)>>,
+    pos_neg: Vec<Vec<(usize, usize)>>,
+}
+
+impl Plan {
+    /// Validates that all matrices share one shape and plans the traversals for it.
+    fn new<Matrix, Row, T>(matrices: &[Matrix]) -> Result<Plan, DiagonalError>
+    where
+        Matrix: AsRef<[Row]>,
+        Row: AsRef<[T]>,
+    {
+        let (rows, cols) = match matrices.first() {
+            Some(first) => checked::shape(first)?,
+            None => (0, 0),
+        };
+        for matrix in matrices.iter().skip(1) {
+            let found = checked::shape(matrix)?;
+            if found != (rows, cols) {
+                return Err(DiagonalError::ShapeMismatch {
+                    expected: (rows, cols),
+                    found,
+                });
+            }
+        }
+
+        Ok(Plan {
+            pos_pos: index::pos_pos_cells(rows, cols)
+                .map(Iterator::collect)
+                .collect(),
+            pos_neg: index::pos_neg_cells(rows, cols)
+                .map(Iterator::collect)
+                .collect(),
+        })
+    }
+
+    /// Traverses one matrix of the planned shape.
+    fn apply<'a, Row: AsRef<[T]>, T>(&self, matrix: &'a [Row]) -> Diagonals<'a, T> {
+        let resolve = |lines: &[Vec<(usize, usize)>]| -> Vec<Vec<&'a T>> {
+            lines
+                .iter()
+                .map(|line| line.iter().map(|&(x, y)| &matrix[x].as_ref()[y]).collect())
+                .collect()
+        };
+        Diagonals {
+            pos_pos: resolve(&self.pos_pos),
+            pos_neg: resolve(&self.pos_neg),
+        }
+    }
+}
+
+/// Extracts both diagonal traversals of many matrices of the same shape.
+///
+/// The shape is validated and the coordinates of every diagonal are computed once for the whole
+/// batch, so nothing but the lookups is repeated per matrix.
+///
+/// # Arguments
+///
+/// * `matrices` - The matrices, each represented as a slice of rows, where each row is a slice
+///   of generic type `T`.
+///
+/// # Returns
+///
+/// A vector with the [`Diagonals`] of every matrix, in the same order as `matrices`.
+///
+/// # Errors
+///
+/// Returns an error if a matrix is invalid (see [`checked::shape`]), or
+/// [`DiagonalError::ShapeMismatch`] if a matrix differs in shape from the first one.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{batch_diagonals, DiagonalError};
+///
+/// let boards = vec![
+///     [[1, 2], [3, 4]],
+///     [[5, 6], [7, 8]],
+/// ];
+///
+/// let diagonals = batch_diagonals(&boards).unwrap();
+/// assert_eq!(diagonals[1].pos_pos, vec![vec![&7], vec![&5, &8], vec![&6]]);
+/// assert_eq!(diagonals[1].pos_neg, vec![vec![&5], vec![&6, &7], vec![&8]]);
+///
+/// let mixed = vec![vec![vec![1, 2]], vec![vec![3], vec![4]]];
+/// assert_eq!(
+///     batch_diagonals(&mixed),
+///     Err(DiagonalError::ShapeMismatch { expected: (1, 2), found: (2, 1) }),
+/// );
+/// ```
+pub fn batch_diagonals<'a, Matrix, Row, T>(
+    matrices: &'a [Matrix],
+) -> Result<Vec<Diagonals<'a, T>>, DiagonalError>
+where
+    Matrix: AsRef<[Row]>,
+    Row: AsRef<[T]> + 'a,
+{
+    let plan = Plan::new(matrices)?;
+    Ok(matrices
+        .iter()
+        .map(|matrix| plan.apply(matrix.as_ref()))
+        .collect())
+}
+
+/// Extracts both diagonal traversals of many matrices of the same shape, in parallel.
+///
+/// This is the parallel counterpart of [`batch_diagonals`]: the shape is validated and planned
+/// once, and the matrices are then traversed on rayon's thread pool.
+///
+/// # Arguments
+///
+/// * `matrices` - The matrices, each represented as a slice of rows, where each row is a slice
+///   of generic type `T`.
+///
+/// # Returns
+///
+/// A vector with the [`Diagonals`] of every matrix, in the same order as `matrices`.
+///
+/// # Errors
+///
+/// Returns an error if a matrix is invalid (see [`checked::shape`]), or
+/// [`DiagonalError::ShapeMismatch`] if a matrix differs in shape from the first one.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{batch_diagonals, par_batch_diagonals};
+///
+/// let boards: Vec<[[u8; 8]; 8]> = (0..100).map(|i| [[i; 8]; 8]).collect();
+///
+/// assert_eq!(par_batch_diagonals(&boards), batch_diagonals(&boards));
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_batch_diagonals<'a, Matrix, Row, T>(
+    matrices: &'a [Matrix],
+) -> Result<Vec<Diagonals<'a, T>>, DiagonalError>
+where
+    Matrix: AsRef<[Row]> + Sync,
+    Row: AsRef<[T]> + 'a,
+    T: Sync,
+{
+    use rayon::prelude::*;
+
+    let plan = Plan::new(matrices)?;
+    Ok(matrices
+        .par_iter()
+        .map(|matrix| plan.apply(matrix.as_ref()))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boards() -> Vec<Vec<Vec<u32>>> {
+        (0..20)
+            .map(|i| {
+                (0..3)
+                    .map(|r| (0..5).map(|c| i * 100 + r * 5 + c).collect())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_single_traversals() {
+        let boards = boards();
+        let diagonals = batch_diagonals(&boards).unwrap();
+        assert_eq!(diagonals.len(), boards.len());
+        for (board, diagonals) in boards.iter().zip(&diagonals) {
+            assert_eq!(diagonals.pos_pos, crate::diagonal_pos_pos(board));
+            assert_eq!(diagonals.pos_neg, crate::diagonal_pos_neg(board));
+        }
+    }
+
+    #[test]
+    fn invalid_batches() {
+        let mut boards = boards();
+        boards[7].pop();
+        assert_eq!(
+            batch_diagonals(&boards),
+            Err(DiagonalError::ShapeMismatch {
+                expected: (3, 5),
+                found: (2, 5)
+            })
+        );
+
+        boards[7] = vec![vec![1, 2, 3, 4, 5], vec![1], vec![1, 2, 3, 4, 5]];
+        assert_eq!(
+            batch_diagonals(&boards),
+            Err(DiagonalError::Ragged {
+                row: 1,
+                expected: 5,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn empty_batch() {
+        let boards: Vec<Vec<Vec<u8>>> = vec![];
+        assert_eq!(batch_diagonals(&boards), Ok(vec![]));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_matches_sequential() {
+        let boards = boards();
+        assert_eq!(par_batch_diagonals(&boards), batch_diagonals(&boards));
+    }
+}
@@ -0,0 +1,291 @@
+//! # Bitboard Operations Module
+//!
+//! This module provides the crate's traversals for boolean matrices packed into bitboards: the
+//! matrix is a slice of `u64`, one per row, where bit `c` of a row holds the cell in column `c`.
+//! Since a `u64` does not know how many of its bits are in use, every function takes the number
+//! of columns explicitly; higher bits are ignored.
+//!
+//! Lines are returned packed in the same way: bit `i` of a line holds its `i`-th cell, in the
+//! same order the slice-based traversals visit them. Together with [`any_run`] this answers
+//! questions like "does any diagonal contain four in a row?" with a handful of bit operations.
+//!
+//! ### Example
+//!
+//! ```rust
+//! use diagonal::bits::{any_run, diagonal_pos_pos};
+//!
+//! // A 4x4 board with a diagonal from (0, 0) to (3, 3).
+//! let board = [0b0001, 0b0010, 0b0100, 0b1000];
+//!
+//! let diagonals = diagonal_pos_pos(&board, 4);
+//! assert_eq!(diagonals[3], 0b1111);
+//! assert!(diagonals.iter().any(|&line| any_run(line, 4)));
+//! ```
+
+use crate::index;
+
+/// Reads the cell at `(row, col)` from a bitboard.
+fn bit(rows: &[u64], (row, col): (usize, usize)) -> u64 {
+    (rows[row] >> col) & 1
+}
+
+/// Packs the cells of every line into a `u64`, first cell in the lowest bit.
+fn pack(
+    rows: &[u64],
+    lines: impl Iterator<Item = impl Iterator<Item = (usize, usize)>>,
+) -> Vec<u64> {
+    lines
+        .map(|line| {
+            line.enumerate()
+                .fold(0, |mask, (i, cell)| mask | (bit(rows, cell) << i))
+        })
+        .collect()
+}
+
+/// Panics unless `cols` columns fit into a `u64` row.
+fn assert_width(cols: usize) {
+    assert!(cols <= 64, "a bitboard row holds at most 64 columns");
+}
+
+/// Extracts diagonals with positive slope from a bitboard, like
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos).
+///
+/// # Arguments
+///
+/// * `rows` - The rows of the bitboard, bit `c` of each row holding column `c`.
+/// * `cols` - The number of columns of the bitboard, at most 64.
+///
+/// # Returns
+///
+/// A `Vec<u64>` holding each diagonal packed into a bitmask, with its first cell in the lowest
+/// bit.
+///
+/// # Panics
+///
+/// Panics if `cols` is larger than 64.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::bits::diagonal_pos_pos;
+///
+/// let board = [
+///     0b001,
+///     0b011,
+///     0b100,
+/// ];
+///
+/// assert_eq!(diagonal_pos_pos(&board, 3), vec![0b0, 0b01, 0b111, 0b00, 0b0]);
+/// ```
+pub fn diagonal_pos_pos(rows: &[u64], cols: usize) -> Vec<u64> {
+    assert_width(cols);
+    pack(rows, index::pos_pos_cells(rows.len(), cols))
+}
+
+/// Extracts diagonals with positive and negative slope from a bitboard, like
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg).
+///
+/// # Arguments
+///
+/// * `rows` - The rows of the bitboard, bit `c` of each row holding column `c`.
+/// * `cols` - The number of columns of the bitboard, at most 64.
+///
+/// # Returns
+///
+/// A `Vec<u64>` holding each diagonal packed into a bitmask, with its first cell in the lowest
+/// bit.
+///
+/// # Panics
+///
+/// Panics if `cols` is larger than 64.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::bits::diagonal_pos_neg;
+///
+/// let board = [
+///     0b100,
+///     0b010,
+///     0b001,
+/// ];
+///
+/// assert_eq!(diagonal_pos_neg(&board, 3), vec![0b0, 0b00, 0b111, 0b00, 0b0]);
+/// ```
+pub fn diagonal_pos_neg(rows: &[u64], cols: usize) -> Vec<u64> {
+    assert_width(cols);
+    pack(rows, index::pos_neg_cells(rows.len(), cols))
+}
+
+/// Extracts the rows of a bitboard, like [`straight_x`](crate::straight_x).
+///
+/// This only masks off the bits beyond `cols`.
+///
+/// # Arguments
+///
+/// * `rows` - The rows of the bitboard, bit `c` of each row holding column `c`.
+/// * `cols` - The number of columns of the bitboard, at most 64.
+///
+/// # Returns
+///
+/// A `Vec<u64>` holding each row with all bits at or above `cols` cleared.
+///
+/// # Panics
+///
+/// Panics if `cols` is larger than 64.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::bits::straight_x;
+///
+/// assert_eq!(straight_x(&[0b1111, 0b1010], 3), vec![0b111, 0b010]);
+/// ```
+pub fn straight_x(rows: &[u64], cols: usize) -> Vec<u64> {
+    assert_width(cols);
+    let mask = u64::MAX.checked_shr(64 - cols as u32).unwrap_or(0);
+    rows.iter().map(|row| row & mask).collect()
+}
+
+/// Extracts the columns of a bitboard, like [`straight_y`](crate::straight_y).
+///
+/// # Arguments
+///
+/// * `rows` - The rows of the bitboard, bit `c` of each row holding column `c`.
+/// * `cols` - The number of columns of the bitboard, at most 64.
+///
+/// # Returns
+///
+/// A `Vec<u64>` holding each column packed into a bitmask, with row `r` in bit `r`.
+///
+/// # Panics
+///
+/// Panics if `cols` is larger than 64 or the bitboard has more than 64 rows.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::bits::straight_y;
+///
+/// let board = [
+///     0b01,
+///     0b11,
+///     0b10,
+/// ];
+///
+/// assert_eq!(straight_y(&board, 2), vec![0b011, 0b110]);
+/// ```
+pub fn straight_y(rows: &[u64], cols: usize) -> Vec<u64> {
+    assert_width(cols);
+    assert!(rows.len() <= 64, "a packed column holds at most 64 rows");
+    pack(
+        rows,
+        (0..cols).map(|col| (0..rows.len()).map(move |row| (row, col))),
+    )
+}
+
+/// Checks whether a packed line contains at least `k` consecutive set bits.
+///
+/// A run of length zero is always present.
+///
+/// # Arguments
+///
+/// * `line` - A packed line, as returned by the functions in this module.
+/// * `k` - The required run length.
+///
+/// # Returns
+///
+/// `true` if `k` adjacent bits of `line` are set.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::bits::any_run;
+///
+/// assert!(any_run(0b0111_0110, 3));
+/// assert!(!any_run(0b0111_0110, 4));
+/// ```
+pub fn any_run(line: u64, k: u32) -> bool {
+    let mut remaining = line;
+    for _ in 1..k {
+        if remaining == 0 {
+            return false;
+        }
+        remaining &= remaining >> 1;
+    }
+    k == 0 || remaining != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Converts a bitboard into a matrix of booleans.
+    fn unpack(rows: &[u64], cols: usize) -> Vec<Vec<bool>> {
+        rows.iter()
+            .map(|row| (0..cols).map(|col| (row >> col) & 1 == 1).collect())
+            .collect()
+    }
+
+    /// Packs lines of booleans the same way the module does.
+    fn packed(lines: Vec<Vec<&bool>>) -> Vec<u64> {
+        lines
+            .into_iter()
+            .map(|line| {
+                line.into_iter()
+                    .enumerate()
+                    .fold(0, |mask, (i, &set)| mask | ((set as u64) << i))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_slice_traversals() {
+        let board = [0b10110, 0b01101, 0b11000, 0b00111];
+        let matrix = unpack(&board, 5);
+
+        assert_eq!(
+            diagonal_pos_pos(&board, 5),
+            packed(crate::diagonal_pos_pos(&matrix))
+        );
+        assert_eq!(
+            diagonal_pos_neg(&board, 5),
+            packed(crate::diagonal_pos_neg(&matrix))
+        );
+        assert_eq!(straight_x(&board, 5), packed(crate::straight_x(&matrix)));
+        assert_eq!(straight_y(&board, 5), packed(crate::straight_y(&matrix)));
+    }
+
+    #[test]
+    fn full_width() {
+        let board = [u64::MAX, 1 << 63];
+        assert_eq!(straight_x(&board, 64), vec![u64::MAX, 1 << 63]);
+        let diagonals = diagonal_pos_pos(&board, 64);
+        assert_eq!(diagonals.len(), 65);
+        assert_eq!(diagonals[64], 0b1);
+        assert_eq!(diagonals[63], 0b11);
+    }
+
+    #[test]
+    fn runs() {
+        assert!(any_run(0, 0));
+        assert!(!any_run(0, 1));
+        assert!(any_run(u64::MAX, 64));
+        assert!(!any_run(u64::MAX >> 1, 64));
+        assert!(any_run(1 << 63, 1));
+    }
+
+    #[test]
+    fn empty() {
+        assert!(diagonal_pos_pos(&[], 4).is_empty());
+        assert!(diagonal_pos_neg(&[0b1, 0b1], 0).is_empty());
+        assert_eq!(straight_x(&[0b1], 0), vec![0]);
+        assert!(straight_y(&[], 0).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_wide() {
+        diagonal_pos_pos(&[0], 65);
+    }
+}
@@ -0,0 +1,126 @@
+/// Extracts the square blocks along the main diagonal of a block-diagonal matrix.
+///
+/// The blocks are laid out corner to corner from the top-left: the first block covers the
+/// first `block_sizes[0]` rows and columns, the next one starts where it ends, and so on. Each
+/// block is returned as a view of its rows and can be passed to every traversal of the crate.
+/// Use [`is_block_diagonal`](crate::is_block_diagonal) to check that the cells outside of the
+/// blocks are zero.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a square matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `block_sizes` - The side length of every block, from the top-left to the bottom-right.
+///
+/// # Returns
+///
+/// A vector with one block per entry of `block_sizes`, each of which is a vector of slices into
+/// the rows of `matrix`.
+///
+/// # Panics
+///
+/// Panics if `matrix` is not square or if the block sizes do not add up to its side length.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{diagonal_blocks, diagonal_pos_pos};
+///
+/// let matrix = vec![
+///     vec![1, 2, 0],
+///     vec![3, 4, 0],
+///     vec![0, 0, 5],
+/// ];
+///
+/// let blocks = diagonal_blocks(&matrix, &[2, 1]);
+/// assert_eq!(blocks, vec![
+///     vec![&[1, 2][..], &[3, 4]],
+///     vec![&[5][..]],
+/// ]);
+/// assert_eq!(diagonal_pos_pos(&blocks[0]), vec![vec![&3], vec![&1, &4], vec![&2]]);
+/// ```
+pub fn diagonal_blocks<'a, Matrix, Row, T>(
+    matrix: &'a Matrix,
+    block_sizes: &[usize],
+) -> Vec<Vec<&'a [T]>>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+{
+    let matrix = matrix.as_ref();
+    let n = matrix.len();
+    assert!(
+        matrix.iter().all(|row| row.as_ref().len() == n),
+        "matrix must be square"
+    );
+    assert!(
+        block_sizes
+            .iter()
+            .try_fold(0usize, |total, &size| total.checked_add(size))
+            == Some(n),
+        "block sizes must add up to the side length of the matrix"
+    );
+
+    let mut offset = 0;
+    block_sizes
+        .iter()
+        .map(|&size| {
+            let block = matrix[offset..offset + size]
+                .iter()
+                .map(|row| &row.as_ref()[offset..offset + size])
+                .collect();
+            offset += size;
+            block
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks_tile_the_diagonal() {
+        let matrix: Vec<Vec<usize>> = (0..6)
+            .map(|r| (0..6).map(|c| r * 6 + c).collect())
+            .collect();
+        let blocks = diagonal_blocks(&matrix, &[1, 3, 0, 2]);
+
+        assert_eq!(
+            blocks.iter().map(Vec::len).collect::<Vec<_>>(),
+            [1, 3, 0, 2]
+        );
+        let mut offset = 0;
+        for block in &blocks {
+            for (i, row) in block.iter().enumerate() {
+                assert_eq!(row.len(), block.len());
+                for (j, &value) in row.iter().enumerate() {
+                    assert_eq!(value, (offset + i) * 6 + offset + j);
+                }
+            }
+            offset += block.len();
+        }
+    }
+
+    #[test]
+    fn single_block_and_empty() {
+        let matrix = [[1, 2], [3, 4]];
+        assert_eq!(
+            diagonal_blocks(&matrix, &[2]),
+            vec![vec![&[1, 2][..], &[3, 4]]]
+        );
+        assert!(diagonal_blocks(&Vec::<Vec<u8>>::new(), &[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn sizes_do_not_add_up() {
+        diagonal_blocks(&[[1, 2], [3, 4]], &[1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn not_square() {
+        diagonal_blocks(&[[1, 2]], &[1]);
+    }
+}
@@ -0,0 +1,296 @@
+use crate::index;
+
+/// Rebuilds a matrix from its diagonals with positive slope.
+///
+/// This is the inverse of [`diagonal_pos_pos`](crate::diagonal_pos_pos): the diagonals must be
+/// given in the same order and with the same element order as that function returns them.
+///
+/// # Arguments
+///
+/// * `diagonals` - The owned diagonals of the matrix.
+/// * `rows` - The number of rows of the matrix.
+/// * `cols` - The number of columns of the matrix.
+///
+/// # Returns
+///
+/// A `rows` x `cols` matrix as a `Vec<Vec<T>>`.
+///
+/// # Panics
+///
+/// Panics if the number of diagonals or the length of any diagonal does not match a
+/// `rows` x `cols` matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::from_diagonal_pos_pos;
+///
+/// let diagonals = vec![
+///     vec![7],
+///     vec![4, 8],
+///     vec![1, 5, 9],
+///     vec![2, 6],
+///     vec![3],
+/// ];
+///
+/// let result = from_diagonal_pos_pos(diagonals, 3, 3);
+/// assert_eq!(result, vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ]);
+/// ```
+pub fn from_diagonal_pos_pos<T>(diagonals: Vec<Vec<T>>, rows: usize, cols: usize) -> Vec<Vec<T>> {
+    assert_diagonal_lengths(&diagonals, rows, cols, |i| {
+        index::pos_pos_start(rows, cols, i).1
+    });
+    index::reassemble(rows, cols, diagonals, |row, col| {
+        index::pos_pos_index(rows, row, col)
+    })
+}
+
+/// Rebuilds a matrix from its diagonals with positive and negative slope.
+///
+/// This is the inverse of [`diagonal_pos_neg`](crate::diagonal_pos_neg): the diagonals must be
+/// given in the same order and with the same element order as that function returns them.
+///
+/// # Arguments
+///
+/// * `diagonals` - The owned diagonals of the matrix.
+/// * `rows` - The number of rows of the matrix.
+/// * `cols` - The number of columns of the matrix.
+///
+/// # Returns
+///
+/// A `rows` x `cols` matrix as a `Vec<Vec<T>>`.
+///
+/// # Panics
+///
+/// Panics if the number of diagonals or the length of any diagonal does not match a
+/// `rows` x `cols` matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::from_diagonal_pos_neg;
+///
+/// let diagonals = vec![
+///     vec![1],
+///     vec![2, 4],
+///     vec![3, 5, 7],
+///     vec![6, 8],
+///     vec![9],
+/// ];
+///
+/// let result = from_diagonal_pos_neg(diagonals, 3, 3);
+/// assert_eq!(result, vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ]);
+/// ```
+pub fn from_diagonal_pos_neg<T>(diagonals: Vec<Vec<T>>, rows: usize, cols: usize) -> Vec<Vec<T>> {
+    assert_diagonal_lengths(&diagonals, rows, cols, |i| {
+        index::pos_neg_start(rows, cols, i).1
+    });
+    index::reassemble(rows, cols, diagonals, index::pos_neg_index)
+}
+
+/// Builds a `rows` x `cols` matrix from a set of diagonals identified by their offset.
+///
+/// The offset of a diagonal is `k = column - row`: `0` is the main diagonal, positive offsets
+/// are above it and negative offsets below it. Every diagonal runs from its top-left end to its
+/// bottom-right end. Values beyond the end of a diagonal are ignored, which means infinite
+/// iterators such as [`std::iter::repeat`] can be used; cells that receive no value keep `fill`.
+///
+/// # Arguments
+///
+/// * `spec` - Pairs of diagonal offsets and the values to place along them.
+/// * `rows` - The number of rows of the matrix.
+/// * `cols` - The number of columns of the matrix.
+/// * `fill` - The value used for all cells that are not covered by `spec`.
+///
+/// # Returns
+///
+/// A `rows` x `cols` matrix as a `Vec<Vec<T>>`.
+///
+/// # Panics
+///
+/// Panics if an offset does not name a diagonal of a `rows` x `cols` matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::matrix_from_diagonals;
+/// use std::iter::repeat;
+///
+/// // A tridiagonal matrix.
+/// let result = matrix_from_diagonals(
+///     [(-1, repeat(-1)), (0, repeat(2)), (1, repeat(-1))],
+///     4,
+///     4,
+///     0,
+/// );
+/// assert_eq!(result, vec![
+///     vec![2, -1, 0, 0],
+///     vec![-1, 2, -1, 0],
+///     vec![0, -1, 2, -1],
+///     vec![0, 0, -1, 2],
+/// ]);
+/// ```
+pub fn matrix_from_diagonals<Spec, Values, T>(
+    spec: Spec,
+    rows: usize,
+    cols: usize,
+    fill: T,
+) -> Vec<Vec<T>>
+where
+    Spec: IntoIterator<Item = (isize, Values)>,
+    Values: IntoIterator<Item = T>,
+    T: Clone,
+{
+    place_diagonals(spec, rows, cols, fill)
+        .unwrap_or_else(|offset| panic!("offset {offset} is outside of a {rows}x{cols} matrix"))
+}
+
+/// Builds the matrix of [`matrix_from_diagonals`], or returns the first offset that does not name
+/// a diagonal of a `rows` x `cols` matrix.
+pub(crate) fn place_diagonals<Spec, Values, T>(
+    spec: Spec,
+    rows: usize,
+    cols: usize,
+    fill: T,
+) -> Result<Vec<Vec<T>>, isize>
+where
+    Spec: IntoIterator<Item = (isize, Values)>,
+    Values: IntoIterator<Item = T>,
+    T: Clone,
+{
+    let mut matrix = vec![vec![fill; cols]; rows];
+
+    for (offset, values) in spec {
+        let (x, y) = if offset >= 0 {
+            (0, offset.unsigned_abs())
+        } else {
+            (offset.unsigned_abs(), 0)
+        };
+        if x >= rows || y >= cols {
+            return Err(offset);
+        }
+
+        let len = (rows - x).min(cols - y);
+        for (i, value) in values.into_iter().take(len).enumerate() {
+            matrix[x + i][y + i] = value;
+        }
+    }
+
+    Ok(matrix)
+}
+
+/// Panics unless `diagonals` has exactly the diagonal lengths of a `rows` x `cols` matrix.
+fn assert_diagonal_lengths<T>(
+    diagonals: &[Vec<T>],
+    rows: usize,
+    cols: usize,
+    len_of: impl Fn(usize) -> usize,
+) {
+    assert_eq!(
+        diagonals.len(),
+        index::diagonal_count(rows, cols),
+        "wrong number of diagonals for a {rows}x{cols} matrix"
+    );
+    for (i, diagonal) in diagonals.iter().enumerate() {
+        assert_eq!(
+            diagonal.len(),
+            len_of(i),
+            "diagonal {i} has the wrong length"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::owned;
+    use crate::{diagonal_pos_neg, diagonal_pos_pos};
+
+    #[test]
+    fn pos_pos_round_trip() {
+        let matrix = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let diagonals = owned(diagonal_pos_pos(&matrix));
+        assert_eq!(from_diagonal_pos_pos(diagonals, 2, 3), matrix);
+
+        let matrix = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
+        let diagonals = owned(diagonal_pos_pos(&matrix));
+        assert_eq!(from_diagonal_pos_pos(diagonals, 3, 2), matrix);
+    }
+
+    #[test]
+    fn pos_neg_round_trip() {
+        let matrix = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let diagonals = owned(diagonal_pos_neg(&matrix));
+        assert_eq!(from_diagonal_pos_neg(diagonals, 2, 3), matrix);
+
+        let matrix = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
+        let diagonals = owned(diagonal_pos_neg(&matrix));
+        assert_eq!(from_diagonal_pos_neg(diagonals, 3, 2), matrix);
+    }
+
+    #[test]
+    fn from_empty() {
+        assert_eq!(
+            from_diagonal_pos_pos::<i32>(vec![], 0, 0),
+            Vec::<Vec<i32>>::new()
+        );
+        assert_eq!(
+            from_diagonal_pos_neg::<i32>(vec![], 2, 0),
+            vec![Vec::<i32>::new(), vec![]]
+        );
+    }
+
+    #[test]
+    fn banded_rectangular() {
+        let result = matrix_from_diagonals([(0, vec![1, 2]), (-2, vec![3, 4, 5])], 3, 4, 0);
+        assert_eq!(
+            result,
+            vec![vec![1, 0, 0, 0], vec![0, 2, 0, 0], vec![3, 0, 0, 0]]
+        );
+
+        let result = matrix_from_diagonals(vec![(3, vec!['a']), (1, vec!['b'; 9])], 2, 4, '.');
+        assert_eq!(
+            result,
+            vec![vec!['.', 'b', '.', 'a'], vec!['.', '.', 'b', '.']]
+        );
+    }
+
+    #[test]
+    fn banded_empty_spec() {
+        let spec = Vec::<(isize, Vec<u8>)>::new;
+        assert_eq!(
+            matrix_from_diagonals(spec(), 2, 2, 7),
+            vec![vec![7, 7], vec![7, 7]]
+        );
+        assert_eq!(
+            matrix_from_diagonals(spec(), 0, 0, 7),
+            Vec::<Vec<u8>>::new()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn banded_offset_out_of_range() {
+        matrix_from_diagonals([(-3, vec![1])], 3, 3, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_wrong_count() {
+        from_diagonal_pos_pos(vec![vec![1], vec![2, 3]], 2, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_wrong_length() {
+        from_diagonal_pos_neg(vec![vec![1], vec![2], vec![3, 4]], 2, 2);
+    }
+}
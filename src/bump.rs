@@ -0,0 +1,196 @@
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
+
+use crate::{lines_iter, Direction};
+
+/// Traverses the lines of a matrix in the given direction and stores them in the arena `bump`.
+///
+/// Both the outer vector and every line are allocated from `bump`, with exactly the capacity
+/// they need, so repeated traversals cost no global allocations once the arena has grown, and
+/// the results of a whole search node can be dropped at once with [`Bump::reset`]. With the
+/// `allocator-api` feature, `&Bump` is also an allocator for `lines_in` and the `*_in`
+/// shorthands, which return standard vectors instead.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `direction` - The traversal to perform.
+/// * `bump` - The arena to allocate the result from.
+///
+/// # Returns
+///
+/// The lines, in the same order as the function named by `direction` returns them.
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::Bump;
+/// use diagonal::{lines_in_bump, Direction};
+///
+/// let matrix = [[1, 2], [3, 4]];
+/// let bump = Bump::new();
+///
+/// let result = lines_in_bump(&matrix, Direction::StraightY, &bump);
+/// assert_eq!(result, [[&1, &3], [&2, &4]]);
+/// ```
+pub fn lines_in_bump<'a, 'bump, Matrix, Row, T>(
+    matrix: &'a Matrix,
+    direction: Direction,
+    bump: &'bump Bump,
+) -> BumpVec<'bump, BumpVec<'bump, &'a T>>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+{
+    let lines = lines_iter(matrix, direction);
+    let mut result = BumpVec::with_capacity_in(lines.len(), bump);
+    for line in lines {
+        let mut collected = BumpVec::with_capacity_in(line.len(), bump);
+        collected.extend(line);
+        result.push(collected);
+    }
+    result
+}
+
+/// Extracts diagonals with positive slope, like [`diagonal_pos_pos`](crate::diagonal_pos_pos),
+/// allocating them from `bump` (see [`lines_in_bump`]).
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::Bump;
+/// use diagonal::diagonal_pos_pos_in_bump;
+///
+/// let mut bump = Bump::new();
+/// let board = [[0u8; 8]; 8];
+///
+/// for _node in 0..1000 {
+///     let diagonals = diagonal_pos_pos_in_bump(&board, &bump);
+///     assert_eq!(diagonals.len(), 15);
+///     drop(diagonals);
+///     bump.reset();
+/// }
+/// ```
+pub fn diagonal_pos_pos_in_bump<'a, 'bump, Matrix, Row, T>(
+    matrix: &'a Matrix,
+    bump: &'bump Bump,
+) -> BumpVec<'bump, BumpVec<'bump, &'a T>>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+{
+    lines_in_bump(matrix, Direction::DiagonalPosPos, bump)
+}
+
+/// Extracts diagonals with positive and negative slope, like
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg), allocating them from `bump` (see
+/// [`lines_in_bump`]).
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::Bump;
+/// use diagonal::diagonal_pos_neg_in_bump;
+///
+/// let matrix = [[1, 2], [3, 4]];
+/// let bump = Bump::new();
+/// assert_eq!(diagonal_pos_neg_in_bump(&matrix, &bump), [&[&1][..], &[&2, &3], &[&4]]);
+/// ```
+pub fn diagonal_pos_neg_in_bump<'a, 'bump, Matrix, Row, T>(
+    matrix: &'a Matrix,
+    bump: &'bump Bump,
+) -> BumpVec<'bump, BumpVec<'bump, &'a T>>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+{
+    lines_in_bump(matrix, Direction::DiagonalPosNeg, bump)
+}
+
+/// Extracts the rows, like [`straight_x`](crate::straight_x), allocating them from `bump`
+/// (see [`lines_in_bump`]).
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::Bump;
+/// use diagonal::straight_x_in_bump;
+///
+/// let matrix = [[1, 2], [3, 4]];
+/// let bump = Bump::new();
+/// assert_eq!(straight_x_in_bump(&matrix, &bump), [[&1, &2], [&3, &4]]);
+/// ```
+pub fn straight_x_in_bump<'a, 'bump, Matrix, Row, T>(
+    matrix: &'a Matrix,
+    bump: &'bump Bump,
+) -> BumpVec<'bump, BumpVec<'bump, &'a T>>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+{
+    lines_in_bump(matrix, Direction::StraightX, bump)
+}
+
+/// Extracts the columns, like [`straight_y`](crate::straight_y), allocating them from `bump`
+/// (see [`lines_in_bump`]).
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::Bump;
+/// use diagonal::straight_y_in_bump;
+///
+/// let matrix = [[1, 2], [3, 4]];
+/// let bump = Bump::new();
+/// assert_eq!(straight_y_in_bump(&matrix, &bump), [[&1, &3], [&2, &4]]);
+/// ```
+pub fn straight_y_in_bump<'a, 'bump, Matrix, Row, T>(
+    matrix: &'a Matrix,
+    bump: &'bump Bump,
+) -> BumpVec<'bump, BumpVec<'bump, &'a T>>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+{
+    lines_in_bump(matrix, Direction::StraightY, bump)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_eager_traversals() {
+        let matrix: Vec<Vec<i32>> = (0..3)
+            .map(|r| (0..4).map(|c| r * 4 + c).collect())
+            .collect();
+        let bump = Bump::new();
+        for direction in Direction::ALL {
+            let lines = lines_in_bump(&matrix, direction, &bump);
+            let eager = lines_iter(&matrix, direction).map(|line| line.collect::<Vec<_>>());
+            assert!(lines.iter().map(|line| line.to_vec()).eq(eager));
+            assert!(lines.iter().all(|line| line.capacity() == line.len()));
+        }
+    }
+
+    #[test]
+    fn reset_reuses_the_arena() {
+        let board = [[0u8; 8]; 8];
+        let mut bump = Bump::new();
+        // After the first reset the arena keeps a single chunk, which fits a whole traversal.
+        drop(diagonal_pos_neg_in_bump(&board, &bump));
+        bump.reset();
+        drop(diagonal_pos_neg_in_bump(&board, &bump));
+        let allocated = bump.allocated_bytes();
+        for _ in 0..100 {
+            bump.reset();
+            let diagonals = diagonal_pos_neg_in_bump(&board, &bump);
+            assert_eq!(diagonals.len(), 15);
+        }
+        assert_eq!(bump.allocated_bytes(), allocated);
+
+        let empty: Vec<Vec<u8>> = vec![];
+        assert!(straight_x_in_bump(&empty, &bump).is_empty());
+    }
+}
@@ -0,0 +1,365 @@
+use std::iter::Sum;
+use std::ops::Sub;
+
+/// Checks whether a matrix is symmetric about its main diagonal.
+///
+/// A matrix is symmetric if it is square and `matrix[i][j] == matrix[j][i]` for every `i` and
+/// `j`. Matrices that are not square are never symmetric; an empty matrix is.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// `true` if the matrix is square and equal to its transpose.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::is_symmetric;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![2, 5, 6],
+///     vec![3, 6, 9],
+/// ];
+/// assert!(is_symmetric(&matrix));
+///
+/// let matrix = vec![
+///     vec![1, 2],
+///     vec![3, 4],
+/// ];
+/// assert!(!is_symmetric(&matrix));
+/// ```
+pub fn is_symmetric<Matrix, Row, T>(matrix: &Matrix) -> bool
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: PartialEq,
+{
+    let matrix = matrix.as_ref();
+    is_reflection_of_itself(matrix, |_, i, j| (j, i))
+}
+
+/// Checks whether a matrix is persymmetric, i.e. symmetric about its anti-diagonal.
+///
+/// A matrix is persymmetric if it is square and
+/// `matrix[i][j] == matrix[n - 1 - j][n - 1 - i]` for every `i` and `j`. Matrices that are not
+/// square are never persymmetric; an empty matrix is.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// `true` if the matrix is square and equal to its reflection across the anti-diagonal.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::is_persymmetric;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 2],
+///     vec![7, 4, 1],
+/// ];
+/// assert!(is_persymmetric(&matrix));
+///
+/// let matrix = vec![
+///     vec![1, 2],
+///     vec![3, 4],
+/// ];
+/// assert!(!is_persymmetric(&matrix));
+/// ```
+pub fn is_persymmetric<Matrix, Row, T>(matrix: &Matrix) -> bool
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: PartialEq,
+{
+    let matrix = matrix.as_ref();
+    is_reflection_of_itself(matrix, |n, i, j| (n - 1 - j, n - 1 - i))
+}
+
+/// Checks whether a matrix is block diagonal with the given block sizes.
+///
+/// The blocks are laid out corner to corner along the main diagonal, as in
+/// [`diagonal_blocks`](crate::diagonal_blocks), and every cell outside of them must equal
+/// `zero`. The cells inside the blocks can hold anything, including `zero`. Matrices that are
+/// not square, or whose side length differs from the sum of the block sizes, are never block
+/// diagonal.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `block_sizes` - The side length of every block, from the top-left to the bottom-right.
+/// * `zero` - The value every cell outside of the blocks must have.
+///
+/// # Returns
+///
+/// `true` if the matrix is square, the blocks cover its diagonal and all other cells are `zero`.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::is_block_diagonal;
+///
+/// let matrix = vec![
+///     vec![1, 2, 0],
+///     vec![3, 4, 0],
+///     vec![0, 0, 5],
+/// ];
+/// assert!(is_block_diagonal(&matrix, &[2, 1], &0));
+/// assert!(!is_block_diagonal(&matrix, &[1, 2], &0));
+/// assert!(!is_block_diagonal(&matrix, &[2], &0));
+/// ```
+pub fn is_block_diagonal<Matrix, Row, T>(matrix: &Matrix, block_sizes: &[usize], zero: &T) -> bool
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: PartialEq,
+{
+    let matrix = matrix.as_ref();
+    let n = matrix.len();
+    if matrix.iter().any(|row| row.as_ref().len() != n) {
+        return false;
+    }
+
+    // The block every row and column belongs to.
+    let block_of: Vec<usize> = block_sizes
+        .iter()
+        .enumerate()
+        .flat_map(|(block, &size)| std::iter::repeat_n(block, size))
+        .take(n + 1)
+        .collect();
+    if block_of.len() != n {
+        return false;
+    }
+
+    matrix.iter().enumerate().all(|(i, row)| {
+        row.as_ref()
+            .iter()
+            .enumerate()
+            .all(|(j, value)| block_of[i] == block_of[j] || value == zero)
+    })
+}
+
+/// Checks whether a matrix is (weakly) diagonally dominant.
+///
+/// A matrix is diagonally dominant if it is square and, in every row, the absolute value of the
+/// element on the main diagonal is at least the sum of the absolute values of the other
+/// elements. Matrices that are not square are never diagonally dominant; an empty matrix is.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// `true` if the matrix is square and every row is dominated by its diagonal element.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::is_diagonally_dominant;
+///
+/// let matrix = vec![
+///     vec![3, -2, 1],
+///     vec![1, -3, 2],
+///     vec![-1, 2, 4],
+/// ];
+/// assert!(is_diagonally_dominant(&matrix));
+///
+/// let matrix = vec![
+///     vec![-2, 2, 1],
+///     vec![1, 3, 2],
+///     vec![1, -2, 0],
+/// ];
+/// assert!(!is_diagonally_dominant(&matrix));
+/// ```
+pub fn is_diagonally_dominant<Matrix, Row, T>(matrix: &Matrix) -> bool
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: Copy + Sum + Sub<Output = T> + PartialOrd,
+{
+    let matrix = matrix.as_ref();
+    is_dominated_by_diagonal(matrix, |diagonal, rest| diagonal >= rest)
+}
+
+/// Checks whether a matrix is strictly diagonally dominant.
+///
+/// A matrix is strictly diagonally dominant if it is square and, in every row, the absolute
+/// value of the element on the main diagonal is greater than the sum of the absolute values of
+/// the other elements. Such matrices are nonsingular, and iterative solvers such as the Jacobi
+/// and Gauss-Seidel methods converge for them. Matrices that are not square are never strictly
+/// diagonally dominant; an empty matrix is.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// `true` if the matrix is square and every row is strictly dominated by its diagonal element.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{is_diagonally_dominant, is_strictly_diagonally_dominant};
+///
+/// let matrix = vec![
+///     vec![3.0, -2.0, 1.0],
+///     vec![1.0, -3.0, 2.0],
+///     vec![-1.0, 2.0, 4.0],
+/// ];
+/// assert!(is_diagonally_dominant(&matrix));
+/// // The second row only has |-3| = |1| + |2|.
+/// assert!(!is_strictly_diagonally_dominant(&matrix));
+/// ```
+pub fn is_strictly_diagonally_dominant<Matrix, Row, T>(matrix: &Matrix) -> bool
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: Copy + Sum + Sub<Output = T> + PartialOrd,
+{
+    let matrix = matrix.as_ref();
+    is_dominated_by_diagonal(matrix, |diagonal, rest| diagonal > rest)
+}
+
+/// Checks that `matrix` is square and `dominates` holds in every row for the absolute value of
+/// the diagonal element and the sum of the absolute values of the other elements.
+fn is_dominated_by_diagonal<Row, T>(matrix: &[Row], dominates: impl Fn(T, T) -> bool) -> bool
+where
+    Row: AsRef<[T]>,
+    T: Copy + Sum + Sub<Output = T> + PartialOrd,
+{
+    let n = matrix.len();
+    if matrix.iter().any(|row| row.as_ref().len() != n) {
+        return false;
+    }
+
+    let zero: T = std::iter::empty().sum();
+    let abs = |value: T| if value < zero { zero - value } else { value };
+    matrix.iter().enumerate().all(|(i, row)| {
+        let row = row.as_ref();
+        let rest: T = row
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .map(|(_, &value)| abs(value))
+            .sum();
+        dominates(abs(row[i]), rest)
+    })
+}
+
+/// Checks that `matrix` is square and every cell equals the cell `reflect` maps it to.
+fn is_reflection_of_itself<Row, T>(
+    matrix: &[Row],
+    reflect: impl Fn(usize, usize, usize) -> (usize, usize),
+) -> bool
+where
+    Row: AsRef<[T]>,
+    T: PartialEq,
+{
+    let n = matrix.len();
+    if matrix.iter().any(|row| row.as_ref().len() != n) {
+        return false;
+    }
+
+    (0..n).all(|i| {
+        (0..n).all(|j| {
+            let (x, y) = reflect(n, i, j);
+            matrix[i].as_ref()[j] == matrix[x].as_ref()[y]
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symmetric() {
+        let matrix = vec![vec![1, 7], vec![7, 4]];
+        assert!(is_symmetric(&matrix));
+        assert!(!is_persymmetric(&matrix));
+
+        let matrix = [[1, 2, 3], [2, 4, 5], [3, 5, 6]];
+        assert!(is_symmetric(&matrix));
+    }
+
+    #[test]
+    fn persymmetric() {
+        let matrix = vec![vec![1, 2], vec![3, 1]];
+        assert!(is_persymmetric(&matrix));
+        assert!(!is_symmetric(&matrix));
+
+        let matrix = [[1, 2, 3, 4], [5, 6, 7, 3], [8, 9, 6, 2], [0, 8, 5, 1]];
+        assert!(is_persymmetric(&matrix));
+    }
+
+    #[test]
+    fn not_square() {
+        let matrix = [[1, 1, 1], [1, 1, 1]];
+        assert!(!is_symmetric(&matrix));
+        assert!(!is_persymmetric(&matrix));
+
+        let matrix = vec![vec![1, 1], vec![1]];
+        assert!(!is_symmetric(&matrix));
+        assert!(!is_persymmetric(&matrix));
+    }
+
+    #[test]
+    fn block_diagonal() {
+        let matrix = [[1, 0, 0, 0], [0, 2, 3, 0], [0, 4, 0, 0], [0, 0, 0, 6]];
+        assert!(is_block_diagonal(&matrix, &[1, 2, 1], &0));
+        assert!(is_block_diagonal(&matrix, &[1, 0, 2, 1], &0));
+        assert!(is_block_diagonal(&matrix, &[4], &0));
+        assert!(!is_block_diagonal(&matrix, &[1, 1, 2], &0));
+        assert!(!is_block_diagonal(&matrix, &[1, 2, 1], &1));
+        assert!(!is_block_diagonal(&matrix, &[1, 2, 2], &0));
+        assert!(!is_block_diagonal(&matrix, &[1, 2], &0));
+        assert!(!is_block_diagonal(&[[0, 0]], &[1], &0));
+        assert!(!is_block_diagonal(&[[0]], &[usize::MAX, 1], &0));
+        assert!(is_block_diagonal(&Vec::<Vec<u8>>::new(), &[], &0));
+    }
+
+    #[test]
+    fn diagonally_dominant() {
+        let matrix = [[4, 1, -2], [0, -1, 0], [1, 1, 2]];
+        assert!(is_diagonally_dominant(&matrix));
+        assert!(!is_strictly_diagonally_dominant(&matrix));
+
+        let matrix = [[4, 1, -2], [0, -1, 0], [1, 0, 2]];
+        assert!(is_strictly_diagonally_dominant(&matrix));
+
+        let matrix = [[1u32, 1], [0, 1]];
+        assert!(is_diagonally_dominant(&matrix));
+        assert!(!is_strictly_diagonally_dominant(&matrix));
+
+        let matrix = [[1.0, 0.5], [2.0, -1.5]];
+        assert!(!is_diagonally_dominant(&matrix));
+
+        assert!(!is_diagonally_dominant(&[[1, 0, 0]]));
+        assert!(is_strictly_diagonally_dominant(&Vec::<Vec<i8>>::new()));
+    }
+
+    #[test]
+    fn trivial() {
+        let matrix: Vec<Vec<usize>> = vec![];
+        assert!(is_symmetric(&matrix));
+        assert!(is_persymmetric(&matrix));
+
+        let matrix = [[f64::NAN]];
+        assert!(!is_symmetric(&matrix));
+    }
+}
@@ -0,0 +1,1237 @@
+//! # Checked Operations Module
+//!
+//! This module provides counterparts of the crate's functions that validate their input and
+//! return a [`DiagonalError`] instead of panicking or silently ignoring cells. They are meant for
+//! matrices that come from untrusted sources, such as a request body in a server.
+//!
+//! A matrix is valid if all of its rows have the same length, and if it has rows, they are not
+//! empty. After validation every function behaves exactly like its unchecked counterpart.
+//!
+//! Apart from the handful listed below, every function that can panic on a nested slice matrix
+//! or on its other arguments has a counterpart here, as do the traversals, which would silently
+//! drop the cells of ragged rows. The remaining slice-taking functions, such as
+//! [`sort_diagonals`](crate::sort_diagonals) or
+//! [`diagonal_sums_pos_pos`](crate::diagonal_sums_pos_pos), cannot fail on a matrix that has passed
+//! [`shape`], so validate an untrusted matrix once and then pass it to any of them. Shape-only
+//! functions, such as [`diagonal_labels`](crate::diagonal_labels) or
+//! [`line_boundaries`](crate::line_boundaries), cannot fail on dimensions that have passed
+//! [`diagonal_count`].
+//!
+//! These functions stay unchecked, and document when they panic:
+//!
+//! - [`map_diagonals_pos_pos`](crate::map_diagonals_pos_pos) and
+//!   [`map_diagonals_pos_neg`](crate::map_diagonals_pos_neg), which only panic if their closure
+//!   returns the wrong number of values.
+//! - [`unrot45`](crate::unrot45), whose input is meant to come from [`rot45`](crate::rot45).
+//! - The [`bits`](crate::bits) traversals, for boards wider or taller than 64 cells.
+//! - [`rail_fence_encode`](crate::cipher::rail_fence_encode) and
+//!   [`rail_fence_decode`](crate::cipher::rail_fence_decode), for zero rails.
+//! - [`ndim::lines`](crate::ndim::lines) and [`ndim::line_offsets`](crate::ndim::line_offsets),
+//!   for mismatched shapes, strides or directions.
+//! - The constructors of the [`flat`](crate::flat) layouts and `StridedView::submatrix`, for
+//!   data that does not fit the shape.
+//! - [`Grid::new`](crate::grid::Grid::new), for ragged rows or origins too close to `i64::MAX`.
+//! - [`Bounds::shape`](crate::hash_grid::Bounds::shape) and the [`hash_grid`](crate::hash_grid)
+//!   traversals, for bounds that span more than `usize::MAX` rows or columns.
+//! - [`hex::lines`](crate::hex::lines), for coordinates whose `s` overflows.
+//! - [`DiagonalStream::push_rows`](crate::stream::DiagonalStream::push_rows), for rows of the
+//!   wrong length.
+//!
+//! ### Example
+//!
+//! ```rust
+//! use diagonal::checked::diagonal_pos_neg;
+//! use diagonal::DiagonalError;
+//!
+//! let matrix = vec![vec![1, 2], vec![3, 4]];
+//! assert_eq!(diagonal_pos_neg(&matrix), Ok(vec![vec![&1], vec![&2, &3], vec![&4]]));
+//!
+//! let ragged = vec![vec![1, 2], vec![3]];
+//! assert_eq!(
+//!     diagonal_pos_neg(&ragged),
+//!     Err(DiagonalError::Ragged { row: 1, expected: 2, found: 1 }),
+//! );
+//! ```
+
+use std::iter::Sum;
+use std::ops::{Add, Mul, Sub};
+
+use crate::{index, DiagonalError, LineDiff, SparseDiagonal, Triplet, WindowDiagonals};
+
+/// The results of a windowed reduction, grouped by diagonal and paired with their coordinate.
+type Windowed<U> = Vec<Vec<((usize, usize), U)>>;
+
+/// Validates a matrix and returns its `(rows, columns)` shape.
+///
+/// # Errors
+///
+/// Returns [`DiagonalError::Ragged`] if a row differs in length from the first row,
+/// [`DiagonalError::EmptyRows`] if the matrix has rows but they are empty, and
+/// [`DiagonalError::DimensionOverflow`] if the matrix has more cells than fit in a `usize`,
+/// which only matrices of zero-sized rows or elements can have.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::shape;
+/// use diagonal::DiagonalError;
+///
+/// assert_eq!(shape(&[[1, 2, 3], [4, 5, 6]]), Ok((2, 3)));
+/// assert_eq!(shape(&Vec::<Vec<u8>>::new()), Ok((0, 0)));
+/// assert_eq!(shape(&[[0u8; 0]]), Err(DiagonalError::EmptyRows));
+/// ```
+pub fn shape<Matrix: AsRef<[Row]> + ?Sized, Row: AsRef<[T]>, T>(
+    matrix: &Matrix,
+) -> Result<(usize, usize), DiagonalError> {
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    if let Some((row, found)) = matrix
+        .iter()
+        .map(|row| row.as_ref().len())
+        .enumerate()
+        .find(|&(_, len)| len != cols)
+    {
+        return Err(DiagonalError::Ragged {
+            row,
+            expected: cols,
+            found,
+        });
+    }
+    if rows > 0 && cols == 0 {
+        return Err(DiagonalError::EmptyRows);
+    }
+    // Every line and index of a matrix whose cells can be counted fits in a `usize` as well.
+    if rows.checked_mul(cols).is_none() {
+        return Err(DiagonalError::DimensionOverflow { rows, cols });
+    }
+    Ok((rows, cols))
+}
+
+/// Validates the shape of a matrix to be built and returns its number of diagonals.
+///
+/// Functions that only take a shape, such as [`diagonal_labels`](crate::diagonal_labels) or
+/// [`wavefront`](crate::wavefront), trust it to be sensible. Run untrusted dimensions through
+/// this function first, and every cell, line and index of the shape fits in a `usize`.
+///
+/// # Errors
+///
+/// Returns [`DiagonalError::EmptyRows`] if `rows` is positive but `cols` is zero, and
+/// [`DiagonalError::DimensionOverflow`] if `rows * cols` or `rows + cols` does not fit in a
+/// `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::diagonal_count;
+/// use diagonal::DiagonalError;
+///
+/// assert_eq!(diagonal_count(3, 4), Ok(6));
+/// assert_eq!(diagonal_count(0, 4), Ok(0));
+/// assert_eq!(
+///     diagonal_count(1 << 40, 1 << 40),
+///     Err(DiagonalError::DimensionOverflow { rows: 1 << 40, cols: 1 << 40 }),
+/// );
+/// ```
+pub fn diagonal_count(rows: usize, cols: usize) -> Result<usize, DiagonalError> {
+    if rows > 0 && cols == 0 {
+        return Err(DiagonalError::EmptyRows);
+    }
+    match rows.checked_mul(cols).and(rows.checked_add(cols)) {
+        Some(_) => Ok(index::diagonal_count(rows, cols)),
+        None => Err(DiagonalError::DimensionOverflow { rows, cols }),
+    }
+}
+
+/// Checks that `diagonals` are the diagonals of a `rows` x `cols` matrix.
+fn check_diagonals<T>(
+    diagonals: &[Vec<T>],
+    rows: usize,
+    cols: usize,
+    len_of: impl Fn(usize) -> usize,
+) -> Result<(), DiagonalError> {
+    let expected = diagonal_count(rows, cols)?;
+    if diagonals.len() != expected {
+        return Err(DiagonalError::DiagonalCount {
+            expected,
+            found: diagonals.len(),
+        });
+    }
+    for (index, diagonal) in diagonals.iter().enumerate() {
+        let expected = len_of(index);
+        if diagonal.len() != expected {
+            return Err(DiagonalError::DiagonalLength {
+                index,
+                expected,
+                found: diagonal.len(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checked counterpart of [`diagonal_pos_pos`](crate::diagonal_pos_pos).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`].
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::diagonal_pos_pos;
+///
+/// let matrix = [[1, 2], [3, 4]];
+/// assert_eq!(diagonal_pos_pos(&matrix), Ok(vec![vec![&3], vec![&1, &4], vec![&2]]));
+/// ```
+pub fn diagonal_pos_pos<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Result<Vec<Vec<&'a T>>, DiagonalError> {
+    shape(matrix)?;
+    Ok(crate::diagonal_pos_pos(matrix))
+}
+
+/// Checked counterpart of [`diagonal_pos_neg`](crate::diagonal_pos_neg).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`].
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::diagonal_pos_neg;
+/// use diagonal::DiagonalError;
+///
+/// let matrix = vec![vec![1, 2], vec![3, 4, 5]];
+/// assert_eq!(
+///     diagonal_pos_neg(&matrix),
+///     Err(DiagonalError::Ragged { row: 1, expected: 2, found: 3 }),
+/// );
+/// ```
+pub fn diagonal_pos_neg<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Result<Vec<Vec<&'a T>>, DiagonalError> {
+    shape(matrix)?;
+    Ok(crate::diagonal_pos_neg(matrix))
+}
+
+/// Checked counterpart of [`straight_x`](crate::straight_x).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`].
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::straight_x;
+///
+/// let matrix = [[1, 2], [3, 4]];
+/// assert_eq!(straight_x(&matrix), Ok(vec![vec![&1, &2], vec![&3, &4]]));
+/// ```
+pub fn straight_x<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Result<Vec<Vec<&'a T>>, DiagonalError> {
+    shape(matrix)?;
+    Ok(crate::straight_x(matrix))
+}
+
+/// Checked counterpart of [`straight_y`](crate::straight_y).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`].
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::straight_y;
+///
+/// let matrix = [[1, 2], [3, 4]];
+/// assert_eq!(straight_y(&matrix), Ok(vec![vec![&1, &3], vec![&2, &4]]));
+/// ```
+pub fn straight_y<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Result<Vec<Vec<&'a T>>, DiagonalError> {
+    shape(matrix)?;
+    Ok(crate::straight_y(matrix))
+}
+
+/// Checked counterpart of [`from_diagonal_pos_pos`](crate::from_diagonal_pos_pos).
+///
+/// # Errors
+///
+/// Returns [`DiagonalError::EmptyRows`] if `rows` is positive but `cols` is zero,
+/// [`DiagonalError::DimensionOverflow`] if the shape is too large, and
+/// [`DiagonalError::DiagonalCount`] or [`DiagonalError::DiagonalLength`] if the diagonals do not
+/// fit the shape.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::from_diagonal_pos_pos;
+/// use diagonal::DiagonalError;
+///
+/// let diagonals = vec![vec![3], vec![1, 4], vec![2]];
+/// assert_eq!(from_diagonal_pos_pos(diagonals.clone(), 2, 2), Ok(vec![vec![1, 2], vec![3, 4]]));
+/// assert_eq!(
+///     from_diagonal_pos_pos(diagonals, 3, 1),
+///     Err(DiagonalError::DiagonalLength { index: 1, expected: 1, found: 2 }),
+/// );
+/// ```
+pub fn from_diagonal_pos_pos<T>(
+    diagonals: Vec<Vec<T>>,
+    rows: usize,
+    cols: usize,
+) -> Result<Vec<Vec<T>>, DiagonalError> {
+    check_diagonals(&diagonals, rows, cols, |i| {
+        index::pos_pos_start(rows, cols, i).1
+    })?;
+    Ok(crate::from_diagonal_pos_pos(diagonals, rows, cols))
+}
+
+/// Checked counterpart of [`from_diagonal_pos_neg`](crate::from_diagonal_pos_neg).
+///
+/// # Errors
+///
+/// Returns [`DiagonalError::EmptyRows`] if `rows` is positive but `cols` is zero,
+/// [`DiagonalError::DimensionOverflow`] if the shape is too large, and
+/// [`DiagonalError::DiagonalCount`] or [`DiagonalError::DiagonalLength`] if the diagonals do not
+/// fit the shape.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::from_diagonal_pos_neg;
+/// use diagonal::DiagonalError;
+///
+/// assert_eq!(
+///     from_diagonal_pos_neg(vec![vec![1], vec![2, 3]], 2, 2),
+///     Err(DiagonalError::DiagonalCount { expected: 3, found: 2 }),
+/// );
+/// assert_eq!(
+///     from_diagonal_pos_neg(Vec::<Vec<u8>>::new(), usize::MAX, 2),
+///     Err(DiagonalError::DimensionOverflow { rows: usize::MAX, cols: 2 }),
+/// );
+/// ```
+pub fn from_diagonal_pos_neg<T>(
+    diagonals: Vec<Vec<T>>,
+    rows: usize,
+    cols: usize,
+) -> Result<Vec<Vec<T>>, DiagonalError> {
+    check_diagonals(&diagonals, rows, cols, |i| {
+        index::pos_neg_start(rows, cols, i).1
+    })?;
+    Ok(crate::from_diagonal_pos_neg(diagonals, rows, cols))
+}
+
+/// Checked counterpart of [`matrix_from_diagonals`](crate::matrix_from_diagonals).
+///
+/// # Errors
+///
+/// Returns [`DiagonalError::EmptyRows`] if `rows` is positive but `cols` is zero,
+/// [`DiagonalError::DimensionOverflow`] if the shape is too large, and
+/// [`DiagonalError::OffsetOutOfRange`] if an offset does not name a diagonal of the shape.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::matrix_from_diagonals;
+/// use diagonal::DiagonalError;
+///
+/// assert_eq!(
+///     matrix_from_diagonals([(1, [5])], 2, 2, 0),
+///     Ok(vec![vec![0, 5], vec![0, 0]]),
+/// );
+/// assert_eq!(
+///     matrix_from_diagonals([(-2, [5])], 2, 2, 0),
+///     Err(DiagonalError::OffsetOutOfRange { offset: -2, rows: 2, cols: 2 }),
+/// );
+/// ```
+pub fn matrix_from_diagonals<Spec, Values, T>(
+    spec: Spec,
+    rows: usize,
+    cols: usize,
+    fill: T,
+) -> Result<Vec<Vec<T>>, DiagonalError>
+where
+    Spec: IntoIterator<Item = (isize, Values)>,
+    Values: IntoIterator<Item = T>,
+    T: Clone,
+{
+    diagonal_count(rows, cols)?;
+    crate::build::place_diagonals(spec, rows, cols, fill)
+        .map_err(|offset| DiagonalError::OffsetOutOfRange { offset, rows, cols })
+}
+
+/// Checked counterpart of [`diff_lines`](crate::diff_lines).
+///
+/// # Errors
+///
+/// Returns an error if either matrix is invalid, see [`shape`], and
+/// [`DiagonalError::ShapeMismatch`] if their shapes differ.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::diff_lines;
+/// use diagonal::DiagonalError;
+///
+/// assert_eq!(diff_lines(&[[1, 2]], &[[1, 2]]), Ok(vec![]));
+/// assert_eq!(
+///     diff_lines(&[[1, 2]], &[[1], [2]]),
+///     Err(DiagonalError::ShapeMismatch { expected: (1, 2), found: (2, 1) }),
+/// );
+/// ```
+pub fn diff_lines<MatrixA, MatrixB, RowA, RowB, T>(
+    a: &MatrixA,
+    b: &MatrixB,
+) -> Result<Vec<LineDiff>, DiagonalError>
+where
+    MatrixA: AsRef<[RowA]> + ?Sized,
+    MatrixB: AsRef<[RowB]> + ?Sized,
+    RowA: AsRef<[T]>,
+    RowB: AsRef<[T]>,
+    T: PartialEq,
+{
+    let expected = shape(a)?;
+    let found = shape(b)?;
+    if expected != found {
+        return Err(DiagonalError::ShapeMismatch { expected, found });
+    }
+    Ok(crate::diff_lines(a, b))
+}
+
+/// Checked counterpart of [`diagonal_difference`](crate::diagonal_difference).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`], and [`DiagonalError::NotSquare`] if
+/// it is not square.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::diagonal_difference;
+/// use diagonal::DiagonalError;
+///
+/// assert_eq!(diagonal_difference(&[[1, 2], [3, 5]]), Ok(1));
+/// assert_eq!(
+///     diagonal_difference(&[[1, 2, 3], [4, 5, 6]]),
+///     Err(DiagonalError::NotSquare { rows: 2, cols: 3 }),
+/// );
+/// ```
+pub fn diagonal_difference<Matrix, Row, T>(matrix: &Matrix) -> Result<T, DiagonalError>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: Copy + Sum + Sub<Output = T> + PartialOrd,
+{
+    square(matrix)?;
+    Ok(crate::diagonal_difference(matrix))
+}
+
+/// Checked counterpart of
+/// [`diagonal_prefix_sums_into_pos_pos`](crate::diagonal_prefix_sums_into_pos_pos).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`], and
+/// [`DiagonalError::ShapeMismatch`] if `output` does not have the same shape.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::diagonal_prefix_sums_into_pos_pos;
+///
+/// let mut output = vec![vec![0; 2]; 2];
+/// assert_eq!(diagonal_prefix_sums_into_pos_pos(&[[1, 2], [3, 4]], &mut output), Ok(()));
+/// assert_eq!(output, vec![vec![1, 2], vec![3, 5]]);
+/// ```
+pub fn diagonal_prefix_sums_into_pos_pos<Matrix, Row, Output, OutputRow, T>(
+    matrix: &Matrix,
+    output: &mut Output,
+) -> Result<(), DiagonalError>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    Output: AsMut<[OutputRow]> + ?Sized,
+    OutputRow: AsMut<[T]>,
+    T: Copy + Add<Output = T>,
+{
+    let (rows, cols) = shape(matrix)?;
+    check_output(rows, cols, output.as_mut())?;
+    crate::diagonal_prefix_sums_into_pos_pos(matrix, output);
+    Ok(())
+}
+
+/// Checked counterpart of
+/// [`diagonal_prefix_sums_into_pos_neg`](crate::diagonal_prefix_sums_into_pos_neg).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`], and
+/// [`DiagonalError::ShapeMismatch`] if `output` does not have the same shape.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::diagonal_prefix_sums_into_pos_neg;
+/// use diagonal::DiagonalError;
+///
+/// let mut output = vec![vec![0; 2], vec![0; 3]];
+/// assert_eq!(
+///     diagonal_prefix_sums_into_pos_neg(&[[1, 2], [3, 4]], &mut output),
+///     Err(DiagonalError::ShapeMismatch { expected: (2, 2), found: (2, 3) }),
+/// );
+/// ```
+pub fn diagonal_prefix_sums_into_pos_neg<Matrix, Row, Output, OutputRow, T>(
+    matrix: &Matrix,
+    output: &mut Output,
+) -> Result<(), DiagonalError>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    Output: AsMut<[OutputRow]> + ?Sized,
+    OutputRow: AsMut<[T]>,
+    T: Copy + Add<Output = T>,
+{
+    let (rows, cols) = shape(matrix)?;
+    check_output(rows, cols, output.as_mut())?;
+    crate::diagonal_prefix_sums_into_pos_neg(matrix, output);
+    Ok(())
+}
+
+/// Checked counterpart of [`convolve_diagonals_pos_pos`](crate::convolve_diagonals_pos_pos).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`], [`DiagonalError::EmptyKernel`] if
+/// `kernel` is empty, and [`DiagonalError::ShapeMismatch`] if `output` does not have the same
+/// shape as the matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::convolve_diagonals_pos_pos;
+/// use diagonal::DiagonalError;
+///
+/// let matrix = [[1, 2], [3, 4]];
+/// let mut output = [[0; 2]; 2];
+/// assert_eq!(convolve_diagonals_pos_pos(&matrix, &[1, 1, 1], &mut output), Ok(()));
+/// assert_eq!(output, [[5, 2], [3, 5]]);
+/// assert_eq!(
+///     convolve_diagonals_pos_pos(&matrix, &[], &mut output),
+///     Err(DiagonalError::EmptyKernel),
+/// );
+/// ```
+pub fn convolve_diagonals_pos_pos<Matrix, Row, Output, OutputRow, T>(
+    matrix: &Matrix,
+    kernel: &[T],
+    output: &mut Output,
+) -> Result<(), DiagonalError>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    Output: AsMut<[OutputRow]> + ?Sized,
+    OutputRow: AsMut<[T]>,
+    T: Copy + Sum + Mul<Output = T>,
+{
+    let (rows, cols) = shape(matrix)?;
+    if kernel.is_empty() {
+        return Err(DiagonalError::EmptyKernel);
+    }
+    check_output(rows, cols, output.as_mut())?;
+    crate::convolve_diagonals_pos_pos(matrix, kernel, output);
+    Ok(())
+}
+
+/// Checked counterpart of [`convolve_diagonals_pos_neg`](crate::convolve_diagonals_pos_neg).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`], [`DiagonalError::EmptyKernel`] if
+/// `kernel` is empty, and [`DiagonalError::ShapeMismatch`] if `output` does not have the same
+/// shape as the matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::convolve_diagonals_pos_neg;
+/// use diagonal::DiagonalError;
+///
+/// let mut output: Vec<Vec<i32>> = vec![];
+/// assert_eq!(
+///     convolve_diagonals_pos_neg(&[[1, 2], [3, 4]], &[1, 1, 1], &mut output),
+///     Err(DiagonalError::ShapeMismatch { expected: (2, 2), found: (0, 0) }),
+/// );
+/// ```
+pub fn convolve_diagonals_pos_neg<Matrix, Row, Output, OutputRow, T>(
+    matrix: &Matrix,
+    kernel: &[T],
+    output: &mut Output,
+) -> Result<(), DiagonalError>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    Output: AsMut<[OutputRow]> + ?Sized,
+    OutputRow: AsMut<[T]>,
+    T: Copy + Sum + Mul<Output = T>,
+{
+    let (rows, cols) = shape(matrix)?;
+    if kernel.is_empty() {
+        return Err(DiagonalError::EmptyKernel);
+    }
+    check_output(rows, cols, output.as_mut())?;
+    crate::convolve_diagonals_pos_neg(matrix, kernel, output);
+    Ok(())
+}
+
+/// Checked counterpart of [`diagonal_windowed_pos_pos`](crate::diagonal_windowed_pos_pos).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`], and [`DiagonalError::EmptyWindow`]
+/// if `k` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::diagonal_windowed_pos_pos;
+/// use diagonal::DiagonalError;
+///
+/// let matrix = [[1, 2], [3, 4]];
+/// let result = diagonal_windowed_pos_pos(&matrix, 2, |window| *window[0] + *window[1]);
+/// assert_eq!(result, Ok(vec![vec![], vec![((0, 0), 5)], vec![]]));
+/// assert_eq!(
+///     diagonal_windowed_pos_pos(&matrix, 0, |window| window.len()),
+///     Err(DiagonalError::EmptyWindow),
+/// );
+/// ```
+pub fn diagonal_windowed_pos_pos<'a, Matrix, Row, T, U, F>(
+    matrix: &'a Matrix,
+    k: usize,
+    f: F,
+) -> Result<Windowed<U>, DiagonalError>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    T: 'a,
+    F: FnMut(&[&'a T]) -> U,
+{
+    window(matrix, k)?;
+    Ok(crate::diagonal_windowed_pos_pos(matrix, k, f))
+}
+
+/// Checked counterpart of [`diagonal_windowed_pos_neg`](crate::diagonal_windowed_pos_neg).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`], and [`DiagonalError::EmptyWindow`]
+/// if `k` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::diagonal_windowed_pos_neg;
+///
+/// let matrix = [[1, 2], [3, 4]];
+/// let result = diagonal_windowed_pos_neg(&matrix, 2, |window| *window[0] + *window[1]);
+/// assert_eq!(result, Ok(vec![vec![], vec![((0, 1), 5)], vec![]]));
+/// ```
+pub fn diagonal_windowed_pos_neg<'a, Matrix, Row, T, U, F>(
+    matrix: &'a Matrix,
+    k: usize,
+    f: F,
+) -> Result<Windowed<U>, DiagonalError>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    T: 'a,
+    F: FnMut(&[&'a T]) -> U,
+{
+    window(matrix, k)?;
+    Ok(crate::diagonal_windowed_pos_neg(matrix, k, f))
+}
+
+/// Checked counterpart of [`diagonal_blocks`](crate::diagonal_blocks).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`], [`DiagonalError::NotSquare`] if it
+/// is not square, and [`DiagonalError::BlockSizes`] if `block_sizes` does not add up to its side
+/// length.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::diagonal_blocks;
+/// use diagonal::DiagonalError;
+///
+/// let matrix = [[1, 0], [0, 2]];
+/// assert_eq!(diagonal_blocks(&matrix, &[1, 1]), Ok(vec![vec![&[1][..]], vec![&[2][..]]]));
+/// assert_eq!(diagonal_blocks(&matrix, &[1, 2]), Err(DiagonalError::BlockSizes { side: 2 }));
+/// ```
+pub fn diagonal_blocks<'a, Matrix, Row, T>(
+    matrix: &'a Matrix,
+    block_sizes: &[usize],
+) -> Result<Vec<Vec<&'a [T]>>, DiagonalError>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+{
+    let side = square(matrix)?;
+    if block_sizes
+        .iter()
+        .try_fold(0usize, |total, &size| total.checked_add(size))
+        != Some(side)
+    {
+        return Err(DiagonalError::BlockSizes { side });
+    }
+    Ok(crate::diagonal_blocks(matrix, block_sizes))
+}
+
+/// Checked counterpart of [`window_diagonals`](crate::window_diagonals).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`], and [`DiagonalError::EmptyWindow`]
+/// if `k` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::window_diagonals;
+/// use diagonal::DiagonalError;
+///
+/// let matrix = [[1, 2], [3, 4]];
+/// assert_eq!(window_diagonals(&matrix, 2).map(|windows| windows.len()), Ok(1));
+/// assert_eq!(window_diagonals(&matrix, 0), Err(DiagonalError::EmptyWindow));
+/// ```
+pub fn window_diagonals<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+    k: usize,
+) -> Result<Vec<WindowDiagonals<'a, T>>, DiagonalError> {
+    window(matrix, k)?;
+    Ok(crate::window_diagonals(matrix, k))
+}
+
+/// Checked counterpart of [`ray_wrapping`](crate::ray_wrapping).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`], [`DiagonalError::CellOutOfBounds`]
+/// if `start` lies outside of it, and [`DiagonalError::ZeroDirection`] if `direction` is
+/// `(0, 0)`.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::ray_wrapping;
+/// use diagonal::DiagonalError;
+///
+/// let matrix = [[1, 2, 3], [4, 5, 6]];
+/// assert_eq!(ray_wrapping(&matrix, (1, 2), (0, 1), 4), Ok(vec![&6, &4, &5]));
+/// assert_eq!(
+///     ray_wrapping(&matrix, (2, 0), (0, 1), 4),
+///     Err(DiagonalError::CellOutOfBounds { cell: (2, 0), rows: 2, cols: 3 }),
+/// );
+/// assert_eq!(ray_wrapping(&matrix, (0, 0), (0, 0), 4), Err(DiagonalError::ZeroDirection));
+/// ```
+pub fn ray_wrapping<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+    start: (usize, usize),
+    direction: (isize, isize),
+    max_len: usize,
+) -> Result<Vec<&'a T>, DiagonalError> {
+    let (rows, cols) = shape(matrix)?;
+    if start.0 >= rows || start.1 >= cols {
+        return Err(DiagonalError::CellOutOfBounds {
+            cell: start,
+            rows,
+            cols,
+        });
+    }
+    if direction == (0, 0) {
+        return Err(DiagonalError::ZeroDirection);
+    }
+    Ok(crate::ray_wrapping(matrix, start, direction, max_len))
+}
+
+/// Checked counterpart of [`triangle_diagonals`](crate::triangle_diagonals).
+///
+/// Unlike the other functions of this module, it takes ragged rows: they are what make a
+/// staircase.
+///
+/// # Errors
+///
+/// Returns [`DiagonalError::NotStaircase`] if the rows do not all grow or all shrink by one
+/// element from each row to the next.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::triangle_diagonals;
+/// use diagonal::DiagonalError;
+///
+/// let pascal = vec![vec![1], vec![1, 1], vec![1, 2, 1]];
+/// assert_eq!(
+///     triangle_diagonals(&pascal),
+///     Ok(vec![vec![&1], vec![&1, &2], vec![&1, &1, &1]]),
+/// );
+///
+/// let gap = vec![vec![1], vec![1, 1], vec![1, 3, 3, 1]];
+/// assert_eq!(triangle_diagonals(&gap), Err(DiagonalError::NotStaircase { row: 2 }));
+/// ```
+pub fn triangle_diagonals<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Result<Vec<Vec<&'a T>>, DiagonalError> {
+    let lengths: Vec<usize> = matrix
+        .as_ref()
+        .iter()
+        .map(|row| row.as_ref().len())
+        .collect();
+    // The first two rows decide whether the staircase grows or shrinks.
+    let growing = lengths.len() < 2 || lengths[0] < lengths[1];
+    let step = |pair: &[usize]| {
+        if growing {
+            pair[1].checked_sub(pair[0])
+        } else {
+            pair[0].checked_sub(pair[1])
+        }
+    };
+    if let Some(row) = lengths.windows(2).position(|pair| step(pair) != Some(1)) {
+        return Err(DiagonalError::NotStaircase { row: row + 1 });
+    }
+    Ok(crate::triangle_diagonals(matrix))
+}
+
+/// Checked counterpart of [`into_diagonal_pos_pos`](crate::into_diagonal_pos_pos).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`].
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::into_diagonal_pos_pos;
+///
+/// let matrix = vec![vec![1, 2], vec![3, 4]];
+/// assert_eq!(into_diagonal_pos_pos(matrix), Ok(vec![vec![3], vec![1, 4], vec![2]]));
+/// ```
+pub fn into_diagonal_pos_pos<T>(matrix: Vec<Vec<T>>) -> Result<Vec<Vec<T>>, DiagonalError> {
+    shape(&matrix)?;
+    Ok(crate::into_diagonal_pos_pos(matrix))
+}
+
+/// Checked counterpart of [`into_diagonal_pos_neg`](crate::into_diagonal_pos_neg).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`].
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::into_diagonal_pos_neg;
+/// use diagonal::DiagonalError;
+///
+/// assert_eq!(
+///     into_diagonal_pos_neg(vec![vec![1, 2], vec![3]]),
+///     Err(DiagonalError::Ragged { row: 1, expected: 2, found: 1 }),
+/// );
+/// ```
+pub fn into_diagonal_pos_neg<T>(matrix: Vec<Vec<T>>) -> Result<Vec<Vec<T>>, DiagonalError> {
+    shape(&matrix)?;
+    Ok(crate::into_diagonal_pos_neg(matrix))
+}
+
+/// Checked counterpart of [`into_straight_x`](crate::into_straight_x).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`].
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::into_straight_x;
+///
+/// assert_eq!(into_straight_x(vec![vec![1, 2], vec![3, 4]]), Ok(vec![vec![1, 2], vec![3, 4]]));
+/// ```
+pub fn into_straight_x<T>(matrix: Vec<Vec<T>>) -> Result<Vec<Vec<T>>, DiagonalError> {
+    shape(&matrix)?;
+    Ok(crate::into_straight_x(matrix))
+}
+
+/// Checked counterpart of [`into_straight_y`](crate::into_straight_y).
+///
+/// # Errors
+///
+/// Returns an error if the matrix is invalid, see [`shape`].
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::into_straight_y;
+///
+/// assert_eq!(into_straight_y(vec![vec![1, 2], vec![3, 4]]), Ok(vec![vec![1, 3], vec![2, 4]]));
+/// ```
+pub fn into_straight_y<T>(matrix: Vec<Vec<T>>) -> Result<Vec<Vec<T>>, DiagonalError> {
+    shape(&matrix)?;
+    Ok(crate::into_straight_y(matrix))
+}
+
+/// Checked counterpart of
+/// [`diagonals_from_triplets_pos_pos`](crate::diagonals_from_triplets_pos_pos).
+///
+/// # Errors
+///
+/// Returns [`DiagonalError::DimensionOverflow`] if `rows + cols` does not fit in a `usize`, and
+/// [`DiagonalError::CellOutOfBounds`] for the first entry that lies outside of the matrix. The
+/// number of cells is never computed, so shapes far larger than memory are fine.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::diagonals_from_triplets_pos_pos;
+///
+/// let entries = [(0, 0, 'a'), (1 << 40, 1 << 40, 'b')];
+/// let result = diagonals_from_triplets_pos_pos(&entries, 1 << 41, 1 << 41);
+/// assert_eq!(result.map(|diagonals| diagonals.len()), Ok(1));
+/// ```
+pub fn diagonals_from_triplets_pos_pos<T>(
+    entries: &[Triplet<T>],
+    rows: usize,
+    cols: usize,
+) -> Result<Vec<SparseDiagonal<'_, T>>, DiagonalError> {
+    check_triplets(entries, rows, cols)?;
+    Ok(crate::diagonals_from_triplets_pos_pos(entries, rows, cols))
+}
+
+/// Checked counterpart of
+/// [`diagonals_from_triplets_pos_neg`](crate::diagonals_from_triplets_pos_neg).
+///
+/// # Errors
+///
+/// Returns [`DiagonalError::DimensionOverflow`] if `rows + cols` does not fit in a `usize`, and
+/// [`DiagonalError::CellOutOfBounds`] for the first entry that lies outside of the matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::checked::diagonals_from_triplets_pos_neg;
+/// use diagonal::DiagonalError;
+///
+/// assert_eq!(
+///     diagonals_from_triplets_pos_neg(&[(0, 1, 'a'), (0, 3, 'b')], 2, 2),
+///     Err(DiagonalError::CellOutOfBounds { cell: (0, 3), rows: 2, cols: 2 }),
+/// );
+/// ```
+pub fn diagonals_from_triplets_pos_neg<T>(
+    entries: &[Triplet<T>],
+    rows: usize,
+    cols: usize,
+) -> Result<Vec<SparseDiagonal<'_, T>>, DiagonalError> {
+    check_triplets(entries, rows, cols)?;
+    Ok(crate::diagonals_from_triplets_pos_neg(entries, rows, cols))
+}
+
+/// Validates a matrix and returns its side length if it is square.
+fn square<Matrix: AsRef<[Row]> + ?Sized, Row: AsRef<[T]>, T>(
+    matrix: &Matrix,
+) -> Result<usize, DiagonalError> {
+    match shape(matrix)? {
+        (rows, cols) if rows == cols => Ok(rows),
+        (rows, cols) => Err(DiagonalError::NotSquare { rows, cols }),
+    }
+}
+
+/// Validates a matrix and the length `k` of a window slid over it.
+fn window<Matrix: AsRef<[Row]> + ?Sized, Row: AsRef<[T]>, T>(
+    matrix: &Matrix,
+    k: usize,
+) -> Result<(), DiagonalError> {
+    shape(matrix)?;
+    if k == 0 {
+        return Err(DiagonalError::EmptyWindow);
+    }
+    Ok(())
+}
+
+/// Checks that `output` is a `rows` x `cols` matrix.
+fn check_output<Row: AsMut<[T]>, T>(
+    rows: usize,
+    cols: usize,
+    output: &mut [Row],
+) -> Result<(), DiagonalError> {
+    let out_rows = output.len();
+    let mut lengths = output.iter_mut().map(|row| row.as_mut().len());
+    let first = lengths.next().unwrap_or(0);
+    let found = (out_rows, lengths.find(|&len| len != first).unwrap_or(first));
+    if found != (rows, cols) {
+        return Err(DiagonalError::ShapeMismatch {
+            expected: (rows, cols),
+            found,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that every entry lies inside a `rows` x `cols` matrix whose diagonals can be numbered.
+fn check_triplets<T>(
+    entries: &[Triplet<T>],
+    rows: usize,
+    cols: usize,
+) -> Result<(), DiagonalError> {
+    if rows.checked_add(cols).is_none() {
+        return Err(DiagonalError::DimensionOverflow { rows, cols });
+    }
+    match entries
+        .iter()
+        .find(|&&(row, col, _)| row >= rows || col >= cols)
+    {
+        Some(&(row, col, _)) => Err(DiagonalError::CellOutOfBounds {
+            cell: (row, col),
+            rows,
+            cols,
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::owned;
+
+    #[test]
+    fn valid_input_matches_unchecked() {
+        let matrix: Vec<Vec<i32>> = (0..3)
+            .map(|r| (0..4).map(|c| r * 4 + c).collect())
+            .collect();
+        assert_eq!(
+            diagonal_pos_pos(&matrix).unwrap(),
+            crate::diagonal_pos_pos(&matrix)
+        );
+        assert_eq!(
+            diagonal_pos_neg(&matrix).unwrap(),
+            crate::diagonal_pos_neg(&matrix)
+        );
+        assert_eq!(straight_x(&matrix).unwrap(), crate::straight_x(&matrix));
+        assert_eq!(straight_y(&matrix).unwrap(), crate::straight_y(&matrix));
+
+        let pos_pos = owned(crate::diagonal_pos_pos(&matrix));
+        assert_eq!(from_diagonal_pos_pos(pos_pos, 3, 4).unwrap(), matrix);
+        let pos_neg = owned(crate::diagonal_pos_neg(&matrix));
+        assert_eq!(from_diagonal_pos_neg(pos_neg, 3, 4).unwrap(), matrix);
+
+        let spec = || [(-1, vec![1, 2]), (2, vec![3, 4, 5])];
+        assert_eq!(
+            matrix_from_diagonals(spec(), 3, 4, 0).unwrap(),
+            crate::matrix_from_diagonals(spec(), 3, 4, 0)
+        );
+    }
+
+    #[test]
+    fn ragged() {
+        let matrix = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7]];
+        let error = DiagonalError::Ragged {
+            row: 2,
+            expected: 3,
+            found: 1,
+        };
+        assert_eq!(diagonal_pos_pos(&matrix), Err(error));
+        assert_eq!(straight_y(&matrix), Err(error));
+        assert_eq!(diff_lines(&matrix, &matrix), Err(error));
+    }
+
+    #[test]
+    fn empty() {
+        let none: Vec<Vec<u8>> = vec![];
+        assert_eq!(straight_x(&none), Ok(vec![]));
+        assert_eq!(
+            from_diagonal_pos_pos(Vec::<Vec<u8>>::new(), 0, 3),
+            Ok(vec![])
+        );
+
+        let empty_rows: Vec<Vec<u8>> = vec![vec![], vec![]];
+        assert_eq!(straight_x(&empty_rows), Err(DiagonalError::EmptyRows));
+        assert_eq!(
+            from_diagonal_pos_neg(Vec::<Vec<u8>>::new(), 2, 0),
+            Err(DiagonalError::EmptyRows)
+        );
+    }
+
+    #[test]
+    fn huge_dimensions() {
+        // Zero-sized elements allow rows of `usize::MAX` cells.
+        let wide = vec![vec![(); usize::MAX]; 2];
+        let overflow = DiagonalError::DimensionOverflow {
+            rows: 2,
+            cols: usize::MAX,
+        };
+        assert_eq!(shape(&wide), Err(overflow));
+        assert_eq!(diagonal_pos_pos(&wide), Err(overflow));
+        assert_eq!(diagonal_pos_neg(&wide), Err(overflow));
+
+        assert_eq!(shape(&[vec![(); usize::MAX]]), Ok((1, usize::MAX)));
+        assert_eq!(
+            diagonal_count(usize::MAX, 1),
+            Err(DiagonalError::DimensionOverflow {
+                rows: usize::MAX,
+                cols: 1
+            })
+        );
+        assert_eq!(diagonal_count(usize::MAX - 1, 1), Ok(usize::MAX - 1));
+    }
+
+    #[test]
+    fn reconstruction_errors() {
+        assert_eq!(
+            from_diagonal_pos_pos(vec![vec![1], vec![2, 3], vec![4, 5]], 2, 2),
+            Err(DiagonalError::DiagonalLength {
+                index: 2,
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_eq!(
+            from_diagonal_pos_pos(Vec::<Vec<u8>>::new(), usize::MAX / 2, 3),
+            Err(DiagonalError::DimensionOverflow {
+                rows: usize::MAX / 2,
+                cols: 3
+            })
+        );
+    }
+
+    #[test]
+    fn banded_errors() {
+        let spec = || [(3, vec![1]), (0, vec![2])];
+        assert_eq!(
+            matrix_from_diagonals(spec(), 2, 3, 0),
+            Err(DiagonalError::OffsetOutOfRange {
+                offset: 3,
+                rows: 2,
+                cols: 3
+            })
+        );
+        assert_eq!(
+            matrix_from_diagonals(spec(), 0, 0, 0),
+            Err(DiagonalError::OffsetOutOfRange {
+                offset: 3,
+                rows: 0,
+                cols: 0
+            })
+        );
+        assert_eq!(
+            matrix_from_diagonals(spec(), 2, 0, 0),
+            Err(DiagonalError::EmptyRows)
+        );
+        assert_eq!(
+            matrix_from_diagonals(spec(), usize::MAX, 2, 0),
+            Err(DiagonalError::DimensionOverflow {
+                rows: usize::MAX,
+                cols: 2
+            })
+        );
+        assert_eq!(
+            matrix_from_diagonals([(isize::MIN, vec![1])], 2, 2, 0),
+            Err(DiagonalError::OffsetOutOfRange {
+                offset: isize::MIN,
+                rows: 2,
+                cols: 2
+            })
+        );
+    }
+
+    #[test]
+    fn counterparts_of_panicking_functions() {
+        let matrix: Vec<Vec<i32>> = (0..3)
+            .map(|r| (0..3).map(|c| r * 3 + c).collect())
+            .collect();
+        assert_eq!(
+            diagonal_difference(&matrix),
+            Ok(crate::diagonal_difference(&matrix))
+        );
+        assert_eq!(
+            diagonal_blocks(&matrix, &[1, 2]),
+            Ok(crate::diagonal_blocks(&matrix, &[1, 2]))
+        );
+        assert_eq!(
+            ray_wrapping(&matrix, (2, 1), (1, -1), 5),
+            Ok(crate::ray_wrapping(&matrix, (2, 1), (1, -1), 5))
+        );
+        let sum = |window: &[&i32]| window.iter().copied().sum::<i32>();
+        assert_eq!(
+            diagonal_windowed_pos_neg(&matrix, 2, sum),
+            Ok(crate::diagonal_windowed_pos_neg(&matrix, 2, sum))
+        );
+
+        let mut checked = vec![vec![0; 3]; 3];
+        let mut unchecked = checked.clone();
+        convolve_diagonals_pos_neg(&matrix, &[1, 2], &mut checked).unwrap();
+        crate::convolve_diagonals_pos_neg(&matrix, &[1, 2], &mut unchecked);
+        assert_eq!(checked, unchecked);
+
+        assert_eq!(
+            into_straight_y(matrix.clone()),
+            Ok(crate::into_straight_y(matrix))
+        );
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let wide = [[1, 2, 3], [4, 5, 6]];
+        let not_square = DiagonalError::NotSquare { rows: 2, cols: 3 };
+        assert_eq!(diagonal_difference(&wide), Err(not_square));
+        assert_eq!(diagonal_blocks(&wide, &[2]), Err(not_square));
+        assert_eq!(
+            diagonal_blocks(&[[1]], &[usize::MAX, 2]),
+            Err(DiagonalError::BlockSizes { side: 1 })
+        );
+        assert_eq!(window_diagonals(&wide, 0), Err(DiagonalError::EmptyWindow));
+        assert_eq!(
+            diagonal_windowed_pos_pos(&wide, 0, |window| window.len()),
+            Err(DiagonalError::EmptyWindow)
+        );
+        assert_eq!(
+            ray_wrapping(&wide, (0, 3), (1, 1), 1),
+            Err(DiagonalError::CellOutOfBounds {
+                cell: (0, 3),
+                rows: 2,
+                cols: 3
+            })
+        );
+
+        let mut output = [[0; 3]; 2];
+        assert_eq!(
+            convolve_diagonals_pos_pos(&wide, &[], &mut output),
+            Err(DiagonalError::EmptyKernel)
+        );
+        assert_eq!(
+            diagonal_prefix_sums_into_pos_pos(&[[1, 2]], &mut output),
+            Err(DiagonalError::ShapeMismatch {
+                expected: (1, 2),
+                found: (2, 3)
+            })
+        );
+
+        assert_eq!(
+            triangle_diagonals(&vec![vec![1, 2], vec![3], vec![4, 5]]),
+            Err(DiagonalError::NotStaircase { row: 2 })
+        );
+        assert_eq!(
+            triangle_diagonals(&vec![vec![(); usize::MAX], vec![]]),
+            Err(DiagonalError::NotStaircase { row: 1 })
+        );
+        assert_eq!(
+            diagonals_from_triplets_pos_pos(&[(0, 0, 'a')], usize::MAX, 1),
+            Err(DiagonalError::DimensionOverflow {
+                rows: usize::MAX,
+                cols: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,210 @@
+//! # Cipher Module
+//!
+//! This module implements the rail-fence (zigzag) cipher. The text is written into a conceptual
+//! grid with one row per rail and one column per character, moving diagonally down and up
+//! between the rails; the ciphertext is that grid read row by row, as
+//! [`straight_x`](crate::straight_x) would. The grid is never built: the rail of every
+//! character follows from its position in the zigzag's cycle, so memory use only depends on the
+//! length of the text, however many rails are requested.
+//!
+//! ### Example
+//!
+//! ```rust
+//! use diagonal::cipher::{rail_fence_decode, rail_fence_encode};
+//!
+//! // W . . . E . . . C . . . R . . . L . . . T . . . E
+//! // . E . R . D . S . O . E . E . F . E . A . O . C .
+//! // . . A . . . I . . . V . . . D . . . E . . . N . .
+//! let cipher = rail_fence_encode("WEAREDISCOVEREDFLEEATONCE", 3);
+//! assert_eq!(cipher, "WECRLTEERDSOEEFEAOCAIVDEN");
+//! assert_eq!(rail_fence_decode(&cipher, 3), "WEAREDISCOVEREDFLEEATONCE");
+//! ```
+
+/// Returns the rail that the character at `position` is written to.
+///
+/// `rails` must not exceed the length of the text, which keeps the cycle from overflowing.
+fn rail(position: usize, rails: usize) -> usize {
+    if rails == 1 {
+        return 0;
+    }
+    let cycle = 2 * (rails - 1);
+    let offset = position % cycle;
+    offset.min(cycle - offset)
+}
+
+/// Returns the number of rails that are actually used for a text of `len` characters.
+///
+/// # Panics
+///
+/// Panics if `rails` is zero.
+fn used_rails(rails: usize, len: usize) -> usize {
+    assert!(rails > 0, "the rail fence cipher needs at least one rail");
+    // Rails below the last character stay empty, so they can be dropped.
+    rails.min(len.max(1))
+}
+
+/// Returns the positions of a text of `len` characters, rail by rail from top to bottom.
+fn positions(len: usize, rails: usize) -> impl Iterator<Item = usize> {
+    let cycle = 2 * (rails - 1);
+    (0..rails).flat_map(move |rail| {
+        let starts = (0..len).step_by(cycle.max(1));
+        starts.flat_map(move |start| {
+            // Rails strictly between the top and the bottom are visited twice per cycle.
+            let down = start + rail;
+            let up = (rail > 0 && rail < rails - 1).then(|| start + cycle - rail);
+            [Some(down), up]
+                .into_iter()
+                .flatten()
+                .filter(move |&p| p < len)
+        })
+    })
+}
+
+/// Encodes `text` with the rail-fence cipher.
+///
+/// The characters of `text` are written in a zigzag across `rails` rows, starting on the top
+/// rail, and the rows are then concatenated from top to bottom.
+///
+/// # Arguments
+///
+/// * `text` - The plaintext. It is processed as a sequence of `char`s.
+/// * `rails` - The number of rails.
+///
+/// # Returns
+///
+/// The ciphertext, a permutation of the characters of `text`.
+///
+/// # Panics
+///
+/// Panics if `rails` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::cipher::rail_fence_encode;
+///
+/// assert_eq!(rail_fence_encode("HELLOWORLD", 2), "HLOOLELWRD");
+/// assert_eq!(rail_fence_encode("HELLOWORLD", 1), "HELLOWORLD");
+/// ```
+pub fn rail_fence_encode(text: &str, rails: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let rails = used_rails(rails, chars.len());
+    positions(chars.len(), rails)
+        .map(|position| chars[position])
+        .collect()
+}
+
+/// Decodes a ciphertext produced by [`rail_fence_encode`] with the same number of rails.
+///
+/// # Arguments
+///
+/// * `cipher` - The ciphertext. It is processed as a sequence of `char`s.
+/// * `rails` - The number of rails used to encode it.
+///
+/// # Returns
+///
+/// The plaintext.
+///
+/// # Panics
+///
+/// Panics if `rails` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::cipher::rail_fence_decode;
+///
+/// assert_eq!(rail_fence_decode("HLOOLELWRD", 2), "HELLOWORLD");
+/// ```
+pub fn rail_fence_decode(cipher: &str, rails: usize) -> String {
+    let chars: Vec<char> = cipher.chars().collect();
+    let len = chars.len();
+    let rails = used_rails(rails, len);
+
+    // The ciphertext holds the rails one after another, so their lengths tell where each starts.
+    let mut starts = vec![0; rails];
+    for position in 0..len {
+        if let Some(next) = starts.get_mut(rail(position, rails) + 1) {
+            *next += 1;
+        }
+    }
+    for rail in 1..rails {
+        starts[rail] += starts[rail - 1];
+    }
+
+    (0..len)
+        .map(|position| {
+            let next = &mut starts[rail(position, rails)];
+            *next += 1;
+            chars[*next - 1]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zigzag_rails() {
+        let rails: Vec<usize> = (0..10).map(|position| rail(position, 4)).collect();
+        assert_eq!(rails, vec![0, 1, 2, 3, 2, 1, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let text = "The quick brown fox jumps over the lazy dog — ünïcödé";
+        for rails in 1..text.len() + 2 {
+            let cipher = rail_fence_encode(text, rails);
+            assert_eq!(cipher.chars().count(), text.chars().count());
+            assert_eq!(rail_fence_decode(&cipher, rails), text);
+        }
+    }
+
+    #[test]
+    fn more_rails_than_characters() {
+        assert_eq!(rail_fence_encode("abc", 10), "abc");
+        assert_eq!(rail_fence_decode("abc", 10), "abc");
+    }
+
+    #[test]
+    fn huge_rail_counts() {
+        for rails in [1 << 40, usize::MAX / 2 + 2, usize::MAX] {
+            assert_eq!(rail_fence_encode("HELLO", rails), "HELLO");
+            assert_eq!(rail_fence_decode("HELLO", rails), "HELLO");
+        }
+        assert_eq!(rail_fence_encode("", usize::MAX), "");
+        assert_eq!(rail_fence_decode("", usize::MAX), "");
+    }
+
+    #[test]
+    fn positions_are_a_permutation() {
+        for len in 0..12 {
+            for rails in 1..=len.max(1) {
+                let mut order: Vec<usize> = positions(len, rails).collect();
+                let by_rail: Vec<usize> = order.iter().map(|&p| rail(p, rails)).collect();
+                assert!(by_rail.windows(2).all(|pair| pair[0] <= pair[1]));
+                order.sort_unstable();
+                assert_eq!(order, (0..len).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(rail_fence_encode("", 3), "");
+        assert_eq!(rail_fence_decode("", 3), "");
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_rails() {
+        rail_fence_encode("abc", 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_rails_for_empty_text() {
+        rail_fence_decode("", 0);
+    }
+}
@@ -0,0 +1,221 @@
+use crate::{CoverageError, Direction};
+
+/// Verifies that a decomposition visits every cell of a matrix exactly once, in the order of a
+/// traversal.
+///
+/// The decomposition is legal if it has one line per line of the traversal named by
+/// `direction`, and the `i`-th line lists the coordinates of the `i`-th line of that traversal
+/// in order, as [`lines_iter`](crate::lines_iter) visits them. This makes it an oracle for
+/// property tests of custom traversals built on top of this crate.
+///
+/// # Arguments
+///
+/// * `rows` - The number of rows of the matrix.
+/// * `cols` - The number of columns of the matrix.
+/// * `direction` - The traversal the decomposition has to follow.
+/// * `decomposition` - The lines of the decomposition, each a list of `(row, column)`
+///   coordinates.
+///
+/// # Errors
+///
+/// Returns the first violation found, checking the lines in order and the cells of each line
+/// front to back. A cell that is listed twice is reported as a [`CoverageError::Step`] within a
+/// line or as a [`CoverageError::WrongLine`] across lines.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{verify_decomposition, CoverageError, Direction};
+///
+/// let columns = [vec![(0, 0), (1, 0)], vec![(0, 1), (1, 1)]];
+/// assert_eq!(verify_decomposition(2, 2, Direction::StraightY, &columns), Ok(()));
+///
+/// let reversed = [vec![(1, 0), (0, 0)], vec![(0, 1), (1, 1)]];
+/// assert_eq!(
+///     verify_decomposition(2, 2, Direction::StraightY, &reversed),
+///     Err(CoverageError::Step { line: 0, position: 1 }),
+/// );
+///
+/// let partial = [vec![(0, 0), (1, 0)], vec![(0, 1)]];
+/// assert_eq!(
+///     verify_decomposition(2, 2, Direction::StraightY, &partial),
+///     Err(CoverageError::Missing { cell: (1, 1) }),
+/// );
+/// ```
+pub fn verify_decomposition<Cells: AsRef<[(usize, usize)]>>(
+    rows: usize,
+    cols: usize,
+    direction: Direction,
+    decomposition: &[Cells],
+) -> Result<(), CoverageError> {
+    let lines = direction.line_count(rows, cols);
+    if decomposition.len() != lines {
+        return Err(CoverageError::LineCount {
+            expected: lines,
+            found: decomposition.len(),
+        });
+    }
+
+    for (line, cells) in decomposition.iter().enumerate() {
+        let cells = cells.as_ref();
+        for (position, &cell) in cells.iter().enumerate() {
+            if cell.0 >= rows || cell.1 >= cols {
+                return Err(CoverageError::OutOfBounds {
+                    line,
+                    position,
+                    cell,
+                });
+            }
+            if direction.line_of(rows, cell) != line {
+                return Err(CoverageError::WrongLine {
+                    line,
+                    position,
+                    cell,
+                });
+            }
+            if position > 0 && !follows(direction, cells[position - 1], cell) {
+                return Err(CoverageError::Step { line, position });
+            }
+        }
+
+        // The cells are consecutive and on the right line, so only its ends can be missing.
+        let (start, len) = direction.line_start(rows, cols, line);
+        if len > 0 && cells.first() != Some(&start) {
+            return Err(CoverageError::Missing { cell: start });
+        }
+        if cells.len() < len {
+            return Err(CoverageError::Missing {
+                cell: direction.cell(start, cells.len()),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether `next` is one step after `prev` in `direction`.
+fn follows(direction: Direction, prev: (usize, usize), next: (usize, usize)) -> bool {
+    match direction {
+        Direction::DiagonalPosPos => next == (prev.0 + 1, prev.1 + 1),
+        Direction::DiagonalPosNeg => next.0 == prev.0 + 1 && next.1 + 1 == prev.1,
+        Direction::StraightX => next == (prev.0, prev.1 + 1),
+        Direction::StraightY => next == (prev.0 + 1, prev.1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decomposition(rows: usize, cols: usize, direction: Direction) -> Vec<Vec<(usize, usize)>> {
+        let matrix: Vec<Vec<(usize, usize)>> = (0..rows)
+            .map(|r| (0..cols).map(|c| (r, c)).collect())
+            .collect();
+        crate::lines_iter(&matrix, direction)
+            .map(|line| line.copied().collect())
+            .collect()
+    }
+
+    #[test]
+    fn traversals_are_legal() {
+        for rows in 1..5 {
+            for cols in 1..5 {
+                for direction in Direction::ALL {
+                    let lines = decomposition(rows, cols, direction);
+                    assert_eq!(verify_decomposition(rows, cols, direction, &lines), Ok(()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn empty_matrices() {
+        let empty: [Vec<(usize, usize)>; 3] = Default::default();
+        assert_eq!(
+            verify_decomposition(0, 3, Direction::StraightY, &empty),
+            Ok(())
+        );
+        assert_eq!(
+            verify_decomposition(3, 0, Direction::StraightX, &empty),
+            Ok(())
+        );
+        assert_eq!(
+            verify_decomposition(3, 0, Direction::DiagonalPosPos, &empty),
+            Err(CoverageError::LineCount {
+                expected: 0,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn violations() {
+        let direction = Direction::DiagonalPosNeg;
+        let verify = |lines: &[Vec<(usize, usize)>]| verify_decomposition(2, 3, direction, lines);
+        let valid = decomposition(2, 3, direction);
+
+        assert_eq!(
+            verify(&valid[1..]),
+            Err(CoverageError::LineCount {
+                expected: 4,
+                found: 3
+            })
+        );
+
+        let mut lines = valid.clone();
+        lines[3].push((2, 1));
+        assert_eq!(
+            verify(&lines),
+            Err(CoverageError::OutOfBounds {
+                line: 3,
+                position: 1,
+                cell: (2, 1)
+            })
+        );
+
+        let mut lines = valid.clone();
+        lines[1].push((1, 1));
+        assert_eq!(
+            verify(&lines),
+            Err(CoverageError::WrongLine {
+                line: 1,
+                position: 2,
+                cell: (1, 1)
+            })
+        );
+
+        let mut lines = valid.clone();
+        lines[2].reverse();
+        assert_eq!(
+            verify(&lines),
+            Err(CoverageError::Step {
+                line: 2,
+                position: 1
+            })
+        );
+
+        let mut lines = valid.clone();
+        lines[0].push((0, 0));
+        assert_eq!(
+            verify(&lines),
+            Err(CoverageError::Step {
+                line: 0,
+                position: 1
+            })
+        );
+
+        let mut lines = valid.clone();
+        lines[1].insert(0, (0, 1));
+        lines[1].insert(0, (0, 1));
+        assert_eq!(
+            verify(&lines),
+            Err(CoverageError::Step {
+                line: 1,
+                position: 1
+            })
+        );
+
+        let mut lines = valid;
+        lines[2].pop();
+        assert_eq!(verify(&lines), Err(CoverageError::Missing { cell: (1, 1) }));
+    }
+}
@@ -0,0 +1,331 @@
+//! # Cube Operations Module
+//!
+//! This module extracts lines from a three-dimensional grid, given as a slice of planes, where
+//! each plane is a matrix in the same shape the rest of the crate uses. A `Vec<Vec<Vec<T>>>` or
+//! `[[[T; Z]; Y]; X]` can be passed directly.
+//!
+//! Coordinates are written as `(x, y, z)`, meaning `cube[x][y][z]`. The grid is assumed to be a
+//! cuboid; its size along each axis is taken from the first plane and the first row.
+//!
+//! ### Example
+//!
+//! ```rust
+//! use diagonal::cube::{axis_lines, space_diagonals, Axis};
+//!
+//! let cube = vec![
+//!     vec![vec![1, 2], vec![3, 4]],
+//!     vec![vec![5, 6], vec![7, 8]],
+//! ];
+//!
+//! assert_eq!(axis_lines(&cube, Axis::X), vec![
+//!     vec![&1, &5],
+//!     vec![&2, &6],
+//!     vec![&3, &7],
+//!     vec![&4, &8],
+//! ]);
+//!
+//! assert_eq!(space_diagonals(&cube), vec![
+//!     vec![&1, &8],
+//!     vec![&2, &7],
+//!     vec![&3, &6],
+//!     vec![&4, &5],
+//! ]);
+//! ```
+
+use crate::index;
+
+/// One of the three axes of a cube.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Axis {
+    /// The outermost index, selecting a plane.
+    X,
+    /// The middle index, selecting a row within a plane.
+    Y,
+    /// The innermost index, selecting an element within a row.
+    Z,
+}
+
+/// Returns the `(x, y, z)` size of a cube, using the first plane and row for `y` and `z`.
+fn shape<Plane: AsRef<[Row]>, Row: AsRef<[T]>, T>(cube: &[Plane]) -> (usize, usize, usize) {
+    let (y_len, z_len) = cube
+        .first()
+        .map_or((0, 0), |plane| index::shape(plane.as_ref()));
+    (cube.len(), y_len, z_len)
+}
+
+/// Extracts every line of a cube that runs parallel to `axis`.
+///
+/// The lines are ordered by their coordinates on the two remaining axes, outermost axis first,
+/// and every line is read in increasing `axis` order.
+///
+/// # Arguments
+///
+/// * `cube` - A reference to a cube represented as a slice of planes, where each plane is a
+///   slice of rows of generic type `T`.
+/// * `axis` - The axis the lines run along.
+///
+/// # Returns
+///
+/// A vector of vectors containing references to the elements of each line.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::cube::{axis_lines, Axis};
+///
+/// let cube = vec![
+///     vec![vec![1, 2], vec![3, 4]],
+///     vec![vec![5, 6], vec![7, 8]],
+/// ];
+///
+/// assert_eq!(axis_lines(&cube, Axis::Y), vec![
+///     vec![&1, &3],
+///     vec![&2, &4],
+///     vec![&5, &7],
+///     vec![&6, &8],
+/// ]);
+/// assert_eq!(axis_lines(&cube, Axis::Z), vec![
+///     vec![&1, &2],
+///     vec![&3, &4],
+///     vec![&5, &6],
+///     vec![&7, &8],
+/// ]);
+/// ```
+pub fn axis_lines<'a, Cube, Plane, Row, T>(cube: &'a Cube, axis: Axis) -> Vec<Vec<&'a T>>
+where
+    Cube: AsRef<[Plane]> + ?Sized,
+    Plane: AsRef<[Row]> + 'a,
+    Row: AsRef<[T]> + 'a,
+{
+    let cube = cube.as_ref();
+    let (x_len, y_len, z_len) = shape(cube);
+    let get = |x: usize, y: usize, z: usize| &cube[x].as_ref()[y].as_ref()[z];
+
+    match axis {
+        Axis::X => (0..y_len)
+            .flat_map(|y| (0..z_len).map(move |z| (0..x_len).map(|x| get(x, y, z)).collect()))
+            .collect(),
+        Axis::Y => (0..x_len)
+            .flat_map(|x| (0..z_len).map(move |z| (0..y_len).map(|y| get(x, y, z)).collect()))
+            .collect(),
+        Axis::Z => (0..x_len)
+            .flat_map(|x| (0..y_len).map(move |y| (0..z_len).map(|z| get(x, y, z)).collect()))
+            .collect(),
+    }
+}
+
+/// Extracts the diagonals of every slice of a cube that is perpendicular to `axis`.
+///
+/// Each slice is treated as a matrix whose rows and columns are the two remaining axes, in
+/// their `x`, `y`, `z` order. For every slice, in increasing `axis` order, its
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos) diagonals are followed by its
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg) diagonals. Calling this for all three axes
+/// yields every face diagonal of the cube.
+///
+/// # Arguments
+///
+/// * `cube` - A reference to a cube represented as a slice of planes, where each plane is a
+///   slice of rows of generic type `T`.
+/// * `axis` - The axis the slices are perpendicular to.
+///
+/// # Returns
+///
+/// A vector of vectors containing references to the elements of each diagonal.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::cube::{face_diagonals, Axis};
+///
+/// let cube = vec![
+///     vec![vec![1, 2], vec![3, 4]],
+///     vec![vec![5, 6], vec![7, 8]],
+/// ];
+///
+/// assert_eq!(face_diagonals(&cube, Axis::X), vec![
+///     vec![&3], vec![&1, &4], vec![&2],
+///     vec![&1], vec![&2, &3], vec![&4],
+///     vec![&7], vec![&5, &8], vec![&6],
+///     vec![&5], vec![&6, &7], vec![&8],
+/// ]);
+/// ```
+pub fn face_diagonals<'a, Cube, Plane, Row, T>(cube: &'a Cube, axis: Axis) -> Vec<Vec<&'a T>>
+where
+    Cube: AsRef<[Plane]> + ?Sized,
+    Plane: AsRef<[Row]> + 'a,
+    Row: AsRef<[T]> + 'a,
+{
+    let cube = cube.as_ref();
+    let (x_len, y_len, z_len) = shape(cube);
+
+    let (slices, rows, cols) = match axis {
+        Axis::X => (x_len, y_len, z_len),
+        Axis::Y => (y_len, x_len, z_len),
+        Axis::Z => (z_len, x_len, y_len),
+    };
+    let get = |slice: usize, (r, c): (usize, usize)| {
+        let (x, y, z) = match axis {
+            Axis::X => (slice, r, c),
+            Axis::Y => (r, slice, c),
+            Axis::Z => (r, c, slice),
+        };
+        &cube[x].as_ref()[y].as_ref()[z]
+    };
+
+    let mut result = Vec::new();
+    for slice in 0..slices {
+        let pos_pos = index::pos_pos_cells(rows, cols);
+        let pos_neg = index::pos_neg_cells(rows, cols);
+        result.extend(pos_pos.map(|d| d.map(|cell| get(slice, cell)).collect::<Vec<_>>()));
+        result.extend(pos_neg.map(|d| d.map(|cell| get(slice, cell)).collect::<Vec<_>>()));
+    }
+    result
+}
+
+/// Extracts the four space diagonals of a cube.
+///
+/// The space diagonals start at the corners `(0, 0, 0)`, `(0, 0, z_max)`, `(0, y_max, 0)` and
+/// `(0, y_max, z_max)`, in that order, and run through the interior towards the opposite corner.
+/// For a cuboid whose sides differ, each diagonal stops after as many cells as the shortest
+/// side has. An empty cube has no space diagonals.
+///
+/// # Arguments
+///
+/// * `cube` - A reference to a cube represented as a slice of planes, where each plane is a
+///   slice of rows of generic type `T`.
+///
+/// # Returns
+///
+/// A vector of four vectors containing references to the elements of each space diagonal, or
+/// an empty vector for an empty cube.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::cube::space_diagonals;
+///
+/// let cube = [
+///     [[1, 2, 3], [4, 5, 6], [7, 8, 9]],
+///     [[10, 11, 12], [13, 14, 15], [16, 17, 18]],
+///     [[19, 20, 21], [22, 23, 24], [25, 26, 27]],
+/// ];
+///
+/// assert_eq!(space_diagonals(&cube), vec![
+///     vec![&1, &14, &27],
+///     vec![&3, &14, &25],
+///     vec![&7, &14, &21],
+///     vec![&9, &14, &19],
+/// ]);
+/// ```
+pub fn space_diagonals<'a, Cube, Plane, Row, T>(cube: &'a Cube) -> Vec<Vec<&'a T>>
+where
+    Cube: AsRef<[Plane]> + ?Sized,
+    Plane: AsRef<[Row]> + 'a,
+    Row: AsRef<[T]> + 'a,
+{
+    let cube = cube.as_ref();
+    let (x_len, y_len, z_len) = shape(cube);
+    let len = x_len.min(y_len).min(z_len);
+    if len == 0 {
+        return vec![];
+    }
+
+    [(false, false), (false, true), (true, false), (true, true)]
+        .into_iter()
+        .map(|(y_reversed, z_reversed)| {
+            (0..len)
+                .map(|i| {
+                    let y = if y_reversed { y_len - 1 - i } else { i };
+                    let z = if z_reversed { z_len - 1 - i } else { i };
+                    &cube[i].as_ref()[y].as_ref()[z]
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn axis_lines_two_by_two_by_three() {
+        let cube = [[[1, 2, 3], [4, 5, 6]], [[7, 8, 9], [10, 11, 12]]];
+        assert_eq!(
+            axis_lines(&cube, Axis::X),
+            vec![
+                vec![&1, &7],
+                vec![&2, &8],
+                vec![&3, &9],
+                vec![&4, &10],
+                vec![&5, &11],
+                vec![&6, &12]
+            ]
+        );
+        assert_eq!(
+            axis_lines(&cube, Axis::Y),
+            vec![
+                vec![&1, &4],
+                vec![&2, &5],
+                vec![&3, &6],
+                vec![&7, &10],
+                vec![&8, &11],
+                vec![&9, &12]
+            ]
+        );
+        assert_eq!(
+            axis_lines(&cube, Axis::Z),
+            vec![
+                vec![&1, &2, &3],
+                vec![&4, &5, &6],
+                vec![&7, &8, &9],
+                vec![&10, &11, &12]
+            ]
+        );
+    }
+
+    #[test]
+    fn face_diagonals_cover_every_slice() {
+        let cube = vec![
+            vec![vec![1, 2, 3], vec![4, 5, 6]],
+            vec![vec![7, 8, 9], vec![10, 11, 12]],
+        ];
+
+        let slices = face_diagonals(&cube, Axis::Z);
+        // Three 2x2 slices with three diagonals per direction.
+        assert_eq!(slices.len(), 3 * 6);
+        assert_eq!(
+            &slices[..6],
+            &[
+                vec![&7],
+                vec![&1, &10],
+                vec![&4],
+                vec![&1],
+                vec![&4, &7],
+                vec![&10]
+            ]
+        );
+
+        let slices = face_diagonals(&cube, Axis::Y);
+        assert_eq!(slices.len(), 2 * 8);
+        assert_eq!(slices[1], vec![&1, &8]);
+    }
+
+    #[test]
+    fn space_diagonals_cuboid() {
+        let cube = [[[1, 2, 3], [4, 5, 6]], [[7, 8, 9], [10, 11, 12]]];
+        assert_eq!(
+            space_diagonals(&cube),
+            vec![vec![&1, &11], vec![&3, &11], vec![&4, &8], vec![&6, &8]]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let cube: Vec<Vec<Vec<usize>>> = vec![];
+        assert_eq!(axis_lines(&cube, Axis::X), Vec::<Vec<&usize>>::new());
+        assert_eq!(face_diagonals(&cube, Axis::Y), Vec::<Vec<&usize>>::new());
+        assert_eq!(space_diagonals(&cube), Vec::<Vec<&usize>>::new());
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{index, lines_iter, Direction};
+
+/// The differences between two matrices along one line, as reported by [`diff_lines`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineDiff {
+    /// The traversal the line belongs to.
+    pub direction: Direction,
+    /// The index of the line in the output of the function named by `direction`.
+    pub line: usize,
+    /// The positions within the line at which the matrices differ, in ascending order.
+    pub positions: Vec<usize>,
+}
+
+/// Compares two matrices of the same shape line by line.
+///
+/// Every row, column and diagonal is compared element by element, and each line that differs
+/// is reported together with the positions of the differing elements. The lines are reported
+/// direction by direction, in the order of [`Direction`]'s variants, and by line index within a
+/// direction.
+///
+/// # Arguments
+///
+/// * `a` - A reference to the first matrix.
+/// * `b` - A reference to the second matrix, with the same shape as `a`.
+///
+/// # Returns
+///
+/// A `Vec<LineDiff>` with one entry per differing line, or an empty vector if the matrices are
+/// equal.
+///
+/// # Panics
+///
+/// Panics if the matrices differ in shape.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{diff_lines, Direction, LineDiff};
+///
+/// let expected = [[1, 2, 3], [4, 5, 6]];
+/// let actual = [[1, 2, 3], [4, 0, 6]];
+///
+/// let diff = diff_lines(&expected, &actual);
+/// assert_eq!(diff, vec![
+///     LineDiff { direction: Direction::DiagonalPosPos, line: 1, positions: vec![1] },
+///     LineDiff { direction: Direction::DiagonalPosNeg, line: 2, positions: vec![1] },
+///     LineDiff { direction: Direction::StraightX, line: 1, positions: vec![1] },
+///     LineDiff { direction: Direction::StraightY, line: 1, positions: vec![1] },
+/// ]);
+/// ```
+pub fn diff_lines<MatrixA, MatrixB, RowA, RowB, T>(a: &MatrixA, b: &MatrixB) -> Vec<LineDiff>
+where
+    MatrixA: AsRef<[RowA]> + ?Sized,
+    MatrixB: AsRef<[RowB]> + ?Sized,
+    RowA: AsRef<[T]>,
+    RowB: AsRef<[T]>,
+    T: PartialEq,
+{
+    let (a, b) = (a.as_ref(), b.as_ref());
+    let (rows, cols) = index::shape(a);
+    assert!(
+        b.len() == rows
+            && a.iter().all(|row| row.as_ref().len() == cols)
+            && b.iter().all(|row| row.as_ref().len() == cols),
+        "matrices must have the same shape"
+    );
+
+    Direction::ALL
+        .into_iter()
+        .flat_map(|direction| {
+            lines_iter(a, direction)
+                .zip(lines_iter(b, direction))
+                .enumerate()
+                .filter_map(move |(line, (line_a, line_b))| {
+                    let positions: Vec<usize> = line_a
+                        .zip(line_b)
+                        .enumerate()
+                        .filter(|(_, (x, y))| x != y)
+                        .map(|(position, _)| position)
+                        .collect();
+                    (!positions.is_empty()).then_some(LineDiff {
+                        direction,
+                        line,
+                        positions,
+                    })
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_point_at_differences() {
+        let a = [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]];
+        let mut b = a;
+        b[0][3] = 0;
+        b[2][0] = 0;
+        b[1][1] = 0;
+
+        let diff = diff_lines(&a, &b);
+        for LineDiff {
+            direction,
+            line,
+            positions,
+        } in &diff
+        {
+            let line_a: Vec<&i32> = lines_iter(&a, *direction).nth(*line).unwrap().collect();
+            let line_b: Vec<&i32> = lines_iter(&b, *direction).nth(*line).unwrap().collect();
+            let differing: Vec<usize> = (0..line_a.len())
+                .filter(|&i| line_a[i] != line_b[i])
+                .collect();
+            assert_eq!(positions, &differing);
+        }
+
+        // Every changed cell shows up once per direction.
+        for direction in Direction::ALL {
+            let count: usize = diff
+                .iter()
+                .filter(|entry| entry.direction == direction)
+                .map(|entry| entry.positions.len())
+                .sum();
+            assert_eq!(count, 3);
+        }
+        let rows: Vec<usize> = diff
+            .iter()
+            .filter(|entry| entry.direction == Direction::StraightX)
+            .map(|entry| entry.line)
+            .collect();
+        assert_eq!(rows, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn equal_matrices() {
+        let a = vec![vec!['a', 'b'], vec!['c', 'd']];
+        assert!(diff_lines(&a, &a.clone()).is_empty());
+        assert!(diff_lines(&Vec::<Vec<u8>>::new(), &Vec::<Vec<u8>>::new()).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn different_shapes() {
+        diff_lines(&[[1, 2]], &[[1], [2]]);
+    }
+}
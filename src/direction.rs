@@ -0,0 +1,75 @@
+use crate::index;
+
+/// One of the crate's line traversals.
+///
+/// Each variant names the function that performs the traversal eagerly, and the lines and
+/// elements are visited in the same order as that function returns them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    /// Diagonals with positive slope, see [`diagonal_pos_pos`](crate::diagonal_pos_pos).
+    DiagonalPosPos,
+    /// Diagonals with positive and negative slope, see
+    /// [`diagonal_pos_neg`](crate::diagonal_pos_neg).
+    DiagonalPosNeg,
+    /// Rows, see [`straight_x`](crate::straight_x).
+    StraightX,
+    /// Columns, see [`straight_y`](crate::straight_y).
+    StraightY,
+}
+
+impl Direction {
+    /// Every direction, in declaration order.
+    pub const ALL: [Direction; 4] = [
+        Direction::DiagonalPosPos,
+        Direction::DiagonalPosNeg,
+        Direction::StraightX,
+        Direction::StraightY,
+    ];
+
+    /// Number of lines in a `rows` x `cols` matrix.
+    pub(crate) fn line_count(self, rows: usize, cols: usize) -> usize {
+        match self {
+            Direction::DiagonalPosPos | Direction::DiagonalPosNeg => {
+                index::diagonal_count(rows, cols)
+            }
+            Direction::StraightX => rows,
+            Direction::StraightY => cols,
+        }
+    }
+
+    /// Index of the line that `(row, col)` of a `rows`-row matrix belongs to.
+    pub(crate) fn line_of(self, rows: usize, (row, col): (usize, usize)) -> usize {
+        match self {
+            Direction::DiagonalPosPos => index::pos_pos_index(rows, row, col),
+            Direction::DiagonalPosNeg => index::pos_neg_index(row, col),
+            Direction::StraightX => row,
+            Direction::StraightY => col,
+        }
+    }
+
+    /// Start coordinate and length of the `index`-th line.
+    pub(crate) fn line_start(
+        self,
+        rows: usize,
+        cols: usize,
+        index: usize,
+    ) -> ((usize, usize), usize) {
+        match self {
+            Direction::DiagonalPosPos => index::pos_pos_start(rows, cols, index),
+            Direction::DiagonalPosNeg => index::pos_neg_start(rows, cols, index),
+            Direction::StraightX => ((index, 0), cols),
+            Direction::StraightY => ((0, index), rows),
+        }
+    }
+
+    /// Coordinate of the `i`-th cell of the line starting at `start`.
+    pub(crate) fn cell(self, (x, y): (usize, usize), i: usize) -> (usize, usize) {
+        match self {
+            Direction::DiagonalPosPos => (x + i, y + i),
+            Direction::DiagonalPosNeg => (x + i, y - i),
+            Direction::StraightX => (x, y + i),
+            Direction::StraightY => (x + i, y),
+        }
+    }
+}
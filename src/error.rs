@@ -0,0 +1,275 @@
+use std::fmt;
+
+/// The ways in which the input of a [`checked`](crate::checked) function can be invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiagonalError {
+    /// A row of the matrix has a different length than the first row.
+    Ragged {
+        /// The index of the offending row.
+        row: usize,
+        /// The length of the first row.
+        expected: usize,
+        /// The length of the offending row.
+        found: usize,
+    },
+    /// The matrix has rows, but all of them are empty.
+    EmptyRows,
+    /// The number of cells or diagonals of a `rows` x `cols` matrix does not fit in a `usize`.
+    DimensionOverflow {
+        /// The requested number of rows.
+        rows: usize,
+        /// The requested number of columns.
+        cols: usize,
+    },
+    /// Two matrices that must have the same shape differ.
+    ShapeMismatch {
+        /// The `(rows, columns)` shape of the first matrix.
+        expected: (usize, usize),
+        /// The `(rows, columns)` shape of the second matrix.
+        found: (usize, usize),
+    },
+    /// A matrix is to be rebuilt from the wrong number of diagonals.
+    DiagonalCount {
+        /// The number of diagonals of the requested shape.
+        expected: usize,
+        /// The number of diagonals given.
+        found: usize,
+    },
+    /// A matrix is to be rebuilt from a diagonal of the wrong length.
+    DiagonalLength {
+        /// The index of the offending diagonal.
+        index: usize,
+        /// The length the diagonal has in the requested shape.
+        expected: usize,
+        /// The length of the diagonal given.
+        found: usize,
+    },
+    /// A diagonal offset does not name a diagonal of a `rows` x `cols` matrix.
+    OffsetOutOfRange {
+        /// The offending offset, `column - row` of the diagonal.
+        offset: isize,
+        /// The number of rows of the matrix.
+        rows: usize,
+        /// The number of columns of the matrix.
+        cols: usize,
+    },
+    /// A function that needs a square matrix was given a rectangular one.
+    NotSquare {
+        /// The number of rows of the matrix.
+        rows: usize,
+        /// The number of columns of the matrix.
+        cols: usize,
+    },
+    /// The block sizes of a block-diagonal split do not add up to the side length of the matrix.
+    BlockSizes {
+        /// The side length of the matrix.
+        side: usize,
+    },
+    /// The rows of a staircase do not all differ in length by one in the same direction.
+    NotStaircase {
+        /// The index of the first row that does not continue the staircase.
+        row: usize,
+    },
+    /// A cell lies outside of a `rows` x `cols` matrix.
+    CellOutOfBounds {
+        /// The `(row, column)` coordinate of the cell.
+        cell: (usize, usize),
+        /// The number of rows of the matrix.
+        rows: usize,
+        /// The number of columns of the matrix.
+        cols: usize,
+    },
+    /// A ray was given the step `(0, 0)`, which never leaves its start.
+    ZeroDirection,
+    /// A sliding window of zero elements was requested.
+    EmptyWindow,
+    /// A convolution kernel has no weights.
+    EmptyKernel,
+}
+
+impl fmt::Display for DiagonalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            DiagonalError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {row} has {found} elements, but the first row has {expected}"
+            ),
+            DiagonalError::EmptyRows => write!(f, "the rows of the matrix are empty"),
+            DiagonalError::DimensionOverflow { rows, cols } => {
+                write!(f, "a {rows}x{cols} matrix is too large")
+            }
+            DiagonalError::ShapeMismatch { expected, found } => write!(
+                f,
+                "expected a {}x{} matrix, found a {}x{} matrix",
+                expected.0, expected.1, found.0, found.1
+            ),
+            DiagonalError::DiagonalCount { expected, found } => {
+                write!(f, "expected {expected} diagonals, found {found}")
+            }
+            DiagonalError::DiagonalLength {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "diagonal {index} has {found} elements, but should have {expected}"
+            ),
+            DiagonalError::OffsetOutOfRange { offset, rows, cols } => {
+                write!(f, "offset {offset} is outside of a {rows}x{cols} matrix")
+            }
+            DiagonalError::NotSquare { rows, cols } => {
+                write!(f, "a {rows}x{cols} matrix is not square")
+            }
+            DiagonalError::BlockSizes { side } => write!(
+                f,
+                "the block sizes do not add up to the side length {side} of the matrix"
+            ),
+            DiagonalError::NotStaircase { row } => {
+                write!(f, "row {row} does not continue the staircase")
+            }
+            DiagonalError::CellOutOfBounds { cell, rows, cols } => write!(
+                f,
+                "cell ({}, {}) is outside of a {rows}x{cols} matrix",
+                cell.0, cell.1
+            ),
+            DiagonalError::ZeroDirection => write!(f, "the direction must not be zero"),
+            DiagonalError::EmptyWindow => write!(f, "the window must not be empty"),
+            DiagonalError::EmptyKernel => write!(f, "the kernel must not be empty"),
+        }
+    }
+}
+
+impl std::error::Error for DiagonalError {}
+
+/// The ways in which a decomposition can fail [`verify_decomposition`](crate::verify_decomposition).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CoverageError {
+    /// The decomposition has a different number of lines than the traversal.
+    LineCount {
+        /// The number of lines of the traversal.
+        expected: usize,
+        /// The number of lines of the decomposition.
+        found: usize,
+    },
+    /// A cell lies outside of the matrix.
+    OutOfBounds {
+        /// The index of the offending line.
+        line: usize,
+        /// The position of the cell within the line.
+        position: usize,
+        /// The `(row, column)` coordinate of the cell.
+        cell: (usize, usize),
+    },
+    /// A cell belongs to a different line of the traversal.
+    WrongLine {
+        /// The index of the offending line.
+        line: usize,
+        /// The position of the cell within the line.
+        position: usize,
+        /// The `(row, column)` coordinate of the cell.
+        cell: (usize, usize),
+    },
+    /// A cell does not directly follow the previous cell of its line.
+    Step {
+        /// The index of the offending line.
+        line: usize,
+        /// The position of the cell within the line.
+        position: usize,
+    },
+    /// A cell of the matrix is not visited.
+    Missing {
+        /// The `(row, column)` coordinate of the cell.
+        cell: (usize, usize),
+    },
+}
+
+impl fmt::Display for CoverageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            CoverageError::LineCount { expected, found } => {
+                write!(f, "expected {expected} lines, found {found}")
+            }
+            CoverageError::OutOfBounds {
+                line,
+                position,
+                cell,
+            } => write!(
+                f,
+                "cell {position} of line {line} at {cell:?} is outside of the matrix"
+            ),
+            CoverageError::WrongLine {
+                line,
+                position,
+                cell,
+            } => write!(
+                f,
+                "cell {position} of line {line} at {cell:?} belongs to another line"
+            ),
+            CoverageError::Step { line, position } => write!(
+                f,
+                "cell {position} of line {line} does not follow the previous cell"
+            ),
+            CoverageError::Missing { cell } => write!(f, "cell {cell:?} is not visited"),
+        }
+    }
+}
+
+impl std::error::Error for CoverageError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let error = DiagonalError::Ragged {
+            row: 2,
+            expected: 3,
+            found: 1,
+        };
+        assert_eq!(
+            error.to_string(),
+            "row 2 has 1 elements, but the first row has 3"
+        );
+        assert_eq!(
+            DiagonalError::ShapeMismatch {
+                expected: (2, 3),
+                found: (3, 2)
+            }
+            .to_string(),
+            "expected a 2x3 matrix, found a 3x2 matrix"
+        );
+
+        assert_eq!(
+            DiagonalError::CellOutOfBounds {
+                cell: (4, 0),
+                rows: 2,
+                cols: 2
+            }
+            .to_string(),
+            "cell (4, 0) is outside of a 2x2 matrix"
+        );
+
+        let boxed: Box<dyn std::error::Error> = Box::new(DiagonalError::EmptyRows);
+        assert_eq!(boxed.to_string(), "the rows of the matrix are empty");
+
+        assert_eq!(
+            CoverageError::Step {
+                line: 2,
+                position: 1
+            }
+            .to_string(),
+            "cell 1 of line 2 does not follow the previous cell"
+        );
+        assert_eq!(
+            CoverageError::Missing { cell: (1, 0) }.to_string(),
+            "cell (1, 0) is not visited"
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! # Fixed-Size Array Operations Module
+//!
+//! This module provides the crate's traversals for matrices whose shape is known at compile
+//! time, stored as `[[T; C]; R]`. Rows and columns have fixed lengths, so they are returned as
+//! nested arrays. Diagonals differ in length and are returned as an array of `Vec`s; the number
+//! of diagonals `N` is usually inferred from the result type and is checked against `R + C - 1`
+//! at compile time.
+//!
+//! ### Example
+//!
+//! ```rust
+//! use diagonal::fixed::{diagonal_pos_pos, straight_y};
+//!
+//! let board = [
+//!     [1, 2, 3],
+//!     [4, 5, 6],
+//! ];
+//!
+//! let diagonals: [Vec<&i32>; 4] = diagonal_pos_pos(&board);
+//! assert_eq!(diagonals[1], vec![&1, &5]);
+//!
+//! let columns: [[&i32; 2]; 3] = straight_y(&board);
+//! assert_eq!(columns[2], [&3, &6]);
+//! ```
+//!
+//! Asking for the wrong number of diagonals does not compile:
+//!
+//! ```compile_fail
+//! use diagonal::fixed::diagonal_pos_pos;
+//!
+//! let diagonals: [Vec<&i32>; 5] = diagonal_pos_pos(&[[1, 2, 3], [4, 5, 6]]);
+//! ```
+
+use crate::Direction;
+
+/// Returns the number of diagonals in either diagonal direction of an `R` x `C` matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::fixed::diagonal_count;
+///
+/// const N: usize = diagonal_count::<3, 4>();
+/// assert_eq!(N, 6);
+/// assert_eq!(diagonal_count::<0, 4>(), 0);
+/// ```
+pub const fn diagonal_count<const R: usize, const C: usize>() -> usize {
+    if R == 0 || C == 0 {
+        0
+    } else {
+        R + C - 1
+    }
+}
+
+/// Collects the `N` diagonals of an `R` x `C` matrix in `direction`.
+fn diagonals<T, const R: usize, const C: usize, const N: usize>(
+    matrix: &[[T; C]; R],
+    direction: Direction,
+) -> [Vec<&T>; N] {
+    const {
+        assert!(
+            N == diagonal_count::<R, C>(),
+            "an R x C matrix has R + C - 1 diagonals"
+        )
+    };
+    std::array::from_fn(|index| {
+        let (start, len) = direction.line_start(R, C, index);
+        (0..len)
+            .map(|i| {
+                let (x, y) = direction.cell(start, i);
+                &matrix[x][y]
+            })
+            .collect()
+    })
+}
+
+/// Extracts diagonals with positive slope from a fixed-size matrix, like
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos).
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to an `R` x `C` array of arrays.
+///
+/// # Returns
+///
+/// An array of `N = R + C - 1` vectors (or none if the matrix is empty) containing references
+/// to the elements along each diagonal. A different `N` is rejected at compile time.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::fixed::diagonal_pos_pos;
+///
+/// let matrix = [
+///     [1, 2],
+///     [3, 4],
+/// ];
+///
+/// let result: [Vec<&i32>; 3] = diagonal_pos_pos(&matrix);
+/// assert_eq!(result, [vec![&3], vec![&1, &4], vec![&2]]);
+/// ```
+pub fn diagonal_pos_pos<T, const R: usize, const C: usize, const N: usize>(
+    matrix: &[[T; C]; R],
+) -> [Vec<&T>; N] {
+    diagonals(matrix, Direction::DiagonalPosPos)
+}
+
+/// Extracts diagonals with positive and negative slope from a fixed-size matrix, like
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg).
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to an `R` x `C` array of arrays.
+///
+/// # Returns
+///
+/// An array of `N = R + C - 1` vectors (or none if the matrix is empty) containing references
+/// to the elements along each diagonal. A different `N` is rejected at compile time.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::fixed::diagonal_pos_neg;
+///
+/// let matrix = [
+///     [1, 2],
+///     [3, 4],
+/// ];
+///
+/// let result: [Vec<&i32>; 3] = diagonal_pos_neg(&matrix);
+/// assert_eq!(result, [vec![&1], vec![&2, &3], vec![&4]]);
+/// ```
+pub fn diagonal_pos_neg<T, const R: usize, const C: usize, const N: usize>(
+    matrix: &[[T; C]; R],
+) -> [Vec<&T>; N] {
+    diagonals(matrix, Direction::DiagonalPosNeg)
+}
+
+/// Extracts the rows of a fixed-size matrix, like [`straight_x`](crate::straight_x).
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to an `R` x `C` array of arrays.
+///
+/// # Returns
+///
+/// An `R` x `C` array of references to the elements of each row.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::fixed::straight_x;
+///
+/// let matrix = [
+///     [1, 2, 3],
+///     [4, 5, 6],
+/// ];
+///
+/// assert_eq!(straight_x(&matrix), [[&1, &2, &3], [&4, &5, &6]]);
+/// ```
+pub fn straight_x<T, const R: usize, const C: usize>(matrix: &[[T; C]; R]) -> [[&T; C]; R] {
+    std::array::from_fn(|x| std::array::from_fn(|y| &matrix[x][y]))
+}
+
+/// Extracts the columns of a fixed-size matrix, like [`straight_y`](crate::straight_y).
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to an `R` x `C` array of arrays.
+///
+/// # Returns
+///
+/// A `C` x `R` array of references to the elements of each column.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::fixed::straight_y;
+///
+/// let matrix = [
+///     [1, 2, 3],
+///     [4, 5, 6],
+/// ];
+///
+/// assert_eq!(straight_y(&matrix), [[&1, &4], [&2, &5], [&3, &6]]);
+/// ```
+pub fn straight_y<T, const R: usize, const C: usize>(matrix: &[[T; C]; R]) -> [[&T; R]; C] {
+    std::array::from_fn(|y| std::array::from_fn(|x| &matrix[x][y]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_slice_traversals() {
+        let matrix = [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]];
+
+        let pos_pos: [Vec<&i32>; 6] = diagonal_pos_pos(&matrix);
+        assert_eq!(pos_pos.to_vec(), crate::diagonal_pos_pos(&matrix));
+        let pos_neg: [Vec<&i32>; 6] = diagonal_pos_neg(&matrix);
+        assert_eq!(pos_neg.to_vec(), crate::diagonal_pos_neg(&matrix));
+        assert_eq!(
+            straight_x(&matrix).map(Vec::from).to_vec(),
+            crate::straight_x(&matrix)
+        );
+        assert_eq!(
+            straight_y(&matrix).map(Vec::from).to_vec(),
+            crate::straight_y(&matrix)
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let matrix: [[u8; 3]; 0] = [];
+        let diagonals: [Vec<&u8>; 0] = diagonal_pos_pos(&matrix);
+        assert!(diagonals.is_empty());
+        assert!(straight_x(&matrix).is_empty());
+        assert_eq!(straight_y(&matrix).len(), 3);
+
+        let matrix: [[u8; 0]; 2] = [[], []];
+        let diagonals: [Vec<&u8>; 0] = diagonal_pos_neg(&matrix);
+        assert!(diagonals.is_empty());
+    }
+}
@@ -0,0 +1,540 @@
+//! # Flat Storage Operations Module
+//!
+//! This module runs the crate's traversals over matrices stored in a single flat buffer instead
+//! of nested rows, without copying or transposing the data first. A buffer is wrapped in a
+//! layout that knows where each `(row, column)` cell lives:
+//!
+//! * [`RowMajor`] for buffers where the cells of a row are contiguous, as in C.
+//! * [`ColMajor`] for buffers where the cells of a column are contiguous, as in Fortran and
+//!   LAPACK.
+//! * [`StridedView`] for any layout with a fixed distance between neighbouring rows and
+//!   columns, which covers submatrices, transposed views and single channels of interleaved
+//!   pixel buffers.
+//!
+//! The traversals return the same lines, in the same order, as their slice-based counterparts
+//! such as [`diagonal_pos_pos`](crate::diagonal_pos_pos) return for the equivalent nested matrix.
+//!
+//! ### Example
+//!
+//! ```rust
+//! use diagonal::flat::{diagonal_pos_pos, ColMajor};
+//!
+//! // The matrix
+//! //   1 2 3
+//! //   4 5 6
+//! // stored column by column.
+//! let data = [1, 4, 2, 5, 3, 6];
+//! let matrix = ColMajor::new(&data, 2, 3);
+//!
+//! assert_eq!(diagonal_pos_pos(&matrix), vec![
+//!     vec![&4],
+//!     vec![&1, &5],
+//!     vec![&2, &6],
+//!     vec![&3],
+//! ]);
+//! ```
+
+use crate::Direction;
+
+/// A matrix stored in a flat buffer, addressed by `(row, column)`.
+pub trait FlatMatrix<'a, T: 'a> {
+    /// Returns the `(rows, columns)` shape of the matrix.
+    fn shape(&self) -> (usize, usize);
+
+    /// Returns the element at `(row, col)`.
+    ///
+    /// # Panics
+    ///
+    /// May panic if the coordinate lies outside of the matrix.
+    fn element(&self, row: usize, col: usize) -> &'a T;
+}
+
+/// Checks that a buffer holds exactly the cells of a `rows` x `cols` matrix.
+fn check_len<T>(data: &[T], rows: usize, cols: usize) {
+    assert!(
+        rows.checked_mul(cols) == Some(data.len()),
+        "data must hold rows * cols elements"
+    );
+}
+
+/// A matrix stored in row-major order: the cell at `(row, col)` is `data[row * cols + col]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RowMajor<'a, T> {
+    data: &'a [T],
+    rows: usize,
+    cols: usize,
+}
+
+impl<'a, T> RowMajor<'a, T> {
+    /// Wraps a row-major buffer holding a `rows` x `cols` matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` does not hold exactly `rows * cols` elements.
+    pub fn new(data: &'a [T], rows: usize, cols: usize) -> Self {
+        check_len(data, rows, cols);
+        RowMajor { data, rows, cols }
+    }
+}
+
+impl<'a, T> FlatMatrix<'a, T> for RowMajor<'a, T> {
+    fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    fn element(&self, row: usize, col: usize) -> &'a T {
+        &self.data[row * self.cols + col]
+    }
+}
+
+/// A matrix stored in column-major order: the cell at `(row, col)` is `data[col * rows + row]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ColMajor<'a, T> {
+    data: &'a [T],
+    rows: usize,
+    cols: usize,
+}
+
+impl<'a, T> ColMajor<'a, T> {
+    /// Wraps a column-major buffer holding a `rows` x `cols` matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` does not hold exactly `rows * cols` elements.
+    pub fn new(data: &'a [T], rows: usize, cols: usize) -> Self {
+        check_len(data, rows, cols);
+        ColMajor { data, rows, cols }
+    }
+}
+
+impl<'a, T> FlatMatrix<'a, T> for ColMajor<'a, T> {
+    fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    fn element(&self, row: usize, col: usize) -> &'a T {
+        &self.data[col * self.rows + row]
+    }
+}
+
+/// A matrix laid out with arbitrary strides: the cell at `(row, col)` is
+/// `data[row * row_stride + col * col_stride]`.
+///
+/// Views never copy the buffer, and reshaping them with [`StridedView::transposed`] or
+/// [`StridedView::submatrix`] only adjusts the strides and the start of the buffer.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::flat::{straight_x, StridedView};
+///
+/// // A 2x2 image with interleaved RGB pixels.
+/// let pixels = [
+///     10, 11, 12, 20, 21, 22,
+///     30, 31, 32, 40, 41, 42,
+/// ];
+///
+/// // The green channel starts at offset 1, with pixels 3 apart and rows 6 apart.
+/// let green = StridedView::new(&pixels[1..], 2, 2, 6, 3);
+/// assert_eq!(straight_x(&green), vec![vec![&11, &21], vec![&31, &41]]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StridedView<'a, T> {
+    data: &'a [T],
+    rows: usize,
+    cols: usize,
+    row_stride: usize,
+    col_stride: usize,
+}
+
+impl<'a, T> StridedView<'a, T> {
+    /// Creates a view of a `rows` x `cols` matrix in `data` with the given strides.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The buffer, starting with the cell at `(0, 0)`.
+    /// * `rows` - The number of rows of the matrix.
+    /// * `cols` - The number of columns of the matrix.
+    /// * `row_stride` - The distance in `data` between a cell and the cell below it.
+    /// * `col_stride` - The distance in `data` between a cell and the cell to its right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the last cell of the matrix lies outside of `data`.
+    pub fn new(
+        data: &'a [T],
+        rows: usize,
+        cols: usize,
+        row_stride: usize,
+        col_stride: usize,
+    ) -> Self {
+        if rows > 0 && cols > 0 {
+            let last = (rows - 1)
+                .checked_mul(row_stride)
+                .zip((cols - 1).checked_mul(col_stride))
+                .and_then(|(a, b)| a.checked_add(b));
+            assert!(
+                last.is_some_and(|last| last < data.len()),
+                "the view must lie inside of data"
+            );
+        }
+        StridedView {
+            data,
+            rows,
+            cols,
+            row_stride,
+            col_stride,
+        }
+    }
+
+    /// Returns a view of the transpose of the matrix, by swapping the strides.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use diagonal::flat::{straight_x, StridedView};
+    ///
+    /// let data = [1, 2, 3, 4, 5, 6];
+    /// let view = StridedView::new(&data, 2, 3, 3, 1).transposed();
+    ///
+    /// assert_eq!(straight_x(&view), vec![vec![&1, &4], vec![&2, &5], vec![&3, &6]]);
+    /// ```
+    pub fn transposed(self) -> Self {
+        StridedView {
+            rows: self.cols,
+            cols: self.rows,
+            row_stride: self.col_stride,
+            col_stride: self.row_stride,
+            ..self
+        }
+    }
+
+    /// Returns a view of the `rows` x `cols` submatrix whose top-left cell is `(top, left)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the submatrix does not lie inside of the matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use diagonal::flat::{diagonal_pos_neg, StridedView};
+    ///
+    /// let data = [
+    ///     1, 2, 3,
+    ///     4, 5, 6,
+    ///     7, 8, 9,
+    /// ];
+    /// let view = StridedView::new(&data, 3, 3, 3, 1).submatrix(1, 1, 2, 2);
+    ///
+    /// assert_eq!(diagonal_pos_neg(&view), vec![vec![&5], vec![&6, &8], vec![&9]]);
+    /// ```
+    pub fn submatrix(self, top: usize, left: usize, rows: usize, cols: usize) -> Self {
+        assert!(
+            top.checked_add(rows)
+                .is_some_and(|bottom| bottom <= self.rows)
+                && left
+                    .checked_add(cols)
+                    .is_some_and(|right| right <= self.cols),
+            "the submatrix must lie inside of the matrix"
+        );
+        // Only a submatrix with cells is guaranteed to start inside of the buffer.
+        let offset = top
+            .checked_mul(self.row_stride)
+            .zip(left.checked_mul(self.col_stride))
+            .and_then(|(a, b)| a.checked_add(b))
+            .filter(|_| rows > 0 && cols > 0);
+        StridedView {
+            data: offset
+                .and_then(|offset| self.data.get(offset..))
+                .unwrap_or_default(),
+            rows,
+            cols,
+            ..self
+        }
+    }
+}
+
+impl<'a, T> FlatMatrix<'a, T> for StridedView<'a, T> {
+    fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    fn element(&self, row: usize, col: usize) -> &'a T {
+        &self.data[row * self.row_stride + col * self.col_stride]
+    }
+}
+
+impl<'a, T> From<RowMajor<'a, T>> for StridedView<'a, T> {
+    fn from(matrix: RowMajor<'a, T>) -> Self {
+        StridedView::new(matrix.data, matrix.rows, matrix.cols, matrix.cols, 1)
+    }
+}
+
+impl<'a, T> From<ColMajor<'a, T>> for StridedView<'a, T> {
+    fn from(matrix: ColMajor<'a, T>) -> Self {
+        StridedView::new(matrix.data, matrix.rows, matrix.cols, 1, matrix.rows)
+    }
+}
+
+/// Extracts the lines of a flat matrix in one direction.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix stored in a flat buffer.
+/// * `direction` - The traversal to perform.
+///
+/// # Returns
+///
+/// A vector of vectors containing references to the elements along the lines, in the same
+/// order as the function named by `direction` returns them for the equivalent nested matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::Direction;
+/// use diagonal::flat::{lines, RowMajor};
+///
+/// let data = [1, 2, 3, 4, 5, 6];
+/// let matrix = RowMajor::new(&data, 2, 3);
+///
+/// assert_eq!(lines(&matrix, Direction::DiagonalPosNeg), vec![
+///     vec![&1],
+///     vec![&2, &4],
+///     vec![&3, &5],
+///     vec![&6],
+/// ]);
+/// ```
+pub fn lines<'a, M: FlatMatrix<'a, T> + ?Sized, T: 'a>(
+    matrix: &M,
+    direction: Direction,
+) -> Vec<Vec<&'a T>> {
+    let (rows, cols) = line_shape(matrix);
+    (0..direction.line_count(rows, cols))
+        .map(|line| {
+            let (start, len) = direction.line_start(rows, cols, line);
+            (0..len)
+                .map(|i| {
+                    let (x, y) = direction.cell(start, i);
+                    matrix.element(x, y)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Extracts diagonals with positive slope from a flat matrix, like
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos).
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::flat::{diagonal_pos_pos, RowMajor};
+///
+/// let data = [1, 2, 3, 4];
+///
+/// assert_eq!(diagonal_pos_pos(&RowMajor::new(&data, 2, 2)), vec![
+///     vec![&3],
+///     vec![&1, &4],
+///     vec![&2],
+/// ]);
+/// ```
+pub fn diagonal_pos_pos<'a, M: FlatMatrix<'a, T> + ?Sized, T: 'a>(matrix: &M) -> Vec<Vec<&'a T>> {
+    lines(matrix, Direction::DiagonalPosPos)
+}
+
+/// Extracts diagonals with positive and negative slope from a flat matrix, like
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg).
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::flat::{diagonal_pos_neg, ColMajor};
+///
+/// let data = [1, 3, 2, 4];
+///
+/// assert_eq!(diagonal_pos_neg(&ColMajor::new(&data, 2, 2)), vec![
+///     vec![&1],
+///     vec![&2, &3],
+///     vec![&4],
+/// ]);
+/// ```
+pub fn diagonal_pos_neg<'a, M: FlatMatrix<'a, T> + ?Sized, T: 'a>(matrix: &M) -> Vec<Vec<&'a T>> {
+    lines(matrix, Direction::DiagonalPosNeg)
+}
+
+/// Extracts the rows of a flat matrix, like [`straight_x`](crate::straight_x).
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::flat::{straight_x, ColMajor};
+///
+/// let data = [1, 3, 2, 4];
+///
+/// assert_eq!(straight_x(&ColMajor::new(&data, 2, 2)), vec![
+///     vec![&1, &2],
+///     vec![&3, &4],
+/// ]);
+/// ```
+pub fn straight_x<'a, M: FlatMatrix<'a, T> + ?Sized, T: 'a>(matrix: &M) -> Vec<Vec<&'a T>> {
+    lines(matrix, Direction::StraightX)
+}
+
+/// Extracts the columns of a flat matrix, like [`straight_y`](crate::straight_y).
+///
+/// Like a nested matrix without rows, a layout without rows has no columns.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::flat::{straight_y, RowMajor};
+///
+/// let data = [1, 2, 3, 4];
+///
+/// assert_eq!(straight_y(&RowMajor::new(&data, 2, 2)), vec![
+///     vec![&1, &3],
+///     vec![&2, &4],
+/// ]);
+/// ```
+pub fn straight_y<'a, M: FlatMatrix<'a, T> + ?Sized, T: 'a>(matrix: &M) -> Vec<Vec<&'a T>> {
+    lines(matrix, Direction::StraightY)
+}
+
+/// Returns the shape of a flat matrix as the slice traversals would see it.
+///
+/// A nested matrix without rows has no first row to take the column count from, so it has no
+/// columns either.
+fn line_shape<'a, M: FlatMatrix<'a, T> + ?Sized, T: 'a>(matrix: &M) -> (usize, usize) {
+    match matrix.shape() {
+        (0, _) => (0, 0),
+        shape => shape,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nested(rows: usize, cols: usize) -> Vec<Vec<usize>> {
+        (0..rows)
+            .map(|r| (0..cols).map(|c| r * cols + c).collect())
+            .collect()
+    }
+
+    #[test]
+    fn layouts_match_nested_traversals() {
+        for rows in 0..5 {
+            for cols in 0..5 {
+                let matrix = nested(rows, cols);
+                let row_major: Vec<usize> = matrix.concat();
+                let col_major: Vec<usize> = (0..cols)
+                    .flat_map(|c| matrix.iter().map(move |row| row[c]))
+                    .collect();
+                let row_major = RowMajor::new(&row_major, rows, cols);
+                let col_major = ColMajor::new(&col_major, rows, cols);
+
+                for direction in Direction::ALL {
+                    let expected: Vec<Vec<&usize>> = crate::lines_iter(&matrix, direction)
+                        .map(Iterator::collect)
+                        .collect();
+                    assert_eq!(lines(&row_major, direction), expected);
+                    assert_eq!(lines(&col_major, direction), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shorthands() {
+        let data = [1, 2, 3, 4, 5, 6];
+        let matrix = ColMajor::new(&data, 3, 2);
+        assert_eq!(
+            straight_x(&matrix),
+            vec![vec![&1, &4], vec![&2, &5], vec![&3, &6]]
+        );
+        assert_eq!(
+            straight_y(&matrix),
+            vec![vec![&1, &2, &3], vec![&4, &5, &6]]
+        );
+        assert_eq!(
+            diagonal_pos_pos(&matrix),
+            lines(&matrix, Direction::DiagonalPosPos)
+        );
+        assert_eq!(
+            diagonal_pos_neg(&matrix),
+            vec![vec![&1], vec![&4, &2], vec![&5, &3], vec![&6]]
+        );
+    }
+
+    #[test]
+    fn strided_views() {
+        let matrix = nested(4, 5);
+        let data = matrix.concat();
+        let row_major = RowMajor::new(&data, 4, 5);
+        let view = StridedView::from(row_major);
+        let transposed = crate::transposed(&matrix);
+
+        for direction in Direction::ALL {
+            assert_eq!(lines(&view, direction), lines(&row_major, direction));
+
+            let expected: Vec<Vec<&usize>> = crate::lines_iter(&transposed, direction)
+                .map(Iterator::collect)
+                .collect();
+            assert_eq!(lines(&view.transposed(), direction), expected);
+
+            let sub: Vec<Vec<usize>> = matrix[1..3].iter().map(|row| row[2..5].to_vec()).collect();
+            let expected: Vec<Vec<&usize>> = crate::lines_iter(&sub, direction)
+                .map(Iterator::collect)
+                .collect();
+            assert_eq!(lines(&view.submatrix(1, 2, 2, 3), direction), expected);
+        }
+
+        let col_major: Vec<usize> = (0..5)
+            .flat_map(|c| matrix.iter().map(move |row| row[c]))
+            .collect();
+        assert_eq!(
+            straight_y(&StridedView::from(ColMajor::new(&col_major, 4, 5))),
+            straight_y(&view)
+        );
+        assert!(straight_x(&view.submatrix(4, 5, 0, 0)).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn strided_view_outside_of_data() {
+        StridedView::new(&[1, 2, 3, 4], 2, 2, 3, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn submatrix_outside_of_view() {
+        StridedView::new(&[1, 2, 3, 4], 2, 2, 2, 1).submatrix(1, 0, 2, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrong_length() {
+        ColMajor::new(&[1, 2, 3], 2, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn overflowing_shape() {
+        RowMajor::new(&[0u8; 0], usize::MAX, 2);
+    }
+
+    #[test]
+    fn empty_submatrices() {
+        let data = [1, 2, 3];
+        // Views without cells are not checked against the buffer, so their strides can be huge.
+        let view = StridedView::new(&data, 4, 0, usize::MAX, 1);
+        let empty = view.submatrix(4, 0, 0, 0);
+        assert_eq!(empty.shape(), (0, 0));
+        assert!(straight_x(&empty).is_empty());
+
+        let view = StridedView::new(&data, 1, 3, usize::MAX, 1);
+        assert!(straight_y(&view.submatrix(1, 0, 0, 3)).is_empty());
+        assert_eq!(straight_x(&view.submatrix(0, 1, 1, 2)), vec![vec![&2, &3]]);
+    }
+}
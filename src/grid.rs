@@ -0,0 +1,464 @@
+//! # Grid Operations Module
+//!
+//! This module provides [`Grid`], an owned rectangular matrix that is addressed with signed
+//! `(row, column)` coordinates. The grid's origin is the coordinate of its top-left cell, so a
+//! world centered at `(0, 0)` can be stored without translating indices by hand. All traversals
+//! report the coordinates of the cells they visit in that same coordinate system.
+//!
+//! ### Example
+//!
+//! ```rust
+//! use diagonal::grid::Grid;
+//!
+//! // A 3x3 world centered at (0, 0).
+//! let grid = Grid::new(vec![
+//!     vec![1, 2, 3],
+//!     vec![4, 5, 6],
+//!     vec![7, 8, 9],
+//! ], (-1, -1));
+//!
+//! assert_eq!(grid.get((0, 0)), Some(&5));
+//! assert_eq!(grid.diagonal_pos_pos()[2], vec![
+//!     ((-1, -1), &1),
+//!     ((0, 0), &5),
+//!     ((1, 1), &9),
+//! ]);
+//! ```
+
+use crate::index;
+
+/// A rectangular matrix addressed with signed `(row, column)` coordinates.
+///
+/// With the `serde` feature, grids serialize as their `cells` and `origin`. Deserialization
+/// checks the same invariants as [`Grid::new`] and fails instead of panicking.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "RawGrid<T>",
+        bound(deserialize = "T: serde::Deserialize<'de>")
+    )
+)]
+pub struct Grid<T> {
+    cells: Vec<Vec<T>>,
+    origin: (i64, i64),
+}
+
+/// The serialized form of a [`Grid`], before its invariants are checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawGrid<T> {
+    cells: Vec<Vec<T>>,
+    origin: (i64, i64),
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<RawGrid<T>> for Grid<T> {
+    type Error = &'static str;
+
+    fn try_from(RawGrid { cells, origin }: RawGrid<T>) -> Result<Self, Self::Error> {
+        validate(&cells, origin)?;
+        Ok(Grid { cells, origin })
+    }
+}
+
+/// Checks that `cells` are rectangular and that every coordinate fits in an `i64`.
+fn validate<T>(cells: &[Vec<T>], origin: (i64, i64)) -> Result<(), &'static str> {
+    let (rows, cols) = index::shape(cells);
+    if cells.iter().any(|row| row.len() != cols) {
+        return Err("all rows of a grid must have the same length");
+    }
+    let fits = |start: i64, len: usize| {
+        len == 0 || i64::try_from(len - 1).is_ok_and(|last| start.checked_add(last).is_some())
+    };
+    if !(fits(origin.0, rows) && fits(origin.1, cols)) {
+        return Err("grid extends past the coordinate range");
+    }
+    Ok(())
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its rows and the coordinate of its top-left cell.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows differ in length, or if the grid would extend past `i64::MAX` in
+    /// either direction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use diagonal::grid::Grid;
+    ///
+    /// let grid = Grid::new(vec![vec!['a', 'b'], vec!['c', 'd']], (10, -3));
+    /// assert_eq!(grid.get((11, -3)), Some(&'c'));
+    /// assert_eq!(grid.get((0, 0)), None);
+    /// ```
+    pub fn new(cells: Vec<Vec<T>>, origin: (i64, i64)) -> Self {
+        if let Err(message) = validate(&cells, origin) {
+            panic!("{message}");
+        }
+        Grid { cells, origin }
+    }
+
+    /// Creates a `rows` x `cols` grid with its origin at `(0, 0)` by computing every cell from
+    /// its 0-based `(row, column)` index.
+    ///
+    /// `f` is called row by row, from left to right.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use diagonal::grid::Grid;
+    ///
+    /// let grid = Grid::generate(2, 3, |row, col| row * 3 + col);
+    /// assert_eq!(grid.cells(), [[0, 1, 2], [3, 4, 5]]);
+    /// assert_eq!(grid.origin(), (0, 0));
+    /// ```
+    pub fn generate(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..rows)
+            .map(|row| (0..cols).map(|col| f(row, col)).collect())
+            .collect();
+        Grid {
+            cells,
+            origin: (0, 0),
+        }
+    }
+
+    /// Creates a `rows` x `cols` grid with its origin at `(0, 0)` by computing every cell from
+    /// the diagonal it lies on and its position along that diagonal.
+    ///
+    /// Diagonals and positions are numbered as in [`diagonal_pos_pos`](crate::diagonal_pos_pos):
+    /// diagonal `0` is the bottom-left corner, and position `0` is the top-left end of every
+    /// diagonal. `f` is called in that same traversal order, so
+    /// `Grid::generate_by_diagonal(rows, cols, f).diagonal_pos_pos()` hands back the values in
+    /// the order they were generated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use diagonal::grid::Grid;
+    ///
+    /// let grid = Grid::generate_by_diagonal(2, 3, |diag, pos| diag * 10 + pos);
+    /// assert_eq!(grid.cells(), [[10, 20, 30], [0, 11, 21]]);
+    ///
+    /// // A Toeplitz matrix: constant along every diagonal.
+    /// let toeplitz = Grid::generate_by_diagonal(3, 3, |diag, _| diag as i32 - 2);
+    /// assert_eq!(toeplitz.cells(), [[0, 1, 2], [-1, 0, 1], [-2, -1, 0]]);
+    /// ```
+    pub fn generate_by_diagonal(
+        rows: usize,
+        cols: usize,
+        mut f: impl FnMut(usize, usize) -> T,
+    ) -> Self {
+        let diagonals = (0..index::diagonal_count(rows, cols))
+            .map(|diag| {
+                let (_, len) = index::pos_pos_start(rows, cols, diag);
+                (0..len).map(|pos| f(diag, pos)).collect()
+            })
+            .collect();
+        Grid {
+            cells: index::reassemble(rows, cols, diagonals, |row, col| {
+                index::pos_pos_index(rows, row, col)
+            }),
+            origin: (0, 0),
+        }
+    }
+
+    /// Returns the coordinate of the top-left cell.
+    pub fn origin(&self) -> (i64, i64) {
+        self.origin
+    }
+
+    /// Returns the number of rows and columns of the grid.
+    pub fn shape(&self) -> (usize, usize) {
+        index::shape(&self.cells)
+    }
+
+    /// Returns the rows of the grid, indexed from zero.
+    pub fn cells(&self) -> &[Vec<T>] {
+        &self.cells
+    }
+
+    /// Consumes the grid and returns its rows, indexed from zero.
+    pub fn into_cells(self) -> Vec<Vec<T>> {
+        self.cells
+    }
+
+    /// Converts a coordinate to a 0-based `(row, column)` index, or `None` if it lies outside of
+    /// the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use diagonal::grid::Grid;
+    ///
+    /// let grid = Grid::new(vec![vec![0; 3]; 2], (-1, -1));
+    /// assert_eq!(grid.to_index((0, 1)), Some((1, 2)));
+    /// assert_eq!(grid.to_index((1, 1)), None);
+    /// ```
+    pub fn to_index(&self, (row, col): (i64, i64)) -> Option<(usize, usize)> {
+        let (rows, cols) = self.shape();
+        let offset = |value: i64, start: i64, len: usize| {
+            value
+                .checked_sub(start)
+                .and_then(|offset| usize::try_from(offset).ok())
+                .filter(|&offset| offset < len)
+        };
+        Some((
+            offset(row, self.origin.0, rows)?,
+            offset(col, self.origin.1, cols)?,
+        ))
+    }
+
+    /// Converts a 0-based `(row, column)` index to a coordinate.
+    ///
+    /// The index does not have to lie inside the grid, as long as the coordinate fits in an
+    /// `i64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use diagonal::grid::Grid;
+    ///
+    /// let grid = Grid::new(vec![vec![0; 3]; 2], (-1, -1));
+    /// assert_eq!(grid.to_coord((1, 2)), (0, 1));
+    /// ```
+    pub fn to_coord(&self, (row, col): (usize, usize)) -> (i64, i64) {
+        (
+            self.origin.0.wrapping_add_unsigned(row as u64),
+            self.origin.1.wrapping_add_unsigned(col as u64),
+        )
+    }
+
+    /// Returns a reference to the cell at a coordinate, or `None` if it lies outside of the
+    /// grid.
+    pub fn get(&self, coord: (i64, i64)) -> Option<&T> {
+        let (row, col) = self.to_index(coord)?;
+        Some(&self.cells[row][col])
+    }
+
+    /// Returns a mutable reference to the cell at a coordinate, or `None` if it lies outside of
+    /// the grid.
+    pub fn get_mut(&mut self, coord: (i64, i64)) -> Option<&mut T> {
+        let (row, col) = self.to_index(coord)?;
+        Some(&mut self.cells[row][col])
+    }
+
+    /// Extracts diagonals with positive slope, like [`diagonal_pos_pos`](crate::diagonal_pos_pos),
+    /// together with the coordinate of every cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use diagonal::grid::Grid;
+    ///
+    /// let grid = Grid::new(vec![vec![1, 2], vec![3, 4]], (5, 5));
+    /// assert_eq!(grid.diagonal_pos_pos(), vec![
+    ///     vec![((6, 5), &3)],
+    ///     vec![((5, 5), &1), ((6, 6), &4)],
+    ///     vec![((5, 6), &2)],
+    /// ]);
+    /// ```
+    pub fn diagonal_pos_pos(&self) -> Vec<Vec<((i64, i64), &T)>> {
+        let (rows, cols) = self.shape();
+        self.collect(index::pos_pos_cells(rows, cols))
+    }
+
+    /// Extracts diagonals with positive and negative slope, like
+    /// [`diagonal_pos_neg`](crate::diagonal_pos_neg), together with the coordinate of every cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use diagonal::grid::Grid;
+    ///
+    /// let grid = Grid::new(vec![vec![1, 2], vec![3, 4]], (5, 5));
+    /// assert_eq!(grid.diagonal_pos_neg(), vec![
+    ///     vec![((5, 5), &1)],
+    ///     vec![((5, 6), &2), ((6, 5), &3)],
+    ///     vec![((6, 6), &4)],
+    /// ]);
+    /// ```
+    pub fn diagonal_pos_neg(&self) -> Vec<Vec<((i64, i64), &T)>> {
+        let (rows, cols) = self.shape();
+        self.collect(index::pos_neg_cells(rows, cols))
+    }
+
+    /// Extracts the rows, like [`straight_x`](crate::straight_x), together with the coordinate
+    /// of every cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use diagonal::grid::Grid;
+    ///
+    /// let grid = Grid::new(vec![vec![1, 2], vec![3, 4]], (0, -1));
+    /// assert_eq!(grid.straight_x(), vec![
+    ///     vec![((0, -1), &1), ((0, 0), &2)],
+    ///     vec![((1, -1), &3), ((1, 0), &4)],
+    /// ]);
+    /// ```
+    pub fn straight_x(&self) -> Vec<Vec<((i64, i64), &T)>> {
+        let (rows, cols) = self.shape();
+        self.collect((0..rows).map(|row| (0..cols).map(move |col| (row, col))))
+    }
+
+    /// Extracts the columns, like [`straight_y`](crate::straight_y), together with the
+    /// coordinate of every cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use diagonal::grid::Grid;
+    ///
+    /// let grid = Grid::new(vec![vec![1, 2], vec![3, 4]], (0, -1));
+    /// assert_eq!(grid.straight_y(), vec![
+    ///     vec![((0, -1), &1), ((1, -1), &3)],
+    ///     vec![((0, 0), &2), ((1, 0), &4)],
+    /// ]);
+    /// ```
+    pub fn straight_y(&self) -> Vec<Vec<((i64, i64), &T)>> {
+        let (rows, cols) = self.shape();
+        self.collect((0..cols).map(|col| (0..rows).map(move |row| (row, col))))
+    }
+
+    /// Resolves every cell of every line to its coordinate and element.
+    fn collect(
+        &self,
+        lines: impl Iterator<Item = impl Iterator<Item = (usize, usize)>>,
+    ) -> Vec<Vec<((i64, i64), &T)>> {
+        lines
+            .map(|line| {
+                line.map(|(row, col)| (self.to_coord((row, col)), &self.cells[row][col]))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::owned;
+
+    #[test]
+    fn coordinates_round_trip() {
+        let grid = Grid::new(vec![vec![0; 4]; 3], (-1, -2));
+        for row in 0..3 {
+            for col in 0..4 {
+                assert_eq!(grid.to_index(grid.to_coord((row, col))), Some((row, col)));
+            }
+        }
+        assert_eq!(grid.to_index((-2, 0)), None);
+        assert_eq!(grid.to_index((0, 2)), None);
+    }
+
+    #[test]
+    fn generated_grids() {
+        for rows in 0..4 {
+            for cols in 0..4 {
+                let grid = Grid::generate(rows, cols, |row, col| (row, col));
+                assert_eq!(grid.shape(), if rows == 0 { (0, 0) } else { (rows, cols) });
+                for (coord, &cell) in grid.straight_x().into_iter().flatten() {
+                    assert_eq!(grid.to_index(coord), Some(cell));
+                }
+
+                let mut order = Vec::new();
+                let grid = Grid::generate_by_diagonal(rows, cols, |diag, pos| {
+                    order.push((diag, pos));
+                    (diag, pos)
+                });
+                assert_eq!(grid.shape(), if rows == 0 { (0, 0) } else { (rows, cols) });
+                let expected: Vec<(usize, usize)> = grid
+                    .diagonal_pos_pos()
+                    .into_iter()
+                    .enumerate()
+                    .flat_map(|(diag, line)| (0..line.len()).map(move |pos| (diag, pos)))
+                    .collect();
+                assert_eq!(order, expected);
+                let values: Vec<(usize, usize)> = grid
+                    .diagonal_pos_pos()
+                    .into_iter()
+                    .flatten()
+                    .map(|(_, &value)| value)
+                    .collect();
+                assert_eq!(values, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn traversals_match_slices() {
+        let cells = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let grid = Grid::new(cells.clone(), (100, -100));
+        let values = |lines: Vec<Vec<((i64, i64), &i32)>>| -> Vec<Vec<i32>> {
+            lines
+                .into_iter()
+                .map(|line| line.into_iter().map(|(_, &value)| value).collect())
+                .collect()
+        };
+        assert_eq!(
+            values(grid.diagonal_pos_pos()),
+            owned(crate::diagonal_pos_pos(&cells))
+        );
+        assert_eq!(
+            values(grid.diagonal_pos_neg()),
+            owned(crate::diagonal_pos_neg(&cells))
+        );
+        assert_eq!(values(grid.straight_x()), owned(crate::straight_x(&cells)));
+        assert_eq!(values(grid.straight_y()), owned(crate::straight_y(&cells)));
+
+        for line in grid.diagonal_pos_neg() {
+            for (coord, value) in line {
+                assert_eq!(grid.get(coord), Some(value));
+            }
+        }
+    }
+
+    #[test]
+    fn get_mut() {
+        let mut grid = Grid::new(vec![vec![0; 2]; 2], (-1, -1));
+        *grid.get_mut((0, 0)).unwrap() = 7;
+        assert!(grid.get_mut((1, 0)).is_none());
+        assert_eq!(grid.into_cells(), vec![vec![0, 0], vec![0, 7]]);
+    }
+
+    #[test]
+    fn empty() {
+        let grid: Grid<u8> = Grid::new(vec![], (i64::MAX, i64::MAX));
+        assert_eq!(grid.shape(), (0, 0));
+        assert!(grid.diagonal_pos_pos().is_empty());
+        assert_eq!(grid.get((i64::MAX, i64::MAX)), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let grid = Grid::new(vec![vec![1, 2], vec![3, 4]], (-1, 5));
+        let json = serde_json::to_string(&grid).unwrap();
+        assert_eq!(json, r#"{"cells":[[1,2],[3,4]],"origin":[-1,5]}"#);
+        assert_eq!(serde_json::from_str::<Grid<i32>>(&json).unwrap(), grid);
+
+        assert!(
+            serde_json::from_str::<Grid<i32>>(r#"{"cells":[[1,2],[3]],"origin":[0,0]}"#).is_err()
+        );
+        assert!(serde_json::from_str::<Grid<i32>>(
+            r#"{"cells":[[1,2]],"origin":[0,9223372036854775807]}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn ragged() {
+        Grid::new(vec![vec![1, 2], vec![3]], (0, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_range() {
+        Grid::new(vec![vec![1, 2]], (0, i64::MAX));
+    }
+}
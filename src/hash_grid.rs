@@ -0,0 +1,396 @@
+//! # Hash Grid Operations Module
+//!
+//! This module provides the crate's traversals for grids stored as a
+//! `HashMap<(i64, i64), T>`, keyed by `(row, column)`. Such grids have no inherent size, so
+//! every traversal takes explicit [`Bounds`]; cells inside the bounds that are missing from the
+//! map are returned as `None`.
+//!
+//! The functions mirror their slice-based counterparts in the crate root: the bounds are treated
+//! like a matrix whose top-left cell is `(bounds.min_row, bounds.min_col)`.
+//!
+//! ### Example
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use diagonal::hash_grid::{diagonal_pos_neg, Bounds};
+//!
+//! let grid = HashMap::from([((-1, -1), 'a'), ((-1, 0), 'b'), ((0, 0), 'c')]);
+//! let bounds = Bounds::of(&grid).unwrap();
+//!
+//! assert_eq!(diagonal_pos_neg(&grid, bounds), vec![
+//!     vec![Some(&'a')],
+//!     vec![Some(&'b'), None],
+//!     vec![Some(&'c')],
+//! ]);
+//! ```
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use crate::index;
+
+/// An inclusive rectangle of `(row, column)` coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bounds {
+    /// The smallest row inside the bounds.
+    pub min_row: i64,
+    /// The smallest column inside the bounds.
+    pub min_col: i64,
+    /// The largest row inside the bounds.
+    pub max_row: i64,
+    /// The largest column inside the bounds.
+    pub max_col: i64,
+}
+
+impl Bounds {
+    /// Creates bounds spanning from the `min` corner to the `max` corner, both inclusive.
+    ///
+    /// If `max` lies above or to the left of `min`, the bounds are empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use diagonal::hash_grid::Bounds;
+    ///
+    /// let bounds = Bounds::new((-2, 0), (2, 3));
+    /// assert_eq!(bounds.shape(), (5, 4));
+    /// ```
+    pub fn new(min: (i64, i64), max: (i64, i64)) -> Self {
+        Bounds {
+            min_row: min.0,
+            min_col: min.1,
+            max_row: max.0,
+            max_col: max.1,
+        }
+    }
+
+    /// Returns the smallest bounds containing every key of `grid`, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use diagonal::hash_grid::Bounds;
+    ///
+    /// let grid = HashMap::from([((3, -1), 1), ((-2, 4), 2)]);
+    /// assert_eq!(Bounds::of(&grid), Some(Bounds::new((-2, -1), (3, 4))));
+    /// ```
+    pub fn of<T, S>(grid: &HashMap<(i64, i64), T, S>) -> Option<Self> {
+        grid.keys().fold(None, |bounds, &(row, col)| {
+            Some(match bounds {
+                None => Bounds::new((row, col), (row, col)),
+                Some(b) => Bounds::new(
+                    (b.min_row.min(row), b.min_col.min(col)),
+                    (b.max_row.max(row), b.max_col.max(col)),
+                ),
+            })
+        })
+    }
+
+    /// Returns the number of rows and columns inside the bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bounds span more rows or columns than fit in a `usize`.
+    pub fn shape(&self) -> (usize, usize) {
+        let span = |min: i64, max: i64| -> usize {
+            if max < min {
+                return 0;
+            }
+            usize::try_from(max.abs_diff(min))
+                .ok()
+                .and_then(|len| len.checked_add(1))
+                .expect("bounds are too large to traverse")
+        };
+        (
+            span(self.min_row, self.max_row),
+            span(self.min_col, self.max_col),
+        )
+    }
+
+    /// Converts a 0-based `(row, column)` offset inside the bounds to a grid coordinate.
+    fn key(&self, (row, col): (usize, usize)) -> (i64, i64) {
+        // Offsets are always smaller than the span, so they fit into the bounds' coordinates.
+        (
+            self.min_row.wrapping_add_unsigned(row as u64),
+            self.min_col.wrapping_add_unsigned(col as u64),
+        )
+    }
+}
+
+/// Extracts diagonals with positive slope from a hash grid, like
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos).
+///
+/// # Arguments
+///
+/// * `grid` - A reference to a map from `(row, column)` coordinates to elements.
+/// * `bounds` - The rectangle of the grid to traverse.
+///
+/// # Returns
+///
+/// A vector of vectors containing the elements along each diagonal, with `None` for cells that
+/// are missing from `grid`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use diagonal::hash_grid::{diagonal_pos_pos, Bounds};
+///
+/// let grid = HashMap::from([((0, 0), 1), ((1, 1), 2), ((1, 0), 3)]);
+///
+/// let result = diagonal_pos_pos(&grid, Bounds::new((0, 0), (1, 1)));
+/// assert_eq!(result, vec![
+///     vec![Some(&3)],
+///     vec![Some(&1), Some(&2)],
+///     vec![None],
+/// ]);
+/// ```
+pub fn diagonal_pos_pos<T, S: BuildHasher>(
+    grid: &HashMap<(i64, i64), T, S>,
+    bounds: Bounds,
+) -> Vec<Vec<Option<&T>>> {
+    let (rows, cols) = bounds.shape();
+    collect(grid, bounds, index::pos_pos_cells(rows, cols))
+}
+
+/// Extracts diagonals with positive and negative slope from a hash grid, like
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg).
+///
+/// # Arguments
+///
+/// * `grid` - A reference to a map from `(row, column)` coordinates to elements.
+/// * `bounds` - The rectangle of the grid to traverse.
+///
+/// # Returns
+///
+/// A vector of vectors containing the elements along each diagonal, with `None` for cells that
+/// are missing from `grid`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use diagonal::hash_grid::{diagonal_pos_neg, Bounds};
+///
+/// let grid = HashMap::from([((0, 0), 1), ((1, 1), 2), ((1, 0), 3)]);
+///
+/// let result = diagonal_pos_neg(&grid, Bounds::new((0, 0), (1, 1)));
+/// assert_eq!(result, vec![
+///     vec![Some(&1)],
+///     vec![None, Some(&3)],
+///     vec![Some(&2)],
+/// ]);
+/// ```
+pub fn diagonal_pos_neg<T, S: BuildHasher>(
+    grid: &HashMap<(i64, i64), T, S>,
+    bounds: Bounds,
+) -> Vec<Vec<Option<&T>>> {
+    let (rows, cols) = bounds.shape();
+    collect(grid, bounds, index::pos_neg_cells(rows, cols))
+}
+
+/// Extracts the rows of a hash grid, like [`straight_x`](crate::straight_x).
+///
+/// # Arguments
+///
+/// * `grid` - A reference to a map from `(row, column)` coordinates to elements.
+/// * `bounds` - The rectangle of the grid to traverse.
+///
+/// # Returns
+///
+/// A vector of vectors containing the elements of each row, with `None` for cells that are
+/// missing from `grid`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use diagonal::hash_grid::{straight_x, Bounds};
+///
+/// let grid = HashMap::from([((0, 0), 1), ((1, 1), 2), ((1, 0), 3)]);
+///
+/// let result = straight_x(&grid, Bounds::new((0, 0), (1, 1)));
+/// assert_eq!(result, vec![
+///     vec![Some(&1), None],
+///     vec![Some(&3), Some(&2)],
+/// ]);
+/// ```
+pub fn straight_x<T, S: BuildHasher>(
+    grid: &HashMap<(i64, i64), T, S>,
+    bounds: Bounds,
+) -> Vec<Vec<Option<&T>>> {
+    let (rows, cols) = line_shape(bounds);
+    collect(
+        grid,
+        bounds,
+        (0..rows).map(|row| (0..cols).map(move |col| (row, col))),
+    )
+}
+
+/// Extracts the columns of a hash grid, like [`straight_y`](crate::straight_y).
+///
+/// Like a slice matrix without rows, bounds without rows have no columns.
+///
+/// # Arguments
+///
+/// * `grid` - A reference to a map from `(row, column)` coordinates to elements.
+/// * `bounds` - The rectangle of the grid to traverse.
+///
+/// # Returns
+///
+/// A vector of vectors containing the elements of each column, with `None` for cells that are
+/// missing from `grid`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use diagonal::hash_grid::{straight_y, Bounds};
+///
+/// let grid = HashMap::from([((0, 0), 1), ((1, 1), 2), ((1, 0), 3)]);
+///
+/// let result = straight_y(&grid, Bounds::new((0, 0), (1, 1)));
+/// assert_eq!(result, vec![
+///     vec![Some(&1), Some(&3)],
+///     vec![None, Some(&2)],
+/// ]);
+/// ```
+pub fn straight_y<T, S: BuildHasher>(
+    grid: &HashMap<(i64, i64), T, S>,
+    bounds: Bounds,
+) -> Vec<Vec<Option<&T>>> {
+    let (rows, cols) = line_shape(bounds);
+    collect(
+        grid,
+        bounds,
+        (0..cols).map(|col| (0..rows).map(move |row| (row, col))),
+    )
+}
+
+/// Returns the shape of the bounds as the slice traversals would see it.
+///
+/// A slice matrix without rows has no first row to take the column count from, so it has no
+/// columns either.
+fn line_shape(bounds: Bounds) -> (usize, usize) {
+    match bounds.shape() {
+        (0, _) => (0, 0),
+        shape => shape,
+    }
+}
+
+/// Looks up every cell of every line in `grid`.
+fn collect<T, S: BuildHasher>(
+    grid: &HashMap<(i64, i64), T, S>,
+    bounds: Bounds,
+    lines: impl Iterator<Item = impl Iterator<Item = (usize, usize)>>,
+) -> Vec<Vec<Option<&T>>> {
+    lines
+        .map(|line| line.map(|cell| grid.get(&bounds.key(cell))).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dense_grid() -> HashMap<(i64, i64), i32> {
+        let mut grid = HashMap::new();
+        for row in 0..2 {
+            for col in 0..3 {
+                grid.insert((row - 5, col + 10), (row * 3 + col + 1) as i32);
+            }
+        }
+        grid
+    }
+
+    #[test]
+    fn matches_slice_traversals() {
+        let grid = dense_grid();
+        let bounds = Bounds::of(&grid).unwrap();
+        let matrix = [[1, 2, 3], [4, 5, 6]];
+        fn some(lines: Vec<Vec<&i32>>) -> Vec<Vec<Option<&i32>>> {
+            lines
+                .into_iter()
+                .map(|line| line.into_iter().map(Some).collect())
+                .collect()
+        }
+
+        assert_eq!(
+            diagonal_pos_pos(&grid, bounds),
+            some(crate::diagonal_pos_pos(&matrix))
+        );
+        assert_eq!(
+            diagonal_pos_neg(&grid, bounds),
+            some(crate::diagonal_pos_neg(&matrix))
+        );
+        assert_eq!(straight_x(&grid, bounds), some(crate::straight_x(&matrix)));
+        assert_eq!(straight_y(&grid, bounds), some(crate::straight_y(&matrix)));
+    }
+
+    #[test]
+    fn empty_bounds_match_slice_traversals() {
+        let grid = dense_grid();
+        let no_rows: Vec<Vec<i32>> = vec![];
+        let no_cols: Vec<Vec<i32>> = vec![vec![], vec![]];
+        for (bounds, matrix) in [
+            (Bounds::new((0, 0), (-1, 2)), no_rows),
+            (Bounds::new((0, 0), (1, -1)), no_cols),
+        ] {
+            let lengths = |lines: Vec<Vec<Option<&i32>>>| -> Vec<usize> {
+                lines.iter().map(Vec::len).collect()
+            };
+            let slice_lengths =
+                |lines: Vec<Vec<&i32>>| -> Vec<usize> { lines.iter().map(Vec::len).collect() };
+            assert_eq!(
+                lengths(straight_x(&grid, bounds)),
+                slice_lengths(crate::straight_x(&matrix))
+            );
+            assert_eq!(
+                lengths(straight_y(&grid, bounds)),
+                slice_lengths(crate::straight_y(&matrix))
+            );
+            assert!(diagonal_pos_pos(&grid, bounds).is_empty());
+            assert!(diagonal_pos_neg(&grid, bounds).is_empty());
+        }
+    }
+
+    #[test]
+    fn bounds_beyond_grid() {
+        let grid = HashMap::from([((0, 0), 'x')]);
+        assert_eq!(
+            straight_x(&grid, Bounds::new((-1, 0), (0, 1))),
+            vec![vec![None, None], vec![Some(&'x'), None]]
+        );
+    }
+
+    #[test]
+    fn bounds_shape() {
+        assert_eq!(Bounds::new((0, 0), (-1, 5)).shape(), (0, 6));
+        assert_eq!(Bounds::new((i64::MIN, 0), (i64::MIN, 0)).shape(), (1, 1));
+        assert_eq!(Bounds::of(&HashMap::<(i64, i64), ()>::new()), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn bounds_serde_round_trip() {
+        let bounds = Bounds::new((-2, 0), (2, 3));
+        let json = serde_json::to_string(&bounds).unwrap();
+        assert_eq!(
+            json,
+            r#"{"min_row":-2,"min_col":0,"max_row":2,"max_col":3}"#
+        );
+        assert_eq!(serde_json::from_str::<Bounds>(&json).unwrap(), bounds);
+    }
+
+    #[test]
+    fn empty_bounds() {
+        let grid = dense_grid();
+        let bounds = Bounds::new((1, 1), (0, 0));
+        assert!(diagonal_pos_pos(&grid, bounds).is_empty());
+        assert!(diagonal_pos_neg(&grid, bounds).is_empty());
+        assert!(straight_x(&grid, bounds).is_empty());
+        assert!(straight_y(&grid, bounds).is_empty());
+    }
+}
@@ -0,0 +1,222 @@
+//! # Hex Grid Operations Module
+//!
+//! This module extracts lines from hexagonal grids in axial coordinates, stored as a
+//! `HashMap<(i64, i64), T>` keyed by `(q, r)`. The third cube coordinate is `s = -q - r`, and
+//! the grid's three natural line directions are the lines along which one of `q`, `r` or `s`
+//! stays constant; they are the hex counterparts of the rows and two diagonals of a matrix.
+//!
+//! Grids can have any shape. A line holds every stored cell with the same constant coordinate,
+//! even if cells in between are missing.
+//!
+//! ### Example
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use diagonal::hex::{lines, HexAxis};
+//!
+//! // A hexagon of radius 1 around the origin.
+//! let grid: HashMap<(i64, i64), char> = HashMap::from([
+//!     ((0, -1), 'a'), ((1, -1), 'b'),
+//!     ((-1, 0), 'c'), ((0, 0), 'd'), ((1, 0), 'e'),
+//!     ((-1, 1), 'f'), ((0, 1), 'g'),
+//! ]);
+//!
+//! let rows: Vec<String> = lines(&grid, HexAxis::R)
+//!     .into_iter()
+//!     .map(|line| line.into_iter().map(|(_, c)| c).collect())
+//!     .collect();
+//! assert_eq!(rows, ["ab", "cde", "fg"]);
+//!
+//! let s_lines: Vec<String> = lines(&grid, HexAxis::S)
+//!     .into_iter()
+//!     .map(|line| line.into_iter().map(|(_, c)| c).collect())
+//!     .collect();
+//! assert_eq!(s_lines, ["ge", "fdb", "ca"]);
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+
+/// The cube coordinate that stays constant along a line of a hex grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HexAxis {
+    /// Lines of constant `q`, running in the direction of increasing `r`.
+    Q,
+    /// Lines of constant `r`, running in the direction of increasing `q`. These are the rows of
+    /// a pointy-top layout.
+    R,
+    /// Lines of constant `s = -q - r`, running in the direction of increasing `q` (and
+    /// decreasing `r`).
+    S,
+}
+
+impl HexAxis {
+    /// Returns the constant coordinate of the line through `(q, r)` and the cell's position
+    /// along it.
+    fn key(self, (q, r): (i64, i64)) -> (i64, i64) {
+        match self {
+            HexAxis::Q => (q, r),
+            HexAxis::R => (r, q),
+            HexAxis::S => (-q - r, q),
+        }
+    }
+}
+
+/// Extracts the lines of a hex grid along which the coordinate `axis` stays constant.
+///
+/// # Arguments
+///
+/// * `grid` - A reference to a map from axial `(q, r)` coordinates to elements.
+/// * `axis` - The coordinate that is constant along every line.
+///
+/// # Returns
+///
+/// A vector of lines, ordered by their constant coordinate from smallest to largest. Every line
+/// holds the coordinates and elements of its cells in the direction described by `axis`.
+///
+/// # Panics
+///
+/// Panics if `axis` is [`HexAxis::S`] and computing `s` of a coordinate overflows.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use diagonal::hex::{lines, HexAxis};
+///
+/// let grid = HashMap::from([((0, 0), 1), ((0, 1), 2), ((1, 0), 3)]);
+///
+/// assert_eq!(lines(&grid, HexAxis::Q), vec![
+///     vec![((0, 0), &1), ((0, 1), &2)],
+///     vec![((1, 0), &3)],
+/// ]);
+/// ```
+pub fn lines<T, S: BuildHasher>(
+    grid: &HashMap<(i64, i64), T, S>,
+    axis: HexAxis,
+) -> Vec<Vec<((i64, i64), &T)>> {
+    let mut grouped = BTreeMap::<i64, Vec<_>>::new();
+    for (&coord, value) in grid {
+        let (line, position) = axis.key(coord);
+        grouped
+            .entry(line)
+            .or_default()
+            .push((position, coord, value));
+    }
+
+    grouped
+        .into_values()
+        .map(|mut line| {
+            line.sort_unstable_by_key(|&(position, _, _)| position);
+            line.into_iter()
+                .map(|(_, coord, value)| (coord, value))
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the axial coordinates of every cell within `radius` steps of `center`, in the order
+/// of increasing `r` and then `q`.
+///
+/// This is a convenient way to build hexagon-shaped grids.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::hex::hexagon;
+///
+/// assert_eq!(hexagon((0, 0), 1), vec![
+///     (0, -1), (1, -1),
+///     (-1, 0), (0, 0), (1, 0),
+///     (-1, 1), (0, 1),
+/// ]);
+/// assert_eq!(hexagon((5, 5), 2).len(), 19);
+/// ```
+pub fn hexagon((q, r): (i64, i64), radius: i64) -> Vec<(i64, i64)> {
+    (-radius..=radius)
+        .flat_map(|dr| {
+            let dq_min = (-radius).max(-radius - dr);
+            let dq_max = radius.min(radius - dr);
+            (dq_min..=dq_max).map(move |dq| (q + dq, r + dr))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::owned;
+
+    fn hexagon_grid(radius: i64) -> HashMap<(i64, i64), (i64, i64)> {
+        hexagon((0, 0), radius)
+            .into_iter()
+            .map(|coord| (coord, coord))
+            .collect()
+    }
+
+    #[test]
+    fn every_line_keeps_its_coordinate() {
+        let grid = hexagon_grid(3);
+        for axis in [HexAxis::Q, HexAxis::R, HexAxis::S] {
+            let lines = lines(&grid, axis);
+            assert_eq!(lines.len(), 7);
+            assert_eq!(lines.iter().map(Vec::len).sum::<usize>(), grid.len());
+
+            let mut previous = None;
+            for line in &lines {
+                let constant = axis.key(line[0].0).0;
+                assert!(previous < Some(constant));
+                previous = Some(constant);
+
+                for pair in line.windows(2) {
+                    let ((aq, ar), (bq, br)) = (pair[0].0, pair[1].0);
+                    let step = (bq - aq, br - ar);
+                    let expected = match axis {
+                        HexAxis::Q => (0, 1),
+                        HexAxis::R => (1, 0),
+                        HexAxis::S => (1, -1),
+                    };
+                    assert_eq!(step, expected);
+                }
+                for &(coord, &value) in line {
+                    assert_eq!(coord, value);
+                    assert_eq!(axis.key(coord).0, constant);
+                }
+            }
+
+            // The middle line of a hexagon spans its full width.
+            assert_eq!(lines[3].len(), 7);
+        }
+    }
+
+    #[test]
+    fn rhombus_matches_matrix_traversals() {
+        // A rhombus stored as `matrix[r][q]` has the rows, columns and anti-diagonals of the
+        // matrix as its lines.
+        let matrix = [[1, 2, 3], [4, 5, 6]];
+        let grid: HashMap<(i64, i64), i32> = (0..2)
+            .flat_map(|r| (0..3).map(move |q| ((q as i64, r as i64), matrix[r][q])))
+            .collect();
+        let values = |axis| -> Vec<Vec<i32>> {
+            lines(&grid, axis)
+                .into_iter()
+                .map(|line| line.into_iter().map(|(_, &v)| v).collect())
+                .collect()
+        };
+        assert_eq!(values(HexAxis::R), owned(crate::straight_x(&matrix)));
+        assert_eq!(values(HexAxis::Q), owned(crate::straight_y(&matrix)));
+        let mut anti_diagonals = owned(crate::diagonal_pos_neg(&matrix));
+        anti_diagonals.reverse();
+        anti_diagonals.iter_mut().for_each(|line| line.reverse());
+        assert_eq!(values(HexAxis::S), anti_diagonals);
+    }
+
+    #[test]
+    fn empty() {
+        let grid: HashMap<(i64, i64), u8> = HashMap::new();
+        assert!(lines(&grid, HexAxis::S).is_empty());
+        assert_eq!(hexagon((0, 0), 0), vec![(0, 0)]);
+        assert!(hexagon((0, 0), -1).is_empty());
+    }
+}
@@ -0,0 +1,268 @@
+//! Index math shared by the traversals.
+//!
+//! The helpers in here only deal with `(row, column)` coordinates and never touch the matrix
+//! itself, so the same plan can be used for reading, writing and reassembling.
+
+/// Returns the `(rows, columns)` shape of a matrix, using the first row for the column count.
+pub(crate) fn shape<Row: AsRef<[T]>, T>(matrix: &[Row]) -> (usize, usize) {
+    let cols = matrix.first().map_or(0, |row| row.as_ref().len());
+    (matrix.len(), cols)
+}
+
+/// Number of rows the cache-blocked traversals read at a time.
+pub(crate) const ROW_BLOCK: usize = 1024;
+
+/// Number of diagonals in either diagonal direction of a `rows` x `cols` matrix, or `None` if
+/// it does not fit in a `usize`.
+pub(crate) fn checked_diagonal_count(rows: usize, cols: usize) -> Option<usize> {
+    if rows == 0 || cols == 0 {
+        Some(0)
+    } else {
+        (rows - 1).checked_add(cols)
+    }
+}
+
+/// Number of diagonals in either diagonal direction of a `rows` x `cols` matrix.
+///
+/// Every other helper in here stays within `0..diagonal_count` and the matrix's bounds, so this
+/// is the only place where the index math can overflow. That takes more than `usize::MAX`
+/// cells, which only matrices of zero-sized rows or elements can have.
+///
+/// # Panics
+///
+/// Panics if the number of diagonals does not fit in a `usize`.
+pub(crate) fn diagonal_count(rows: usize, cols: usize) -> usize {
+    checked_diagonal_count(rows, cols).expect("matrix has more diagonals than fit in a usize")
+}
+
+/// Returns `a - b` as an `isize`.
+///
+/// # Panics
+///
+/// Panics if the difference does not fit in an `isize`.
+pub(crate) fn signed_difference(a: usize, b: usize) -> isize {
+    let difference = if a >= b {
+        0isize.checked_add_unsigned(a - b)
+    } else {
+        0isize.checked_sub_unsigned(b - a)
+    };
+    difference.expect("offset does not fit in an isize")
+}
+
+/// Turns a signed step along a cycle of `len` positions into the equivalent forward step, which
+/// is less than `len`.
+pub(crate) fn wrap(step: isize, len: usize) -> usize {
+    // Work on the magnitude, so that `isize::MIN` cannot overflow.
+    let forward = step.unsigned_abs() % len;
+    if step < 0 {
+        (len - forward) % len
+    } else {
+        forward
+    }
+}
+
+/// Moves `position` forward by a `step` less than `len` along a cycle of `len` positions.
+pub(crate) fn advance(position: usize, step: usize, len: usize) -> usize {
+    let left = len - position;
+    if step < left {
+        position + step
+    } else {
+        step - left
+    }
+}
+
+/// Start coordinate and length of the `index`-th `diagonal_pos_pos` diagonal.
+pub(crate) fn pos_pos_start(rows: usize, cols: usize, index: usize) -> ((usize, usize), usize) {
+    let start = if index < rows {
+        (rows - 1 - index, 0)
+    } else {
+        (0, index + 1 - rows)
+    };
+    (start, (rows - start.0).min(cols - start.1))
+}
+
+/// Start coordinate and length of the `index`-th `diagonal_pos_neg` diagonal.
+pub(crate) fn pos_neg_start(rows: usize, cols: usize, index: usize) -> ((usize, usize), usize) {
+    let start = if index < cols {
+        (0, index)
+    } else {
+        (index + 1 - cols, cols - 1)
+    };
+    (start, (rows - start.0).min(start.1 + 1))
+}
+
+/// Index of the `diagonal_pos_pos` diagonal that `(row, col)` belongs to.
+pub(crate) fn pos_pos_index(rows: usize, row: usize, col: usize) -> usize {
+    rows - 1 - row + col
+}
+
+/// Index of the `diagonal_pos_neg` diagonal that `(row, col)` belongs to.
+pub(crate) fn pos_neg_index(row: usize, col: usize) -> usize {
+    row + col
+}
+
+/// Rebuilds a `rows` x `cols` matrix from diagonals given in traversal order.
+///
+/// Walking the matrix row by row visits the cells of every diagonal in the same order the
+/// traversals emit them, so each diagonal can simply be drained front to back.
+///
+/// # Panics
+///
+/// Panics if a diagonal is shorter than the number of cells `index_of` assigns to it.
+pub(crate) fn reassemble<U>(
+    rows: usize,
+    cols: usize,
+    diagonals: Vec<Vec<U>>,
+    index_of: impl Fn(usize, usize) -> usize,
+) -> Vec<Vec<U>> {
+    let mut diagonals: Vec<_> = diagonals.into_iter().map(Vec::into_iter).collect();
+
+    (0..rows)
+        .map(|row| {
+            (0..cols)
+                .map(|col| {
+                    diagonals[index_of(row, col)]
+                        .next()
+                        .expect("diagonal is shorter than the matrix shape requires")
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Coordinates of every `diagonal_pos_pos` diagonal, in traversal order.
+pub(crate) fn pos_pos_cells(
+    rows: usize,
+    cols: usize,
+) -> impl Iterator<Item = impl Iterator<Item = (usize, usize)>> {
+    (0..diagonal_count(rows, cols)).map(move |index| {
+        let ((x, y), len) = pos_pos_start(rows, cols, index);
+        (0..len).map(move |i| (x + i, y + i))
+    })
+}
+
+/// Coordinates of every `diagonal_pos_neg` diagonal, in traversal order.
+pub(crate) fn pos_neg_cells(
+    rows: usize,
+    cols: usize,
+) -> impl Iterator<Item = impl Iterator<Item = (usize, usize)>> {
+    (0..diagonal_count(rows, cols)).map(move |index| {
+        let ((x, y), len) = pos_neg_start(rows, cols, index);
+        (0..len).map(move |i| (x + i, y - i))
+    })
+}
+
+/// Copies the elements of borrowed lines, so that tests can compare them with owned lines.
+#[cfg(test)]
+pub(crate) fn owned<T: Copy>(lines: Vec<Vec<&T>>) -> Vec<Vec<T>> {
+    lines
+        .into_iter()
+        .map(|line| line.into_iter().copied().collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect<I: Iterator<Item = J>, J: Iterator<Item = (usize, usize)>>(
+        cells: I,
+    ) -> Vec<Vec<(usize, usize)>> {
+        cells.map(Iterator::collect).collect()
+    }
+
+    #[test]
+    fn huge_dimensions() {
+        const MAX: usize = usize::MAX;
+        assert_eq!(checked_diagonal_count(MAX, 1), Some(MAX));
+        assert_eq!(checked_diagonal_count(1, MAX), Some(MAX));
+        assert_eq!(checked_diagonal_count(MAX, 0), Some(0));
+        assert_eq!(checked_diagonal_count(2, MAX), None);
+        assert_eq!(checked_diagonal_count(MAX, MAX), None);
+
+        assert_eq!(pos_pos_start(MAX, 1, 0), ((MAX - 1, 0), 1));
+        assert_eq!(pos_pos_start(1, MAX, MAX - 1), ((0, MAX - 1), 1));
+        assert_eq!(pos_neg_start(1, MAX, MAX - 1), ((0, MAX - 1), 1));
+        assert_eq!(pos_neg_start(MAX, 1, MAX - 1), ((MAX - 1, 0), 1));
+        assert_eq!(pos_pos_index(MAX, 0, 0), MAX - 1);
+        assert_eq!(pos_neg_index(MAX - 1, 0), MAX - 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_diagonals() {
+        diagonal_count(2, usize::MAX);
+    }
+
+    #[test]
+    fn signed_differences() {
+        let min = isize::MIN.unsigned_abs();
+        assert_eq!(signed_difference(3, 5), -2);
+        assert_eq!(signed_difference(min - 1, 0), isize::MAX);
+        assert_eq!(signed_difference(0, min), isize::MIN);
+        assert_eq!(signed_difference(usize::MAX, usize::MAX), 0);
+        assert!(std::panic::catch_unwind(|| signed_difference(min, 0)).is_err());
+        assert!(std::panic::catch_unwind(|| signed_difference(0, min + 1)).is_err());
+    }
+
+    #[test]
+    fn cyclic_steps() {
+        const MAX: usize = usize::MAX;
+        assert_eq!(wrap(-1, 5), 4);
+        assert_eq!(wrap(-5, 5), 0);
+        assert_eq!(wrap(7, 5), 2);
+        assert_eq!(wrap(isize::MIN, MAX), MAX - isize::MIN.unsigned_abs());
+        assert_eq!(wrap(isize::MAX, MAX), isize::MAX.unsigned_abs());
+        assert_eq!(wrap(-1, MAX), MAX - 1);
+
+        assert_eq!(advance(1, 3, 5), 4);
+        assert_eq!(advance(3, 3, 5), 1);
+        assert_eq!(advance(MAX - 1, MAX - 1, MAX), MAX - 2);
+        assert_eq!(advance(MAX - 2, 1, MAX), MAX - 1);
+    }
+
+    #[test]
+    fn pos_pos_two_by_three() {
+        assert_eq!(
+            collect(pos_pos_cells(2, 3)),
+            vec![
+                vec![(1, 0)],
+                vec![(0, 0), (1, 1)],
+                vec![(0, 1), (1, 2)],
+                vec![(0, 2)]
+            ]
+        );
+    }
+
+    #[test]
+    fn pos_neg_three_by_two() {
+        assert_eq!(
+            collect(pos_neg_cells(3, 2)),
+            vec![
+                vec![(0, 0)],
+                vec![(0, 1), (1, 0)],
+                vec![(1, 1), (2, 0)],
+                vec![(2, 1)]
+            ]
+        );
+    }
+
+    #[test]
+    fn reassemble_round_trip() {
+        let diagonals: Vec<Vec<_>> = pos_neg_cells(3, 2).map(Iterator::collect).collect();
+        assert_eq!(
+            reassemble(3, 2, diagonals, pos_neg_index),
+            vec![
+                vec![(0, 0), (0, 1)],
+                vec![(1, 0), (1, 1)],
+                vec![(2, 0), (2, 1)]
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_shapes() {
+        assert!(collect(pos_pos_cells(0, 0)).is_empty());
+        assert!(collect(pos_neg_cells(3, 0)).is_empty());
+    }
+}
@@ -0,0 +1,831 @@
+use std::iter::{Flatten, FusedIterator};
+use std::marker::PhantomData;
+use std::ops::Range;
+
+use crate::{index, Direction};
+
+/// A lazy iterator over the lines of a matrix, created by [`lines_iter`] and its per-direction
+/// shorthands.
+///
+/// Every line is yielded as an [`Elements`] iterator. Lines are only located when they are
+/// reached, so nothing is allocated up front. Since any line can be located directly, walking
+/// the lines back to front with [`rev`](Iterator::rev), or skipping over them with
+/// [`nth`](Iterator::nth) and [`nth_back`](DoubleEndedIterator::nth_back), costs nothing extra.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_pos_neg_iter;
+///
+/// let matrix = [[1, 2, 3], [4, 5, 6]];
+///
+/// // Draw the diagonals back to front, without collecting them first.
+/// let mut drawn: Vec<&i32> = Vec::new();
+/// for diagonal in diagonal_pos_neg_iter(&matrix).rev() {
+///     drawn.extend(diagonal);
+/// }
+/// assert_eq!(drawn, vec![&6, &3, &5, &2, &4, &1]);
+/// ```
+pub struct Lines<'a, Row, T> {
+    matrix: &'a [Row],
+    direction: Direction,
+    rows: usize,
+    cols: usize,
+    front: usize,
+    back: usize,
+    element: PhantomData<&'a T>,
+}
+
+/// A lazy iterator over the elements of one line, yielded by [`Lines`].
+pub struct Elements<'a, Row, T> {
+    matrix: &'a [Row],
+    direction: Direction,
+    start: (usize, usize),
+    front: usize,
+    back: usize,
+    element: PhantomData<&'a T>,
+}
+
+impl<'a, Row: AsRef<[T]>, T> Lines<'a, Row, T> {
+    fn new(matrix: &'a [Row], direction: Direction) -> Self {
+        let (rows, cols) = index::shape(matrix);
+        Lines {
+            matrix,
+            direction,
+            rows,
+            cols,
+            front: 0,
+            back: direction.line_count(rows, cols),
+            element: PhantomData,
+        }
+    }
+
+    /// Returns the direction the lines run in.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    fn line(&self, index: usize) -> Elements<'a, Row, T> {
+        let (start, len) = self.direction.line_start(self.rows, self.cols, index);
+        Elements {
+            matrix: self.matrix,
+            direction: self.direction,
+            start,
+            front: 0,
+            back: len,
+            element: PhantomData,
+        }
+    }
+}
+
+impl<'a, Row: AsRef<[T]>, T> Elements<'a, Row, T> {
+    fn element(&self, i: usize) -> &'a T {
+        let (x, y) = self.direction.cell(self.start, i);
+        &self.matrix[x].as_ref()[y]
+    }
+}
+
+impl<'a, Row: AsRef<[T]>, T> Iterator for Lines<'a, Row, T> {
+    type Item = Elements<'a, Row, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.line(self.front - 1))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.front = self.front.saturating_add(n).min(self.back);
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<Row: AsRef<[T]>, T> DoubleEndedIterator for Lines<'_, Row, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.line(self.back))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.back = self.back.saturating_sub(n).max(self.front);
+        self.next_back()
+    }
+}
+
+impl<Row: AsRef<[T]>, T> ExactSizeIterator for Lines<'_, Row, T> {}
+
+impl<Row: AsRef<[T]>, T> FusedIterator for Lines<'_, Row, T> {}
+
+impl<'a, Row: AsRef<[T]>, T> Iterator for Elements<'a, Row, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.element(self.front - 1))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.front = self.front.saturating_add(n).min(self.back);
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<Row: AsRef<[T]>, T> DoubleEndedIterator for Elements<'_, Row, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.element(self.back))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.back = self.back.saturating_sub(n).max(self.front);
+        self.next_back()
+    }
+}
+
+impl<Row: AsRef<[T]>, T> ExactSizeIterator for Elements<'_, Row, T> {}
+
+impl<Row: AsRef<[T]>, T> FusedIterator for Elements<'_, Row, T> {}
+
+// Manual impls, since deriving would require `Row: Clone` and `T: Clone`.
+impl<Row, T> Clone for Lines<'_, Row, T> {
+    fn clone(&self) -> Self {
+        Lines { ..*self }
+    }
+}
+
+impl<Row, T> Clone for Elements<'_, Row, T> {
+    fn clone(&self) -> Self {
+        Elements { ..*self }
+    }
+}
+
+/// Lazily traverses the lines of a matrix in the given direction.
+///
+/// This yields the same lines, in the same order, as the eager function named by `direction`,
+/// but without collecting them. Both the returned iterator and every line know their exact
+/// length and can be walked from either end.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `direction` - The traversal to perform.
+///
+/// # Returns
+///
+/// A [`Lines`] iterator yielding one [`Elements`] iterator per line.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{lines_iter, Direction};
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ];
+///
+/// let mut lines = lines_iter(&matrix, Direction::DiagonalPosNeg);
+/// assert_eq!(lines.len(), 4);
+///
+/// let last = lines.next_back().unwrap();
+/// assert_eq!(last.rev().collect::<Vec<_>>(), vec![&6]);
+///
+/// let second = lines.nth(1).unwrap();
+/// assert_eq!(second.len(), 2);
+/// assert_eq!(second.rev().collect::<Vec<_>>(), vec![&4, &2]);
+/// ```
+pub fn lines_iter<'a, Matrix: AsRef<[Row]> + ?Sized, Row: AsRef<[T]>, T>(
+    matrix: &'a Matrix,
+    direction: Direction,
+) -> Lines<'a, Row, T> {
+    Lines::new(matrix.as_ref(), direction)
+}
+
+/// Lazily traverses diagonals with positive slope, like
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos).
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_pos_pos_iter;
+///
+/// let matrix = [[1, 2], [3, 4]];
+///
+/// let lines: Vec<Vec<&i32>> = diagonal_pos_pos_iter(&matrix).rev().map(Iterator::collect).collect();
+/// assert_eq!(lines, vec![vec![&2], vec![&1, &4], vec![&3]]);
+/// ```
+pub fn diagonal_pos_pos_iter<'a, Matrix: AsRef<[Row]> + ?Sized, Row: AsRef<[T]>, T>(
+    matrix: &'a Matrix,
+) -> Lines<'a, Row, T> {
+    lines_iter(matrix, Direction::DiagonalPosPos)
+}
+
+/// Lazily traverses diagonals with positive and negative slope, like
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg).
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_pos_neg_iter;
+///
+/// let matrix = [[1, 2], [3, 4]];
+///
+/// let lines: Vec<Vec<&i32>> = diagonal_pos_neg_iter(&matrix).map(Iterator::collect).collect();
+/// assert_eq!(lines, vec![vec![&1], vec![&2, &3], vec![&4]]);
+/// ```
+pub fn diagonal_pos_neg_iter<'a, Matrix: AsRef<[Row]> + ?Sized, Row: AsRef<[T]>, T>(
+    matrix: &'a Matrix,
+) -> Lines<'a, Row, T> {
+    lines_iter(matrix, Direction::DiagonalPosNeg)
+}
+
+/// Lazily traverses the rows of a matrix, like [`straight_x`](crate::straight_x).
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::straight_x_iter;
+///
+/// let matrix = [[1, 2], [3, 4]];
+///
+/// let lines: Vec<Vec<&i32>> = straight_x_iter(&matrix).map(Iterator::collect).collect();
+/// assert_eq!(lines, vec![vec![&1, &2], vec![&3, &4]]);
+/// ```
+pub fn straight_x_iter<'a, Matrix: AsRef<[Row]> + ?Sized, Row: AsRef<[T]>, T>(
+    matrix: &'a Matrix,
+) -> Lines<'a, Row, T> {
+    lines_iter(matrix, Direction::StraightX)
+}
+
+/// Lazily traverses the columns of a matrix, like [`straight_y`](crate::straight_y).
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::straight_y_iter;
+///
+/// let matrix = [[1, 2], [3, 4]];
+///
+/// let lines: Vec<Vec<&i32>> = straight_y_iter(&matrix).map(Iterator::collect).collect();
+/// assert_eq!(lines, vec![vec![&1, &3], vec![&2, &4]]);
+/// ```
+pub fn straight_y_iter<'a, Matrix: AsRef<[Row]> + ?Sized, Row: AsRef<[T]>, T>(
+    matrix: &'a Matrix,
+) -> Lines<'a, Row, T> {
+    lines_iter(matrix, Direction::StraightY)
+}
+
+/// Traverses the lines of a matrix in the given direction and collects them into containers of
+/// the caller's choice.
+///
+/// Each line is collected straight into an `Inner`, and the lines into an `Outer`, so no
+/// intermediate `Vec<Vec<&T>>` is built. Any pair of [`FromIterator`] implementations works,
+/// for example `Vec<SmallVec<[&T; N]>>` from the `smallvec` crate, which stores every line of
+/// up to `N` elements inline instead of allocating it on the heap, and which the `smallvec`
+/// feature provides as `lines_small`. The containers are usually inferred from the type of the
+/// result.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `direction` - The traversal to perform.
+///
+/// # Returns
+///
+/// The collected lines, in the same order as the eager function named by `direction` returns
+/// them.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::VecDeque;
+/// use diagonal::{lines_into, Direction};
+///
+/// let matrix = vec![
+///     vec![1, 2],
+///     vec![3, 4],
+/// ];
+///
+/// let result: VecDeque<Vec<&i32>> = lines_into(&matrix, Direction::StraightY);
+/// assert_eq!(result, [vec![&1, &3], vec![&2, &4]]);
+/// ```
+pub fn lines_into<'a, Inner, Outer, Matrix, Row, T>(
+    matrix: &'a Matrix,
+    direction: Direction,
+) -> Outer
+where
+    Inner: FromIterator<&'a T>,
+    Outer: FromIterator<Inner>,
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    T: 'a,
+{
+    lines_iter(matrix, direction)
+        .map(Iterator::collect)
+        .collect()
+}
+
+/// Extracts diagonals with positive slope, like [`diagonal_pos_pos`](crate::diagonal_pos_pos),
+/// collecting them into containers of the caller's choice (see [`lines_into`]).
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_pos_pos_into;
+///
+/// let matrix = [['a', 'b'], ['c', 'd']];
+///
+/// let result: Vec<String> = diagonal_pos_pos_into(&matrix);
+/// assert_eq!(result, ["c", "ad", "b"]);
+/// ```
+pub fn diagonal_pos_pos_into<'a, Inner, Outer, Matrix, Row, T>(matrix: &'a Matrix) -> Outer
+where
+    Inner: FromIterator<&'a T>,
+    Outer: FromIterator<Inner>,
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    T: 'a,
+{
+    lines_into(matrix, Direction::DiagonalPosPos)
+}
+
+/// Extracts diagonals with positive and negative slope, like
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg), collecting them into containers of the
+/// caller's choice (see [`lines_into`]).
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_pos_neg_into;
+///
+/// let matrix = [['a', 'b'], ['c', 'd']];
+///
+/// let result: Vec<String> = diagonal_pos_neg_into(&matrix);
+/// assert_eq!(result, ["a", "bc", "d"]);
+/// ```
+pub fn diagonal_pos_neg_into<'a, Inner, Outer, Matrix, Row, T>(matrix: &'a Matrix) -> Outer
+where
+    Inner: FromIterator<&'a T>,
+    Outer: FromIterator<Inner>,
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    T: 'a,
+{
+    lines_into(matrix, Direction::DiagonalPosNeg)
+}
+
+/// Extracts the rows of a matrix, like [`straight_x`](crate::straight_x), collecting them into
+/// containers of the caller's choice (see [`lines_into`]).
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::straight_x_into;
+///
+/// let matrix = [['a', 'b'], ['c', 'd']];
+///
+/// let result: Vec<String> = straight_x_into(&matrix);
+/// assert_eq!(result, ["ab", "cd"]);
+/// ```
+pub fn straight_x_into<'a, Inner, Outer, Matrix, Row, T>(matrix: &'a Matrix) -> Outer
+where
+    Inner: FromIterator<&'a T>,
+    Outer: FromIterator<Inner>,
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    T: 'a,
+{
+    lines_into(matrix, Direction::StraightX)
+}
+
+/// Extracts the columns of a matrix, like [`straight_y`](crate::straight_y), collecting them
+/// into containers of the caller's choice (see [`lines_into`]).
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::straight_y_into;
+///
+/// let matrix = [['a', 'b'], ['c', 'd']];
+///
+/// let result: Vec<String> = straight_y_into(&matrix);
+/// assert_eq!(result, ["ac", "bd"]);
+/// ```
+pub fn straight_y_into<'a, Inner, Outer, Matrix, Row, T>(matrix: &'a Matrix) -> Outer
+where
+    Inner: FromIterator<&'a T>,
+    Outer: FromIterator<Inner>,
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    T: 'a,
+{
+    lines_into(matrix, Direction::StraightY)
+}
+
+/// A lazy iterator over all elements of a traversal in one flat sequence, created by
+/// [`lines_flat`].
+///
+/// The elements come line after line, in the order of [`lines_iter`], and the iterator knows
+/// its exact length up front: one element per cell of the matrix.
+pub struct FlatElements<'a, Row: AsRef<[T]>, T> {
+    elements: Flatten<Lines<'a, Row, T>>,
+    remaining: usize,
+}
+
+impl<'a, Row: AsRef<[T]>, T> Iterator for FlatElements<'a, Row, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let element = self.elements.next()?;
+        self.remaining -= 1;
+        Some(element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<Row: AsRef<[T]>, T> DoubleEndedIterator for FlatElements<'_, Row, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let element = self.elements.next_back()?;
+        self.remaining -= 1;
+        Some(element)
+    }
+}
+
+impl<Row: AsRef<[T]>, T> ExactSizeIterator for FlatElements<'_, Row, T> {}
+
+impl<Row: AsRef<[T]>, T> FusedIterator for FlatElements<'_, Row, T> {}
+
+impl<Row: AsRef<[T]>, T> Clone for FlatElements<'_, Row, T> {
+    fn clone(&self) -> Self {
+        FlatElements {
+            elements: self.elements.clone(),
+            remaining: self.remaining,
+        }
+    }
+}
+
+/// An iterator over where every line of a traversal lies in its flat sequence, created by
+/// [`line_boundaries`].
+#[derive(Debug, Clone)]
+pub struct Boundaries {
+    direction: Direction,
+    rows: usize,
+    cols: usize,
+    line: usize,
+    count: usize,
+    offset: usize,
+}
+
+impl Iterator for Boundaries {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.line == self.count {
+            return None;
+        }
+        let (_, len) = self.direction.line_start(self.rows, self.cols, self.line);
+        let range = self.offset..self.offset + len;
+        self.line += 1;
+        self.offset = range.end;
+        Some(range)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.count - self.line;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Boundaries {}
+
+impl FusedIterator for Boundaries {}
+
+/// Lazily traverses the lines of a matrix in the given direction as one flat sequence of
+/// elements.
+///
+/// This yields the elements of [`lines_iter`] line after line, without any nesting, so a
+/// traversal can be streamed straight into a writer or encoder. [`line_boundaries`] says where
+/// each line starts and ends in the sequence, and both iterators know their exact length, so
+/// headers such as element or line counts can be written before the elements.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `direction` - The traversal to perform.
+///
+/// # Returns
+///
+/// A [`FlatElements`] iterator over references to every element of the matrix.
+///
+/// # Panics
+///
+/// Panics if the matrix has more than `usize::MAX` cells, which takes zero-sized rows or
+/// elements.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{line_boundaries, lines_flat, Direction};
+///
+/// let matrix = [[1, 2, 3], [4, 5, 6]];
+///
+/// // Encode the diagonals as a line count, the line lengths and then the elements.
+/// let boundaries = line_boundaries(2, 3, Direction::DiagonalPosNeg);
+/// let mut encoded = vec![boundaries.len() as u8];
+/// encoded.extend(boundaries.map(|line| line.len() as u8));
+/// encoded.extend(lines_flat(&matrix, Direction::DiagonalPosNeg).map(|&x| x as u8));
+///
+/// assert_eq!(encoded, [4, 1, 2, 2, 1, 1, 2, 4, 3, 5, 6]);
+/// ```
+pub fn lines_flat<'a, Matrix: AsRef<[Row]> + ?Sized, Row: AsRef<[T]>, T>(
+    matrix: &'a Matrix,
+    direction: Direction,
+) -> FlatElements<'a, Row, T> {
+    let lines = Lines::new(matrix.as_ref(), direction);
+    FlatElements {
+        remaining: lines
+            .rows
+            .checked_mul(lines.cols)
+            .expect("matrix has more cells than fit in a usize"),
+        elements: lines.flatten(),
+    }
+}
+
+/// Locates every line of a `rows` x `cols` matrix in the flat sequence of [`lines_flat`].
+///
+/// # Arguments
+///
+/// * `rows` - The number of rows of the matrix.
+/// * `cols` - The number of columns of the matrix.
+/// * `direction` - The traversal to locate the lines of.
+///
+/// # Returns
+///
+/// A [`Boundaries`] iterator yielding the range of positions of every line, in the order of
+/// [`lines_iter`]. The ranges are adjacent and together cover `0..rows * cols`.
+///
+/// # Panics
+///
+/// Panics if `rows * cols` does not fit in a `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{line_boundaries, lines_flat, Direction};
+///
+/// let matrix = [[1, 2, 3], [4, 5, 6]];
+///
+/// let flat: Vec<&i32> = lines_flat(&matrix, Direction::DiagonalPosPos).collect();
+/// let boundaries: Vec<_> = line_boundaries(2, 3, Direction::DiagonalPosPos).collect();
+/// assert_eq!(boundaries, [0..1, 1..3, 3..5, 5..6]);
+/// assert_eq!(flat[boundaries[1].clone()], [&1, &5]);
+/// ```
+pub fn line_boundaries(rows: usize, cols: usize, direction: Direction) -> Boundaries {
+    // Every offset is at most `rows * cols`, so the iterator itself cannot overflow.
+    rows.checked_mul(cols)
+        .expect("matrix has more cells than fit in a usize");
+    Boundaries {
+        direction,
+        rows,
+        cols,
+        line: 0,
+        count: direction.line_count(rows, cols),
+        offset: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eager(matrix: &[[i32; 4]; 3], direction: Direction) -> Vec<Vec<&i32>> {
+        match direction {
+            Direction::DiagonalPosPos => crate::diagonal_pos_pos(matrix),
+            Direction::DiagonalPosNeg => crate::diagonal_pos_neg(matrix),
+            Direction::StraightX => crate::straight_x(matrix),
+            Direction::StraightY => crate::straight_y(matrix),
+        }
+    }
+
+    #[test]
+    fn matches_eager_traversals() {
+        let matrix = [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]];
+        for direction in Direction::ALL {
+            let lazy: Vec<Vec<&i32>> = lines_iter(&matrix, direction)
+                .map(Iterator::collect)
+                .collect();
+            assert_eq!(lazy, eager(&matrix, direction));
+        }
+    }
+
+    #[test]
+    fn reversed() {
+        let matrix = [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]];
+        for direction in Direction::ALL {
+            let lazy: Vec<Vec<&i32>> = lines_iter(&matrix, direction)
+                .rev()
+                .map(|line| line.rev().collect())
+                .collect();
+            let mut expected = eager(&matrix, direction);
+            expected.reverse();
+            expected.iter_mut().for_each(|line| line.reverse());
+            assert_eq!(lazy, expected);
+        }
+    }
+
+    #[test]
+    fn lengths() {
+        let matrix = [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]];
+        for direction in Direction::ALL {
+            let expected = eager(&matrix, direction);
+            let mut lines = lines_iter(&matrix, direction);
+            assert_eq!(lines.len(), expected.len());
+
+            for (taken, line) in expected.iter().enumerate() {
+                let mut elements = lines.next().unwrap();
+                assert_eq!(lines.len(), expected.len() - taken - 1);
+                assert_eq!(elements.len(), line.len());
+                elements.next_back();
+                assert_eq!(elements.len(), line.len() - 1);
+            }
+        }
+    }
+
+    #[test]
+    fn skipping() {
+        let matrix = [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]];
+        for direction in Direction::ALL {
+            let expected = eager(&matrix, direction);
+            for n in 0..expected.len() + 2 {
+                let mut lines = lines_iter(&matrix, direction);
+                let line = lines.nth(n).map(Iterator::collect::<Vec<_>>);
+                assert_eq!(line.as_ref(), expected.get(n));
+                assert_eq!(lines.len(), expected.len().saturating_sub(n + 1));
+
+                let mut lines = lines_iter(&matrix, direction).rev();
+                let line = lines.nth(n).map(|mut line| line.next_back());
+                let expected_back = expected
+                    .iter()
+                    .rev()
+                    .nth(n)
+                    .map(|line| line.last().copied());
+                assert_eq!(line, expected_back);
+            }
+
+            for line in &expected {
+                let mut elements = lines_iter(&matrix, direction)
+                    .find(|elements| elements.clone().eq(line.iter().copied()))
+                    .unwrap();
+                assert_eq!(elements.nth_back(line.len()), None);
+                assert_eq!(elements.next(), None);
+            }
+        }
+    }
+
+    #[test]
+    fn meeting_in_the_middle() {
+        let matrix = [[1, 2, 3], [4, 5, 6], [7, 8, 9]];
+        let mut lines = diagonal_pos_pos_iter(&matrix);
+        lines.next();
+        lines.next_back();
+        let mut middle = lines.nth(1).unwrap();
+        assert_eq!(lines.len(), 1);
+        lines.next_back();
+        assert!(lines.next().is_none());
+        assert!(lines.next_back().is_none());
+
+        assert_eq!(middle.next(), Some(&1));
+        assert_eq!(middle.next_back(), Some(&9));
+        assert_eq!(middle.next_back(), Some(&5));
+        assert_eq!(middle.next(), None);
+        assert_eq!(middle.next_back(), None);
+    }
+
+    #[test]
+    fn into_custom_containers() {
+        use std::collections::{BTreeSet, VecDeque};
+
+        let matrix = [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]];
+        for direction in Direction::ALL {
+            let expected = eager(&matrix, direction);
+            let collected: Vec<Vec<&i32>> = lines_into(&matrix, direction);
+            assert_eq!(collected, expected);
+
+            let sets: VecDeque<BTreeSet<&i32>> = lines_into(&matrix, direction);
+            assert_eq!(sets.len(), expected.len());
+            for (set, line) in sets.iter().zip(&expected) {
+                assert!(set.iter().eq(line.iter().collect::<BTreeSet<_>>()));
+            }
+        }
+    }
+
+    #[test]
+    fn flat_matches_lines() {
+        let matrix = [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]];
+        for direction in Direction::ALL {
+            let expected = eager(&matrix, direction);
+            let flat = lines_flat(&matrix, direction);
+            assert_eq!(flat.len(), 12);
+            let flat: Vec<&i32> = flat.collect();
+            assert_eq!(flat, expected.concat());
+
+            let mut backwards: Vec<&i32> = lines_flat(&matrix, direction).rev().collect();
+            backwards.reverse();
+            assert_eq!(backwards, flat);
+
+            let boundaries = line_boundaries(3, 4, direction);
+            assert_eq!(boundaries.len(), expected.len());
+            for (range, line) in boundaries.zip(&expected) {
+                assert_eq!(flat[range], line[..]);
+            }
+        }
+    }
+
+    #[test]
+    fn flat_lengths() {
+        let matrix = [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]];
+        let mut flat = lines_flat(&matrix, Direction::DiagonalPosNeg);
+        flat.next();
+        flat.next_back();
+        assert_eq!(flat.len(), 10);
+
+        let mut boundaries = line_boundaries(3, 4, Direction::StraightY);
+        assert_eq!(boundaries.next(), Some(0..3));
+        assert_eq!(boundaries.len(), 3);
+        assert_eq!(boundaries.last(), Some(9..12));
+
+        let empty: Vec<Vec<i32>> = vec![vec![], vec![]];
+        assert_eq!(lines_flat(&empty, Direction::StraightX).len(), 0);
+        assert_eq!(
+            line_boundaries(2, 0, Direction::StraightX).collect::<Vec<_>>(),
+            [0..0, 0..0]
+        );
+    }
+
+    #[test]
+    fn huge_dimensions() {
+        // Zero-sized elements allow rows of `usize::MAX` cells.
+        let wide = [vec![(); usize::MAX]];
+        for direction in [Direction::DiagonalPosPos, Direction::DiagonalPosNeg] {
+            let mut lines = lines_iter(&wide, direction);
+            assert_eq!(lines.len(), usize::MAX);
+            assert_eq!(lines.next_back().map(|line| line.len()), Some(1));
+            assert_eq!(lines.nth(usize::MAX - 2).map(|line| line.len()), Some(1));
+            assert!(lines.next().is_none());
+        }
+        assert_eq!(straight_y_iter(&wide).len(), usize::MAX);
+        assert_eq!(lines_flat(&wide, Direction::StraightX).len(), usize::MAX);
+        let mut boundaries = line_boundaries(1, usize::MAX, Direction::StraightX);
+        assert_eq!(boundaries.next(), Some(0..usize::MAX));
+        assert_eq!(boundaries.next(), None);
+
+        let too_wide = [vec![(); usize::MAX], vec![(); usize::MAX]];
+        assert!(
+            std::panic::catch_unwind(|| lines_iter(&too_wide, Direction::DiagonalPosPos)).is_err()
+        );
+        assert!(std::panic::catch_unwind(|| lines_flat(&too_wide, Direction::StraightX)).is_err());
+        assert!(
+            std::panic::catch_unwind(|| line_boundaries(2, usize::MAX, Direction::StraightX))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let matrix: Vec<Vec<i32>> = vec![];
+        for direction in Direction::ALL {
+            assert_eq!(lines_iter(&matrix, direction).len(), 0);
+        }
+        let matrix: Vec<Vec<i32>> = vec![vec![], vec![]];
+        assert_eq!(diagonal_pos_pos_iter(&matrix).len(), 0);
+        assert_eq!(straight_y_iter(&matrix).len(), 0);
+        assert!(straight_x_iter(&matrix).all(|row| row.len() == 0));
+    }
+}
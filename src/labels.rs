@@ -0,0 +1,82 @@
+use crate::Direction;
+
+/// Builds a `rows` x `cols` matrix in which every cell holds the index of the line it belongs
+/// to.
+///
+/// The indices are the positions of the lines in the output of the function named by `kind`,
+/// so the matrix works as a lookup table from cells to lines, for example to group cells by
+/// diagonal or to upload to a GPU.
+///
+/// # Arguments
+///
+/// * `rows` - The number of rows of the matrix.
+/// * `cols` - The number of columns of the matrix.
+/// * `kind` - The traversal whose line indices to use.
+///
+/// # Returns
+///
+/// A `rows` x `cols` matrix as a `Vec<Vec<usize>>`.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{diagonal_labels, Direction};
+///
+/// assert_eq!(diagonal_labels(2, 3, Direction::DiagonalPosPos), vec![
+///     vec![1, 2, 3],
+///     vec![0, 1, 2],
+/// ]);
+/// assert_eq!(diagonal_labels(2, 3, Direction::DiagonalPosNeg), vec![
+///     vec![0, 1, 2],
+///     vec![1, 2, 3],
+/// ]);
+/// ```
+pub fn diagonal_labels(rows: usize, cols: usize, kind: Direction) -> Vec<Vec<usize>> {
+    (0..rows)
+        .map(|row| {
+            (0..cols)
+                .map(|col| kind.line_of(rows, (row, col)))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_match_traversals() {
+        for rows in 1..5 {
+            for cols in 1..5 {
+                let matrix: Vec<Vec<(usize, usize)>> = (0..rows)
+                    .map(|r| (0..cols).map(|c| (r, c)).collect())
+                    .collect();
+                for direction in Direction::ALL {
+                    let labels = diagonal_labels(rows, cols, direction);
+                    for (index, line) in crate::lines_iter(&matrix, direction).enumerate() {
+                        for &(x, y) in line {
+                            assert_eq!(labels[x][y], index);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn straight_lines() {
+        assert_eq!(
+            diagonal_labels(2, 2, Direction::StraightX),
+            vec![vec![0, 0], vec![1, 1]]
+        );
+        assert_eq!(
+            diagonal_labels(2, 2, Direction::StraightY),
+            vec![vec![0, 1], vec![0, 1]]
+        );
+        assert_eq!(
+            diagonal_labels(2, 0, Direction::StraightY),
+            vec![Vec::<usize>::new(); 2]
+        );
+    }
+}
@@ -85,9 +85,201 @@
 //! ]);
 //! ```
 //!
+//! ## Checked Functions
+//!
+//! The [`checked`] module validates its input and returns a [`DiagonalError`] for ragged rows,
+//! empty rows, oversized shapes or mismatched diagonals instead of panicking, for matrices that
+//! come from untrusted sources. [`checked::diagonal_count`] validates bare dimensions, such as
+//! the ones handed to [`diagonal_labels`] or [`wavefront`], before anything is allocated. The
+//! slice-taking functions that can panic, such as [`diagonal_difference`] on a rectangular
+//! matrix or [`window_diagonals`] with an empty window, have checked counterparts as well. The
+//! [`checked`] module lists the few functions that stay unchecked; the rest cannot fail on a
+//! matrix that has passed [`checked::shape`].
+//!
+//! ## Verifying Custom Traversals
+//!
+//! [`verify_decomposition`] checks that a list of coordinate lines visits every cell exactly
+//! once and in the order of a [`Direction`], and reports the first violation as a
+//! [`CoverageError`], for property tests of traversals built on top of this crate.
+//!
+//! ## Lazy Iterators
+//!
+//! [`lines_iter`] and the shorthands such as [`diagonal_pos_pos_iter`] perform the same
+//! traversals without allocating. Both the [`Lines`] and the [`Elements`] of each line are
+//! double-ended and know their exact length, so `.rev()` walks the lines back to front without
+//! collecting them first. [`lines_into`] and the `*_into` shorthands collect the lines directly
+//! into any [`FromIterator`] containers, such as `Vec<String>` or `Vec<SmallVec<[&T; 4]>>`;
+//! the `smallvec` feature names the latter `lines_small`. The `bumpalo` feature adds
+//! `lines_in_bump`, which allocates the lines from an arena.
+//!
+//! [`lines_flat`] yields a whole traversal as one flat sequence of elements, and
+//! [`line_boundaries`] yields where each line starts and ends in it, so a decomposition can be
+//! streamed into writers and encoders without building nested vectors.
+//!
+//! ## Owning Traversals
+//!
+//! [`into_diagonal_pos_pos`], [`into_diagonal_pos_neg`], [`into_straight_x`] and
+//! [`into_straight_y`] consume a `Vec<Vec<T>>` and move its elements into the lines, for element
+//! types that cannot or should not be cloned.
+//!
+//! ## Line Metadata
+//!
+//! [`lines`] returns every line in a direction as a [`Line`] that dereferences to its elements
+//! and also carries its direction, index, diagonal offset and starting coordinate.
+//! [`diagonals_by_offset`] keys the diagonals by the linear-algebra offset `k = column - row`
+//! instead of their position in the traversal.
+//!
+//! [`diagonal_labels`] builds the matching lookup table: a matrix of a given shape whose cells
+//! hold the index of the line they belong to.
+//!
+//! ## Pattern Search
+//!
+//! [`find_pattern`] reports every occurrence of a sequence along the rows, columns and
+//! diagonals of a matrix as a [`Match`] with its direction, line and starting coordinate. With
+//! the `rayon` feature, `par_find_pattern` searches the lines in parallel and returns the same
+//! matches in the same order. [`find_pattern_fuzzy`] compares cells with a matcher closure
+//! instead of `PartialEq` and tolerates a number of mismatches, reporting each as a
+//! [`FuzzyMatch`] with the coordinates of its mismatched cells. [`segments_matching`] reports
+//! every maximal stretch of cells that satisfy a predicate along each line as a [`Segment`] with
+//! the same metadata and its length. [`Search`] is the pipeline underneath such analyses: a
+//! builder that picks the directions and a minimum length and then yields every remaining line
+//! as a [`Line`].
+//!
+//! ## Monotonic Runs
+//!
+//! [`longest_increasing_run`] finds the longest strictly increasing run along any row, column
+//! or diagonal in a single pass and returns its coordinates, and [`longest_run_by`] does the
+//! same for any condition on neighbouring elements.
+//!
+//! ## Wrapping Rays
+//!
+//! [`ray_wrapping`] walks from a cell in any direction on a torus, re-entering the matrix on
+//! the opposite side at the edges, for line-of-sight queries in wrap-around worlds.
+//!
+//! ## Batches
+//!
+//! [`batch_diagonals`] extracts both diagonal traversals of many matrices of the same shape,
+//! validating the shape and planning the traversal only once for the whole batch. With the
+//! `rayon` feature, `par_batch_diagonals` traverses the matrices in parallel.
+//!
+//! ## Wavefronts
+//!
+//! [`wavefront`] computes a table cell by cell in anti-diagonal order, handing every cell the
+//! ones above and to the left of it through a [`Frontier`], as dynamic programs like edit
+//! distance need. With the `rayon` feature, `par_wavefront` computes the cells of each
+//! anti-diagonal in parallel.
+//!
+//! ## Streaming
+//!
+//! The [`stream`] module extracts diagonals from matrices that arrive in horizontal strips,
+//! such as memory-mapped files larger than RAM, and hands out every diagonal as soon as it is
+//! complete.
+//!
+//! ## Window Diagonals
+//!
+//! [`window_diagonals`] returns the main and anti-diagonal of every `k` x `k` window together
+//! with the window's top-left coordinate, for cross-shaped pattern searches and local diagonal
+//! features, without copying any window.
+//!
+//! ## Windowed Reductions
+//!
+//! [`diagonal_windowed_pos_pos`] and [`diagonal_windowed_pos_neg`] reduce every window of `k`
+//! consecutive elements along each diagonal, such as a moving average, and pair every result
+//! with the coordinate of the window's first element.
+//!
+//! ## Block-Diagonal Matrices
+//!
+//! [`diagonal_blocks`] returns the square blocks along the main diagonal as views of the rows,
+//! and [`is_block_diagonal`] checks that every cell outside of them is zero.
+//!
+//! ## 45-Degree Rotation
+//!
+//! [`rot45`] rotates a matrix into diagonal coordinates, a padded grid whose rows are the
+//! diagonals with positive slope and whose columns are the diagonals with positive and negative
+//! slope, so row algorithms can run on diagonals. [`unrot45`] rotates it back.
+//!
+//! ## Staircase Matrices
+//!
+//! [`triangle_diagonals`] extracts the diagonals of jagged matrices whose rows grow or shrink by
+//! one element at a time, such as Pascal's triangle, along the staircase's slanted edge.
+//!
+//! ## Bitboards
+//!
+//! The [`bits`] module traverses boolean matrices packed into one `u64` per row and returns
+//! every line as a packed bitmask, with helpers such as [`bits::any_run`].
+//!
+//! ## Cubes
+//!
+//! The [`cube`] module extracts axis-aligned lines, face diagonals and space diagonals from
+//! three-dimensional grids such as `Vec<Vec<Vec<T>>>`.
+//!
+//! ## Fixed-Size Arrays
+//!
+//! The [`fixed`] module provides const-generic traversals for `[[T; C]; R]` matrices, returning
+//! nested arrays for rows and columns and an array of exactly `R + C - 1` diagonals.
+//!
+//! ## Flat Storage
+//!
+//! The [`flat`] module runs the traversals directly over flat buffers in row-major
+//! ([`flat::RowMajor`]) or column-major ([`flat::ColMajor`]) order, such as data shared with
+//! Fortran or LAPACK, without transposing it into nested rows first. [`flat::StridedView`]
+//! describes any layout by its row and column strides, covering submatrices, transposed views
+//! and interleaved pixel buffers with zero copying.
+//!
+//! ## Signed-Coordinate Grids
+//!
+//! The [`grid`] module provides [`grid::Grid`], an owned matrix addressed with signed
+//! coordinates relative to a configurable origin, whose traversals report every cell's
+//! coordinate. [`grid::Grid::generate`] and [`grid::Grid::generate_by_diagonal`] build grids
+//! from a function of each cell's row and column, or of its diagonal and position along it.
+//!
+//! ## Hex Grids
+//!
+//! The [`hex`] module extracts the three natural line directions of hexagonal grids stored in
+//! axial coordinates, the hex counterparts of rows and diagonals.
+//!
+//! ## Hash Grids
+//!
+//! The [`hash_grid`] module runs the same traversals over grids stored as
+//! `HashMap<(i64, i64), T>` within explicit bounds, returning `None` for missing cells.
+//!
+//! ## N-Dimensional Grids
+//!
+//! The [`ndim`] module enumerates all maximal lines in a given direction through grids of any
+//! number of dimensions, described by a shape and stored in a flat slice.
+//!
+//! ## Rail-Fence Cipher
+//!
+//! The [`cipher`] module encodes and decodes text with the rail-fence (zigzag) cipher by
+//! writing it diagonally across a grid of rails and reading the grid row by row.
+//!
+//! ## Pretty-Printing
+//!
+//! The [`pretty`] module renders a matrix with each cell labeled by its line, or a
+//! decomposition with one line per row, through [`Display`](std::fmt::Display).
+//!
 //! The provided functions enable convenient extraction and manipulation of matrix diagonals,
 //! making it easier to perform various operations on matrix elements.
 //!
+//! # Features
+//!
+//! * `rayon` - Adds parallel variants such as `par_find_pattern` that spread the lines over
+//!   rayon's thread pool.
+//! * `serde` - Implements `Serialize` and `Deserialize` for the crate's owned types:
+//!   [`grid::Grid`], [`hash_grid::Bounds`], [`hex::HexAxis`], [`cube::Axis`] and
+//!   [`Direction`], the results [`Match`], [`FuzzyMatch`], [`Segment`], [`LineDiff`] and
+//!   [`DiagonalStats`], and the errors [`DiagonalError`] and [`CoverageError`]. Owned
+//!   decompositions such as `Vec<Vec<T>>` are serializable already.
+//! * `bumpalo` - Adds `lines_in_bump` and the `*_in_bump` shorthands such as
+//!   `diagonal_pos_pos_in_bump`, which allocate their results from a `bumpalo::Bump` arena.
+//!   Together with `allocator-api`, `&Bump` can also be passed to `lines_in`.
+//! * `smallvec` - Adds `lines_small` and the `*_small` shorthands such as
+//!   `diagonal_pos_pos_small`, which store every line of up to `N` elements inline in a
+//!   `SmallVec<[&T; N]>`.
+//! * `allocator-api` - Adds `lines_in` and the `*_in` shorthands such as `diagonal_pos_pos_in`,
+//!   which allocate their results from a custom [`Allocator`](std::alloc::Allocator). This
+//!   feature requires a nightly compiler.
+//!
 //! # Note
 //!
 //! Make sure to use the `diagonal` crate and import the necessary functions for these examples to work.
@@ -98,6 +290,120 @@
 //! diagonal = "0.1.0"
 //! ```
 
+#![cfg_attr(feature = "allocator-api", feature(allocator_api))]
+
+#[cfg(feature = "allocator-api")]
+mod alloc;
+mod batch;
+pub mod bits;
+mod block;
+mod build;
+#[cfg(feature = "bumpalo")]
+mod bump;
+mod check;
+pub mod checked;
+pub mod cipher;
+mod coverage;
+pub mod cube;
+mod diff;
+mod direction;
+mod error;
+pub mod fixed;
+pub mod flat;
+pub mod grid;
+pub mod hash_grid;
+pub mod hex;
+mod index;
+mod iter;
+mod labels;
+mod line;
+pub mod ndim;
+mod owned;
+pub mod pretty;
+mod ray;
+mod reduce;
+mod rotate;
+mod run;
+mod search;
+#[cfg(feature = "smallvec")]
+mod small;
+mod sparse;
+mod stats;
+pub mod stream;
+mod transform;
+mod triangle;
+mod view;
+mod wavefront;
+mod window;
+
+#[cfg(feature = "allocator-api")]
+pub use alloc::{diagonal_pos_neg_in, diagonal_pos_pos_in, lines_in, straight_x_in, straight_y_in};
+#[cfg(feature = "rayon")]
+pub use batch::par_batch_diagonals;
+pub use batch::{batch_diagonals, Diagonals};
+pub use block::diagonal_blocks;
+pub use build::{from_diagonal_pos_neg, from_diagonal_pos_pos, matrix_from_diagonals};
+#[cfg(feature = "bumpalo")]
+pub use bump::{
+    diagonal_pos_neg_in_bump, diagonal_pos_pos_in_bump, lines_in_bump, straight_x_in_bump,
+    straight_y_in_bump,
+};
+pub use check::{
+    is_block_diagonal, is_diagonally_dominant, is_persymmetric, is_strictly_diagonally_dominant,
+    is_symmetric,
+};
+pub use coverage::verify_decomposition;
+pub use diff::{diff_lines, LineDiff};
+pub use direction::Direction;
+pub use error::{CoverageError, DiagonalError};
+pub use iter::{
+    diagonal_pos_neg_into, diagonal_pos_neg_iter, diagonal_pos_pos_into, diagonal_pos_pos_iter,
+    line_boundaries, lines_flat, lines_into, lines_iter, straight_x_into, straight_x_iter,
+    straight_y_into, straight_y_iter, Boundaries, Elements, FlatElements, Lines,
+};
+pub use labels::diagonal_labels;
+pub use line::{diagonals_by_offset, lines, Line};
+pub use owned::{into_diagonal_pos_neg, into_diagonal_pos_pos, into_straight_x, into_straight_y};
+pub use ray::ray_wrapping;
+pub use reduce::{
+    anti_trace, convolve_diagonals_pos_neg, convolve_diagonals_pos_pos, diagonal_argmax_pos_neg,
+    diagonal_argmax_pos_pos, diagonal_argmin_pos_neg, diagonal_argmin_pos_pos, diagonal_difference,
+    diagonal_max_pos_neg, diagonal_max_pos_pos, diagonal_min_pos_neg, diagonal_min_pos_pos,
+    diagonal_prefix_sums_into_pos_neg, diagonal_prefix_sums_into_pos_pos,
+    diagonal_prefix_sums_pos_neg, diagonal_prefix_sums_pos_pos, diagonal_sums_pos_neg,
+    diagonal_sums_pos_pos, diagonal_windowed_pos_neg, diagonal_windowed_pos_pos, reduce_lines,
+    trace,
+};
+pub use rotate::{rot45, unrot45};
+pub use run::{longest_increasing_run, longest_run_by};
+#[cfg(feature = "rayon")]
+pub use search::par_find_pattern;
+pub use search::{
+    find_pattern, find_pattern_fuzzy, segments_matching, FuzzyMatch, Match, Search, Segment,
+};
+#[cfg(feature = "smallvec")]
+pub use small::{
+    diagonal_pos_neg_small, diagonal_pos_pos_small, lines_small, straight_x_small, straight_y_small,
+};
+pub use sparse::{
+    diagonals_from_triplets_pos_neg, diagonals_from_triplets_pos_pos, SparseDiagonal, Triplet,
+};
+pub use stats::{diagonal_stats, DiagonalStats};
+pub use transform::{
+    apply_diagonal_pos_neg, apply_diagonal_pos_pos, map_diagonals_pos_neg, map_diagonals_pos_pos,
+    shift_diagonals_pos_neg, shift_diagonals_pos_pos, sort_diagonals, sort_diagonals_by,
+};
+pub use triangle::triangle_diagonals;
+pub use view::{
+    diagonal_pos_neg_flip_horizontal, diagonal_pos_neg_flip_vertical, diagonal_pos_neg_reversed,
+    diagonal_pos_pos_flip_horizontal, diagonal_pos_pos_flip_vertical, diagonal_pos_pos_reversed,
+    flip_horizontal, flip_vertical, rotate180, rotate270, rotate90, transpose, transposed,
+};
+#[cfg(feature = "rayon")]
+pub use wavefront::par_wavefront;
+pub use wavefront::{wavefront, Frontier};
+pub use window::{window_diagonals, WindowDiagonals};
+
 /// Extracts diagonals with positive slope from a matrix starting from the bottom-left (x: maximum, y: 0) corner.
 ///
 /// Given a matrix, this function traverses diagonals with `x+ y+` slope starting from
@@ -138,39 +444,8 @@ pub fn diagonal_pos_pos<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
     matrix: &'a Matrix,
 ) -> Vec<Vec<&'a T>> {
     let matrix = matrix.as_ref();
-    let x_len = matrix.len();
-    if matrix.is_empty() {
-        return vec![];
-    }
-    let y_len = matrix[0].as_ref().len();
-
-    let mut x = x_len - 1;
-    let mut y = 0;
-
-    let mut result: Vec<Vec<&T>> = vec![vec![]];
-
-    loop {
-        result.last_mut().unwrap().push(&matrix[x].as_ref()[y]);
-
-        x += 1;
-        y += 1;
-
-        if !(0..x_len).contains(&x) || !(0..y_len).contains(&y) {
-            y += 1;
-            let min = x.min(y);
-            x -= min;
-            y -= min;
-
-            result.push(Vec::new());
-        }
-
-        if !(0..x_len).contains(&x) || !(0..y_len).contains(&y) {
-            break;
-        }
-    }
-
-    result.pop();
-    result
+    let (rows, cols) = index::shape(matrix);
+    collect_cells(matrix, index::pos_pos_cells(rows, cols))
 }
 
 /// Extracts diagonals with positive and negative slope from a matrix starting from the top-left (x & y: 0) corner.
@@ -216,46 +491,8 @@ pub fn diagonal_pos_neg<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
     matrix: &'a Matrix,
 ) -> Vec<Vec<&'a T>> {
     let matrix = matrix.as_ref();
-    let x_len = matrix.len();
-    if matrix.is_empty() {
-        return vec![];
-    }
-    let y_len = matrix[0].as_ref().len();
-
-    if matrix.is_empty() {
-        return vec![];
-    }
-
-    let mut x: isize = 0;
-    let mut y: isize = 0;
-
-    let mut result: Vec<Vec<&T>> = vec![vec![]];
-
-    loop {
-        result
-            .last_mut()
-            .unwrap()
-            .push(&matrix[x as usize].as_ref()[y as usize]);
-
-        x += 1;
-        y -= 1;
-
-        if !(0..x_len).contains(&(x as usize)) || !(0..y_len).contains(&(y as usize)) {
-            x += 1;
-            let min = x.min(y_len as isize - 1 - y);
-            x -= min;
-            y += min;
-
-            result.push(Vec::new());
-        }
-
-        if !(0..x_len).contains(&(x as usize)) || !(0..y_len).contains(&(y as usize)) {
-            break;
-        }
-    }
-
-    result.pop();
-    result
+    let (rows, cols) = index::shape(matrix);
+    collect_cells(matrix, index::pos_neg_cells(rows, cols))
 }
 
 /// Extracts elements from a matrix in a row-major order and organizes them into vectors,
@@ -344,23 +581,29 @@ pub fn straight_y<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
     matrix: &'a Matrix,
 ) -> Vec<Vec<&'a T>> {
     let matrix = matrix.as_ref();
-    if matrix.is_empty() {
-        return vec![];
-    }
-
-    let mut result: Vec<Vec<&T>> = vec![vec![]];
-
-    for y in 0..matrix[0].as_ref().len() {
-        for x in matrix.iter() {
-            result.last_mut().unwrap().push(&x.as_ref()[y]);
+    let (rows, cols) = index::shape(matrix);
+
+    // Walking down a whole column touches every row once per column, so tall matrices are read
+    // in blocks of rows that stay in cache while all columns are extended from them.
+    let mut result: Vec<Vec<&T>> = (0..cols).map(|_| Vec::with_capacity(rows)).collect();
+    for block in matrix.chunks(index::ROW_BLOCK) {
+        for (y, column) in result.iter_mut().enumerate() {
+            column.extend(block.iter().map(|row| &row.as_ref()[y]));
         }
-        result.push(Vec::new());
     }
-
-    result.pop();
     result
 }
 
+/// Resolves every cell of every line to a reference to its element.
+fn collect_cells<Row: AsRef<[T]>, T>(
+    matrix: &[Row],
+    lines: impl Iterator<Item = impl Iterator<Item = (usize, usize)>>,
+) -> Vec<Vec<&T>> {
+    lines
+        .map(|line| line.map(|(x, y)| &matrix[x].as_ref()[y]).collect())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -588,5 +831,21 @@ mod tests {
 
         let matrix: [[usize; 0]; 0] = [];
         assert_eq!(straight_y(&matrix), Vec::<Vec<&usize>>::new());
+
+        let matrix = vec![Vec::<usize>::new(); 3];
+        assert_eq!(straight_y(&matrix), Vec::<Vec<&usize>>::new());
+    }
+
+    #[test]
+    fn straight_y_spans_row_blocks() {
+        let rows = 2 * index::ROW_BLOCK + 5;
+        let matrix: Vec<[usize; 3]> = (0..rows).map(|r| [3 * r, 3 * r + 1, 3 * r + 2]).collect();
+        for (y, column) in straight_y(&matrix).iter().enumerate() {
+            assert_eq!(column.len(), rows);
+            assert!(column
+                .iter()
+                .enumerate()
+                .all(|(x, &&value)| value == 3 * x + y));
+        }
     }
 }
@@ -0,0 +1,301 @@
+use std::collections::BTreeMap;
+use std::ops::Deref;
+
+use crate::{index, Direction};
+
+/// One line of a matrix together with where it lies, as returned by [`lines`].
+///
+/// A `Line` dereferences to the slice of references to its elements, so it can be used wherever
+/// the `Vec<&T>` returned by the other traversals can.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{lines, Direction};
+///
+/// let matrix = [[1, 2, 3], [4, 5, 6]];
+///
+/// let hit = lines(&matrix, Direction::DiagonalPosPos)
+///     .into_iter()
+///     .find(|line| line.contains(&&6))
+///     .unwrap();
+/// assert_eq!(hit.index(), 2);
+/// assert_eq!(hit.offset(), 1);
+/// assert_eq!(hit.start(), (0, 1));
+/// assert_eq!(hit.len(), 2);
+/// assert_eq!(hit[0], &2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Line<'a, T> {
+    direction: Direction,
+    index: usize,
+    offset: isize,
+    start: (usize, usize),
+    elements: Vec<&'a T>,
+}
+
+impl<'a, T> Line<'a, T> {
+    /// Collects the `index`-th line in `direction` of a `rows` x `cols` matrix.
+    pub(crate) fn new<Row: AsRef<[T]>>(
+        matrix: &'a [Row],
+        (rows, cols): (usize, usize),
+        direction: Direction,
+        index: usize,
+    ) -> Self {
+        let (start, len) = direction.line_start(rows, cols, index);
+        let (x, y) = start;
+        let offset = match direction {
+            Direction::DiagonalPosPos => index::signed_difference(y, x),
+            Direction::DiagonalPosNeg => index::signed_difference(cols - 1, x + y),
+            Direction::StraightX => index::signed_difference(x, 0),
+            Direction::StraightY => index::signed_difference(y, 0),
+        };
+        Line {
+            direction,
+            index,
+            offset,
+            start,
+            elements: (0..len)
+                .map(|i| {
+                    let (x, y) = direction.cell(start, i);
+                    &matrix[x].as_ref()[y]
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the direction the line runs in.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Returns the index of the line in the output of the function named by its direction.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the offset of the line from the matrix's main line in its direction.
+    ///
+    /// * For [`Direction::DiagonalPosPos`] it is `column - row` of the line's cells, so `0` is
+    ///   the main diagonal starting in the top-left corner, as in
+    ///   [`matrix_from_diagonals`](crate::matrix_from_diagonals).
+    /// * For [`Direction::DiagonalPosNeg`] it is `(columns - 1) - (row + column)`, so `0` is the
+    ///   anti-diagonal starting in the top-right corner.
+    /// * For [`Direction::StraightX`] and [`Direction::StraightY`] it is the row or column
+    ///   index.
+    ///
+    /// In both diagonal directions, lines above the main one have positive offsets and lines
+    /// below it negative ones.
+    ///
+    /// Offsets are computed when the line is created, which panics if the offset does not fit in
+    /// an `isize`. That takes a matrix of zero-sized rows or elements with more than `isize::MAX`
+    /// rows or columns.
+    pub fn offset(&self) -> isize {
+        self.offset
+    }
+
+    /// Returns the `(row, column)` coordinate of the line's first element.
+    pub fn start(&self) -> (usize, usize) {
+        self.start
+    }
+
+    /// Returns the references to the line's elements.
+    pub fn into_elements(self) -> Vec<&'a T> {
+        self.elements
+    }
+}
+
+impl<'a, T> Deref for Line<'a, T> {
+    type Target = [&'a T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.elements
+    }
+}
+
+/// Extracts the lines of a matrix in one direction, each together with its position.
+///
+/// The lines and their elements are the ones the function named by `direction` returns, in the
+/// same order, but every line also carries its direction, index, offset and starting
+/// coordinate, so results can be reported without recomputing which line they came from.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `direction` - The traversal to perform.
+///
+/// # Returns
+///
+/// A vector with one [`Line`] per line of the matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{lines, Direction};
+///
+/// let matrix = [[1, 2], [3, 4]];
+///
+/// let offsets: Vec<isize> = lines(&matrix, Direction::DiagonalPosNeg)
+///     .iter()
+///     .map(|line| line.offset())
+///     .collect();
+/// assert_eq!(offsets, vec![1, 0, -1]);
+/// ```
+pub fn lines<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+    direction: Direction,
+) -> Vec<Line<'a, T>> {
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+
+    (0..direction.line_count(rows, cols))
+        .map(|index| Line::new(matrix, (rows, cols), direction, index))
+        .collect()
+}
+
+/// Extracts the diagonals with positive slope of a matrix, keyed by their offset.
+///
+/// The offset of a diagonal is `k = column - row`, the convention of linear algebra: `0` is the
+/// main diagonal, positive offsets are superdiagonals and negative offsets subdiagonals. The
+/// elements of every diagonal are in [`diagonal_pos_pos`](crate::diagonal_pos_pos) order, from
+/// the top-left end to the bottom-right end, and iterating the map visits the diagonals in the
+/// same order as that function returns them.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `BTreeMap` from the offset of each diagonal to references to its elements.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonals_by_offset;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ];
+///
+/// let diagonals = diagonals_by_offset(&matrix);
+/// assert_eq!(diagonals[&0], vec![&1, &5]);
+/// assert_eq!(diagonals[&-1], vec![&4]);
+/// assert_eq!(diagonals[&2], vec![&3]);
+/// assert_eq!(diagonals.keys().copied().collect::<Vec<_>>(), vec![-1, 0, 1, 2]);
+/// ```
+pub fn diagonals_by_offset<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> BTreeMap<isize, Vec<&'a T>> {
+    lines(matrix, Direction::DiagonalPosPos)
+        .into_iter()
+        .map(|line| (line.offset(), line.into_elements()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix() -> Vec<Vec<(usize, usize)>> {
+        (0..3).map(|r| (0..5).map(|c| (r, c)).collect()).collect()
+    }
+
+    #[test]
+    fn elements_match_traversals() {
+        let matrix = matrix();
+        for direction in Direction::ALL {
+            let expected: Vec<Vec<&(usize, usize)>> = crate::lines_iter(&matrix, direction)
+                .map(Iterator::collect)
+                .collect();
+            let lines = lines(&matrix, direction);
+            for (i, (line, expected)) in lines.iter().zip(&expected).enumerate() {
+                assert_eq!(line.direction(), direction);
+                assert_eq!(line.index(), i);
+                assert_eq!(*line[0], line.start());
+                assert_eq!(&line[..], &expected[..]);
+            }
+            assert_eq!(
+                lines
+                    .into_iter()
+                    .map(Line::into_elements)
+                    .collect::<Vec<_>>(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn offsets() {
+        let matrix = matrix();
+        for direction in Direction::ALL {
+            for line in lines(&matrix, direction) {
+                for &&(row, col) in line.iter() {
+                    let (row, col) = (row as isize, col as isize);
+                    let expected = match direction {
+                        Direction::DiagonalPosPos => col - row,
+                        Direction::DiagonalPosNeg => 4 - (row + col),
+                        Direction::StraightX => row,
+                        Direction::StraightY => col,
+                    };
+                    assert_eq!(line.offset(), expected);
+                }
+            }
+        }
+
+        let main = lines(&matrix, Direction::DiagonalPosPos)
+            .into_iter()
+            .find(|line| line.offset() == 0)
+            .unwrap();
+        assert_eq!(main.start(), (0, 0));
+        let anti = lines(&matrix, Direction::DiagonalPosNeg)
+            .into_iter()
+            .find(|line| line.offset() == 0)
+            .unwrap();
+        assert_eq!(anti.start(), (0, 4));
+    }
+
+    #[test]
+    fn by_offset() {
+        let matrix = matrix();
+        let diagonals = diagonals_by_offset(&matrix);
+        assert_eq!(
+            diagonals.values().cloned().collect::<Vec<_>>(),
+            crate::diagonal_pos_pos(&matrix)
+        );
+        for (&k, diagonal) in &diagonals {
+            assert!(diagonal
+                .iter()
+                .all(|&&(row, col)| col as isize - row as isize == k));
+        }
+        assert!(diagonals_by_offset(&Vec::<Vec<u8>>::new()).is_empty());
+    }
+
+    #[test]
+    fn offsets_at_the_limit() {
+        // Zero-sized rows allow more rows than an `isize` can count.
+        let rows = isize::MIN.unsigned_abs() + 1;
+        let tall = vec![[(); 1]; rows];
+
+        let first = Line::new(&tall, (rows, 1), Direction::DiagonalPosPos, 0);
+        assert_eq!((first.start(), first.offset()), ((rows - 1, 0), isize::MIN));
+        let last = Line::new(&tall, (rows, 1), Direction::DiagonalPosNeg, rows - 1);
+        assert_eq!((last.start(), last.offset()), ((rows - 1, 0), isize::MIN));
+        let row = Line::new(&tall, (rows, 1), Direction::StraightX, rows - 2);
+        assert_eq!(row.offset(), isize::MAX);
+
+        let taller = vec![[(); 1]; rows + 1];
+        let overflow = std::panic::catch_unwind(|| {
+            Line::new(&taller, (rows + 1, 1), Direction::DiagonalPosPos, 0)
+        });
+        assert!(overflow.is_err());
+    }
+
+    #[test]
+    fn empty() {
+        assert!(lines(&Vec::<Vec<u8>>::new(), Direction::StraightY).is_empty());
+    }
+}
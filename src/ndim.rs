@@ -0,0 +1,260 @@
+//! # N-Dimensional Operations Module
+//!
+//! This module enumerates lines through n-dimensional grids. Instead of nested containers,
+//! a grid is described by its shape and stored in a flat slice, either in row-major order (the
+//! last axis changes fastest) or with explicit strides.
+//!
+//! A direction is given as one component per axis, each of which is `-1`, `0` or `1`. The lines
+//! in a direction are all maximal runs of cells that can be reached by repeatedly stepping in
+//! that direction, so every cell belongs to exactly one line per direction.
+//!
+//! ### Example
+//!
+//! ```rust
+//! use diagonal::ndim::lines;
+//!
+//! // A 2x2x2 grid stored in row-major order.
+//! let data = [1, 2, 3, 4, 5, 6, 7, 8];
+//!
+//! assert_eq!(lines(&data, &[2, 2, 2], &[1, 1, 1]), vec![
+//!     vec![&1, &8],
+//!     vec![&2],
+//!     vec![&3],
+//!     vec![&4],
+//!     vec![&5],
+//!     vec![&6],
+//!     vec![&7],
+//! ]);
+//! ```
+
+/// Computes the row-major strides of a grid with the given shape.
+///
+/// # Arguments
+///
+/// * `shape` - The size of the grid along each axis.
+///
+/// # Returns
+///
+/// The distance in a flat row-major buffer between neighbouring cells along each axis.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::ndim::row_major_strides;
+///
+/// assert_eq!(row_major_strides(&[2, 3, 4]), vec![12, 4, 1]);
+/// ```
+pub fn row_major_strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for axis in (0..shape.len().saturating_sub(1)).rev() {
+        strides[axis] = strides[axis + 1] * shape[axis + 1];
+    }
+    strides
+}
+
+/// Enumerates the flat offsets of every maximal line through a grid in one direction.
+///
+/// Lines are ordered by their first cell in row-major order, and each line lists its cells in
+/// the order they are visited when stepping along `direction`.
+///
+/// # Arguments
+///
+/// * `shape` - The size of the grid along each axis.
+/// * `strides` - The distance in the flat buffer between neighbouring cells along each axis.
+/// * `direction` - The step along each axis, each component being `-1`, `0` or `1`.
+///
+/// # Returns
+///
+/// A `Vec<Vec<usize>>` where each inner vector holds the flat offsets of one line.
+///
+/// # Panics
+///
+/// Panics if `shape`, `strides` and `direction` differ in length, or if `direction` is all
+/// zeros or has a component other than `-1`, `0` or `1`.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::ndim::line_offsets;
+///
+/// // The anti-diagonals of a 2x3 matrix stored in column-major order.
+/// assert_eq!(line_offsets(&[2, 3], &[1, 2], &[1, -1]), vec![
+///     vec![0],
+///     vec![2, 1],
+///     vec![4, 3],
+///     vec![5],
+/// ]);
+/// ```
+pub fn line_offsets(shape: &[usize], strides: &[usize], direction: &[isize]) -> Vec<Vec<usize>> {
+    assert_eq!(
+        shape.len(),
+        strides.len(),
+        "shape and strides must have the same length"
+    );
+    assert_eq!(
+        shape.len(),
+        direction.len(),
+        "shape and direction must have the same length"
+    );
+    assert!(
+        direction.iter().all(|step| (-1..=1).contains(step)),
+        "direction components must be -1, 0 or 1"
+    );
+    assert!(
+        direction.iter().any(|&step| step != 0),
+        "direction must not be all zeros"
+    );
+
+    let offset = |cell: &[usize]| -> usize { cell.iter().zip(strides).map(|(c, s)| c * s).sum() };
+    let step = |cell: &mut [usize], sign: isize| -> bool {
+        for ((c, &d), &len) in cell.iter_mut().zip(direction).zip(shape) {
+            match c.checked_add_signed(d * sign) {
+                Some(next) if next < len => *c = next,
+                _ => return false,
+            }
+        }
+        true
+    };
+
+    let mut result = Vec::new();
+    if shape.contains(&0) {
+        return result;
+    }
+
+    let mut cell = vec![0; shape.len()];
+    loop {
+        // A cell starts a line if stepping backwards leaves the grid.
+        if !step(&mut cell.clone(), -1) {
+            let mut current = cell.clone();
+            let mut line = vec![offset(&current)];
+            while step(&mut current, 1) {
+                line.push(offset(&current));
+            }
+            result.push(line);
+        }
+
+        // Advance to the next cell in row-major order.
+        let mut axis = shape.len();
+        loop {
+            if axis == 0 {
+                return result;
+            }
+            axis -= 1;
+            cell[axis] += 1;
+            if cell[axis] < shape[axis] {
+                break;
+            }
+            cell[axis] = 0;
+        }
+    }
+}
+
+/// Extracts every maximal line through a row-major grid in one direction.
+///
+/// This resolves the offsets returned by [`line_offsets`] with [`row_major_strides`] against
+/// `data`.
+///
+/// # Arguments
+///
+/// * `data` - The cells of the grid in row-major order.
+/// * `shape` - The size of the grid along each axis.
+/// * `direction` - The step along each axis, each component being `-1`, `0` or `1`.
+///
+/// # Returns
+///
+/// A vector of vectors containing references to the elements of each line.
+///
+/// # Panics
+///
+/// Panics if `data` does not hold exactly as many cells as `shape` describes, or for any of the
+/// reasons [`line_offsets`] panics.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::ndim::lines;
+///
+/// let data = [1, 2, 3, 4, 5, 6];
+///
+/// // The columns of a 2x3 matrix.
+/// assert_eq!(lines(&data, &[2, 3], &[1, 0]), vec![
+///     vec![&1, &4],
+///     vec![&2, &5],
+///     vec![&3, &6],
+/// ]);
+/// ```
+pub fn lines<'a, T>(data: &'a [T], shape: &[usize], direction: &[isize]) -> Vec<Vec<&'a T>> {
+    assert_eq!(
+        data.len(),
+        shape.iter().product::<usize>(),
+        "data does not match the shape"
+    );
+
+    line_offsets(shape, &row_major_strides(shape), direction)
+        .into_iter()
+        .map(|line| line.into_iter().map(|offset| &data[offset]).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_two_dimensional_diagonals() {
+        let matrix = [[1, 2, 3], [4, 5, 6]];
+        let data: Vec<i32> = matrix.iter().flatten().copied().collect();
+
+        let mut expected = crate::diagonal_pos_pos(&matrix);
+        let mut actual = lines(&data, &[2, 3], &[1, 1]);
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+
+        let mut expected = crate::diagonal_pos_neg(&matrix);
+        let mut actual = lines(&data, &[2, 3], &[1, -1]);
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn every_cell_once() {
+        let shape = [3, 2, 4, 2];
+        let total: usize = shape.iter().product();
+        let strides = row_major_strides(&shape);
+
+        for direction in [[1, 0, 0, 0], [0, -1, 1, 0], [1, 1, -1, 1], [-1, -1, -1, -1]] {
+            let mut seen: Vec<usize> = line_offsets(&shape, &strides, &direction)
+                .into_iter()
+                .flatten()
+                .collect();
+            seen.sort();
+            assert_eq!(seen, (0..total).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn reversed_direction() {
+        let data = [1, 2, 3, 4];
+        assert_eq!(lines(&data, &[4], &[-1]), vec![vec![&4, &3, &2, &1]]);
+    }
+
+    #[test]
+    fn empty_shape() {
+        assert_eq!(
+            line_offsets(&[3, 0], &[0, 1], &[1, 1]),
+            Vec::<Vec<usize>>::new()
+        );
+        assert_eq!(
+            lines::<u8>(&[], &[0, 0, 0], &[0, 0, 1]),
+            Vec::<Vec<&u8>>::new()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_direction() {
+        line_offsets(&[2, 2], &[2, 1], &[0, 0]);
+    }
+}
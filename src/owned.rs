@@ -0,0 +1,220 @@
+use crate::{index, Direction};
+
+/// Consumes a matrix and moves its elements into its diagonals with positive slope.
+///
+/// This is the owning counterpart of [`diagonal_pos_pos`](crate::diagonal_pos_pos): the
+/// diagonals and their elements are in the same order, but the elements are moved instead of
+/// borrowed, so `T` does not need to implement [`Clone`].
+///
+/// # Arguments
+///
+/// * `matrix` - The matrix to consume, as a vector of rows of equal length.
+///
+/// # Returns
+///
+/// A `Vec<Vec<T>>` where each inner vector holds the elements of one diagonal.
+///
+/// # Panics
+///
+/// Panics if the rows differ in length.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::into_diagonal_pos_pos;
+///
+/// let matrix = vec![
+///     vec![String::from("a"), String::from("b")],
+///     vec![String::from("c"), String::from("d")],
+/// ];
+///
+/// assert_eq!(into_diagonal_pos_pos(matrix), vec![
+///     vec!["c"],
+///     vec!["a", "d"],
+///     vec!["b"],
+/// ]);
+/// ```
+pub fn into_diagonal_pos_pos<T>(matrix: Vec<Vec<T>>) -> Vec<Vec<T>> {
+    into_lines(matrix, Direction::DiagonalPosPos)
+}
+
+/// Consumes a matrix and moves its elements into its diagonals with positive and negative
+/// slope.
+///
+/// This is the owning counterpart of [`diagonal_pos_neg`](crate::diagonal_pos_neg): the
+/// diagonals and their elements are in the same order, but the elements are moved instead of
+/// borrowed, so `T` does not need to implement [`Clone`].
+///
+/// # Arguments
+///
+/// * `matrix` - The matrix to consume, as a vector of rows of equal length.
+///
+/// # Returns
+///
+/// A `Vec<Vec<T>>` where each inner vector holds the elements of one diagonal.
+///
+/// # Panics
+///
+/// Panics if the rows differ in length.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::into_diagonal_pos_neg;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ];
+///
+/// assert_eq!(into_diagonal_pos_neg(matrix), vec![
+///     vec![1],
+///     vec![2, 4],
+///     vec![3, 5],
+///     vec![6],
+/// ]);
+/// ```
+pub fn into_diagonal_pos_neg<T>(matrix: Vec<Vec<T>>) -> Vec<Vec<T>> {
+    into_lines(matrix, Direction::DiagonalPosNeg)
+}
+
+/// Consumes a matrix and returns its rows.
+///
+/// This is the owning counterpart of [`straight_x`](crate::straight_x). The rows already are
+/// the lines, so the matrix is only checked and handed back without moving any element.
+///
+/// # Arguments
+///
+/// * `matrix` - The matrix to consume, as a vector of rows of equal length.
+///
+/// # Returns
+///
+/// A `Vec<Vec<T>>` where each inner vector holds the elements of one row.
+///
+/// # Panics
+///
+/// Panics if the rows differ in length.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::into_straight_x;
+///
+/// let matrix = vec![vec![1, 2], vec![3, 4]];
+/// assert_eq!(into_straight_x(matrix), vec![vec![1, 2], vec![3, 4]]);
+/// ```
+pub fn into_straight_x<T>(matrix: Vec<Vec<T>>) -> Vec<Vec<T>> {
+    assert_rectangular(&matrix);
+    matrix
+}
+
+/// Consumes a matrix and moves its elements into its columns.
+///
+/// This is the owning counterpart of [`straight_y`](crate::straight_y).
+///
+/// # Arguments
+///
+/// * `matrix` - The matrix to consume, as a vector of rows of equal length.
+///
+/// # Returns
+///
+/// A `Vec<Vec<T>>` where each inner vector holds the elements of one column.
+///
+/// # Panics
+///
+/// Panics if the rows differ in length.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::into_straight_y;
+///
+/// let matrix = vec![vec![1, 2], vec![3, 4]];
+/// assert_eq!(into_straight_y(matrix), vec![vec![1, 3], vec![2, 4]]);
+/// ```
+pub fn into_straight_y<T>(matrix: Vec<Vec<T>>) -> Vec<Vec<T>> {
+    into_lines(matrix, Direction::StraightY)
+}
+
+/// Panics unless every row has the same length as the first one.
+fn assert_rectangular<T>(matrix: &[Vec<T>]) -> usize {
+    let (_, cols) = index::shape(matrix);
+    assert!(
+        matrix.iter().all(|row| row.len() == cols),
+        "all rows must have the same length"
+    );
+    cols
+}
+
+/// Moves every element into its line in `direction`.
+///
+/// Walking the matrix row by row visits the cells of every line in traversal order, so the
+/// elements can simply be pushed onto their line.
+fn into_lines<T>(matrix: Vec<Vec<T>>, direction: Direction) -> Vec<Vec<T>> {
+    let rows = matrix.len();
+    let cols = assert_rectangular(&matrix);
+
+    let mut lines: Vec<Vec<T>> = (0..direction.line_count(rows, cols))
+        .map(|line| Vec::with_capacity(direction.line_start(rows, cols, line).1))
+        .collect();
+    for (row, elements) in matrix.into_iter().enumerate() {
+        for (col, element) in elements.into_iter().enumerate() {
+            lines[direction.line_of(rows, (row, col))].push(element);
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A coordinate that can be neither copied nor cloned.
+    #[derive(Debug, PartialEq)]
+    struct Cell(usize, usize);
+
+    fn matrix(rows: usize, cols: usize) -> Vec<Vec<Cell>> {
+        (0..rows)
+            .map(|r| (0..cols).map(|c| Cell(r, c)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn matches_borrowing_traversals() {
+        type Traversal = fn(Vec<Vec<Cell>>) -> Vec<Vec<Cell>>;
+        let traversals: [(Direction, Traversal); 4] = [
+            (Direction::DiagonalPosPos, into_diagonal_pos_pos),
+            (Direction::DiagonalPosNeg, into_diagonal_pos_neg),
+            (Direction::StraightX, into_straight_x),
+            (Direction::StraightY, into_straight_y),
+        ];
+
+        for rows in 0..4 {
+            for cols in 1..4 {
+                let borrowed = matrix(rows, cols);
+                for (direction, traversal) in traversals {
+                    let expected: Vec<Vec<&Cell>> = crate::lines_iter(&borrowed, direction)
+                        .map(Iterator::collect)
+                        .collect();
+                    let owned = traversal(matrix(rows, cols));
+                    let owned: Vec<Vec<&Cell>> =
+                        owned.iter().map(|line| line.iter().collect()).collect();
+                    assert_eq!(owned, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn capacity_is_exact() {
+        for line in into_diagonal_pos_neg(matrix(3, 5)) {
+            assert_eq!(line.capacity(), line.len());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn ragged() {
+        into_diagonal_pos_pos(vec![vec![1, 2], vec![3]]);
+    }
+}
@@ -0,0 +1,258 @@
+//! # Pretty-Printing Module
+//!
+//! This module renders matrices and their decompositions as text, for documentation, teaching
+//! and debugging. [`annotate`] prints the matrix itself with every cell labeled by the index of
+//! the line it belongs to, and [`decomposition`] prints the lines one per row, the way the
+//! traversal functions return them.
+//!
+//! Both return lightweight wrappers that implement [`Display`], so they can be used with
+//! `println!`, `format!` or `to_string`.
+//!
+//! ### Example
+//!
+//! ```rust
+//! use diagonal::pretty::{annotate, decomposition};
+//! use diagonal::Direction;
+//!
+//! let matrix = vec![
+//!     vec![1, 2, 3],
+//!     vec![4, 5, 6],
+//! ];
+//!
+//! assert_eq!(
+//!     annotate(&matrix, Direction::DiagonalPosNeg).to_string(),
+//!     "1(0)  2(1)  3(2)\n\
+//!      4(1)  5(2)  6(3)",
+//! );
+//! assert_eq!(
+//!     decomposition(&matrix, Direction::DiagonalPosNeg).to_string(),
+//!     "0: 1\n\
+//!      1: 2 4\n\
+//!      2: 3 5\n\
+//!      3: 6",
+//! );
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+use std::marker::PhantomData;
+
+use crate::{index, lines_iter, Direction};
+
+/// A matrix whose cells are labeled with the index of their line, created by [`annotate`].
+pub struct Annotated<'a, Row, T> {
+    matrix: &'a [Row],
+    direction: Direction,
+    element: PhantomData<&'a T>,
+}
+
+/// The lines of a matrix printed one per row, created by [`decomposition`].
+pub struct Decomposition<'a, Row, T> {
+    matrix: &'a [Row],
+    direction: Direction,
+    element: PhantomData<&'a T>,
+}
+
+/// Renders a matrix with every cell labeled by the index of its line in `direction`.
+///
+/// Each cell is printed as `value(label)`, where the label is the index the cell's line has in
+/// the output of the function named by `direction`. Columns are right-aligned and rows are
+/// separated by newlines, without a trailing one.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `direction` - The traversal whose lines are used as labels.
+///
+/// # Returns
+///
+/// An [`Annotated`] value implementing [`Display`].
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::pretty::annotate;
+/// use diagonal::Direction;
+///
+/// let matrix = [[1, 2], [30, 4]];
+///
+/// assert_eq!(
+///     annotate(&matrix, Direction::DiagonalPosPos).to_string(),
+///     " 1(1)  2(2)\n\
+///      30(0)  4(1)",
+/// );
+/// ```
+pub fn annotate<'a, Matrix: AsRef<[Row]> + ?Sized, Row: AsRef<[T]>, T>(
+    matrix: &'a Matrix,
+    direction: Direction,
+) -> Annotated<'a, Row, T> {
+    Annotated {
+        matrix: matrix.as_ref(),
+        direction,
+        element: PhantomData,
+    }
+}
+
+/// Renders the lines of a matrix in `direction`, one per row.
+///
+/// Each row is printed as `index: elements`, with the elements separated by spaces and the
+/// indices right-aligned. Rows are separated by newlines, without a trailing one.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `direction` - The traversal to print.
+///
+/// # Returns
+///
+/// A [`Decomposition`] value implementing [`Display`].
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::pretty::decomposition;
+/// use diagonal::Direction;
+///
+/// let matrix = [['a', 'b'], ['c', 'd']];
+///
+/// assert_eq!(
+///     decomposition(&matrix, Direction::StraightY).to_string(),
+///     "0: a c\n\
+///      1: b d",
+/// );
+/// ```
+pub fn decomposition<'a, Matrix: AsRef<[Row]> + ?Sized, Row: AsRef<[T]>, T>(
+    matrix: &'a Matrix,
+    direction: Direction,
+) -> Decomposition<'a, Row, T> {
+    Decomposition {
+        matrix: matrix.as_ref(),
+        direction,
+        element: PhantomData,
+    }
+}
+
+impl<Row: AsRef<[T]>, T: Display> Display for Annotated<'_, Row, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (rows, cols) = index::shape(self.matrix);
+        let cells: Vec<Vec<String>> = self
+            .matrix
+            .iter()
+            .enumerate()
+            .map(|(x, row)| {
+                row.as_ref()[..cols]
+                    .iter()
+                    .enumerate()
+                    .map(|(y, value)| {
+                        let label = self.direction.line_of(rows, (x, y));
+                        format!("{value}({label})")
+                    })
+                    .collect()
+            })
+            .collect();
+        let widths: Vec<usize> = (0..cols)
+            .map(|y| {
+                cells
+                    .iter()
+                    .map(|row| row[y].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        for (x, row) in cells.iter().enumerate() {
+            if x > 0 {
+                writeln!(f)?;
+            }
+            for (y, cell) in row.iter().enumerate() {
+                let separator = if y > 0 { "  " } else { "" };
+                write!(f, "{separator}{cell:>width$}", width = widths[y])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<Row: AsRef<[T]>, T: Display> Display for Decomposition<'_, Row, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let lines = lines_iter(self.matrix, self.direction);
+        let width = lines.len().saturating_sub(1).to_string().len();
+
+        for (index, line) in lines.enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{index:>width$}:")?;
+            for value in line {
+                write!(f, " {value}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_match_traversals() {
+        let matrix = [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]];
+        for direction in [
+            Direction::DiagonalPosPos,
+            Direction::DiagonalPosNeg,
+            Direction::StraightX,
+            Direction::StraightY,
+        ] {
+            for (index, line) in lines_iter(&matrix, direction).enumerate() {
+                for value in line {
+                    let (x, y) = (((value - 1) / 4) as usize, ((value - 1) % 4) as usize);
+                    assert_eq!(direction.line_of(3, (x, y)), index);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn aligned_non_ascii_columns() {
+        // Padding counts characters, so multi-byte cells take no extra space.
+        let matrix = [["日本", "a"], ["x", "b"]];
+        assert_eq!(
+            annotate(&matrix, Direction::StraightY).to_string(),
+            "日本(0)  a(1)\n x(0)  b(1)"
+        );
+    }
+
+    #[test]
+    fn aligned_columns() {
+        let matrix = [[100, 2], [3, 4]];
+        assert_eq!(
+            annotate(&matrix, Direction::StraightX).to_string(),
+            "100(0)  2(0)\n  3(1)  4(1)"
+        );
+    }
+
+    #[test]
+    fn wide_indices() {
+        let matrix = [[0; 11]];
+        let text = decomposition(&matrix, Direction::StraightY).to_string();
+        assert!(text.starts_with(" 0: 0\n 1: 0\n"));
+        assert!(text.ends_with("\n10: 0"));
+    }
+
+    #[test]
+    fn empty() {
+        let matrix: Vec<Vec<i32>> = vec![];
+        assert_eq!(annotate(&matrix, Direction::DiagonalPosPos).to_string(), "");
+        assert_eq!(
+            decomposition(&matrix, Direction::DiagonalPosPos).to_string(),
+            ""
+        );
+        let matrix: Vec<Vec<i32>> = vec![vec![], vec![]];
+        assert_eq!(
+            decomposition(&matrix, Direction::StraightX).to_string(),
+            "0:\n1:"
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use crate::index;
+
+/// Walks from a cell in a fixed direction, wrapping around the edges of the matrix as if it were
+/// a torus.
+///
+/// Leaving the matrix on one side re-enters it on the opposite side, so the ray only ends after
+/// `max_len` cells, or just before it would return to `start`; every cell is visited at most once.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `start` - The `(row, column)` coordinate of the first cell of the ray.
+/// * `direction` - The `(row, column)` step between consecutive cells, such as `(1, 1)` for a
+///   diagonal heading down and to the right or `(0, -1)` for heading left. Steps longer than one
+///   cell are allowed.
+/// * `max_len` - The maximum number of cells in the ray.
+///
+/// # Returns
+///
+/// A vector of references to the elements along the ray, starting with the element at `start`.
+///
+/// # Panics
+///
+/// Panics if `start` lies outside of the matrix, or if `direction` is `(0, 0)`.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::ray_wrapping;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ];
+///
+/// assert_eq!(ray_wrapping(&matrix, (1, 2), (0, 1), 4), vec![&6, &4, &5]);
+/// assert_eq!(ray_wrapping(&matrix, (0, 2), (1, 1), 4), vec![&3, &4, &2, &6]);
+/// assert_eq!(ray_wrapping(&matrix, (0, 0), (-1, -1), 2), vec![&1, &6]);
+/// ```
+pub fn ray_wrapping<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+    start: (usize, usize),
+    direction: (isize, isize),
+    max_len: usize,
+) -> Vec<&'a T> {
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    assert!(
+        start.0 < rows && start.1 < cols,
+        "start must lie inside of the matrix"
+    );
+    assert!(direction != (0, 0), "direction must not be zero");
+
+    let step = (
+        index::wrap(direction.0, rows),
+        index::wrap(direction.1, cols),
+    );
+
+    let mut result = Vec::new();
+    let (mut x, mut y) = start;
+    while result.len() < max_len {
+        result.push(&matrix[x].as_ref()[y]);
+        (x, y) = (
+            index::advance(x, step.0, rows),
+            index::advance(y, step.1, cols),
+        );
+        if (x, y) == start {
+            break;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix() -> Vec<Vec<(usize, usize)>> {
+        (0..4).map(|r| (0..6).map(|c| (r, c)).collect()).collect()
+    }
+
+    #[test]
+    fn wraps_in_every_direction() {
+        let matrix = matrix();
+        for dx in -2..=2isize {
+            for dy in -2..=2isize {
+                if (dx, dy) == (0, 0) {
+                    continue;
+                }
+                let ray = ray_wrapping(&matrix, (1, 3), (dx, dy), 10);
+                for (i, &&(x, y)) in ray.iter().enumerate() {
+                    let i = i as isize;
+                    assert_eq!(x as isize, (1 + i * dx).rem_euclid(4));
+                    assert_eq!(y as isize, (3 + i * dy).rem_euclid(6));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn stops_before_revisiting_start() {
+        let matrix = matrix();
+        assert_eq!(ray_wrapping(&matrix, (2, 0), (0, 1), 100).len(), 6);
+        assert_eq!(ray_wrapping(&matrix, (2, 0), (1, 0), 100).len(), 4);
+        // The orbit of a diagonal step has the least common multiple of both sides as length.
+        assert_eq!(ray_wrapping(&matrix, (0, 0), (1, 1), 100).len(), 12);
+        assert_eq!(ray_wrapping(&matrix, (0, 0), (2, 3), 100).len(), 2);
+        assert_eq!(ray_wrapping(&matrix, (0, 0), (4, 6), 100), vec![&(0, 0)]);
+    }
+
+    #[test]
+    fn max_len() {
+        let matrix = matrix();
+        assert!(ray_wrapping(&matrix, (0, 0), (1, -1), 0).is_empty());
+        assert_eq!(ray_wrapping(&matrix, (0, 0), (1, -1), 3).len(), 3);
+        assert_eq!(
+            ray_wrapping(&matrix, (0, 0), (isize::MIN, isize::MAX), 2),
+            vec![&(0, 0), &(0, 1)]
+        );
+    }
+
+    #[test]
+    fn huge_dimensions() {
+        // Zero-sized elements allow rows longer than `isize::MAX`.
+        let wide = [vec![(); usize::MAX]];
+        assert_eq!(ray_wrapping(&wide, (0, usize::MAX - 1), (0, 2), 3).len(), 3);
+        assert_eq!(ray_wrapping(&wide, (0, 1), (0, -1), 3).len(), 3);
+        assert_eq!(ray_wrapping(&wide, (0, 0), (1, 0), 3).len(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn start_outside() {
+        ray_wrapping(&matrix(), (4, 0), (1, 0), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_direction() {
+        ray_wrapping(&matrix(), (0, 0), (0, 0), 1);
+    }
+}
@@ -0,0 +1,1479 @@
+use std::iter::Sum;
+use std::ops::{Add, Mul, Sub};
+
+use crate::{index, lines_iter, Direction};
+
+/// Sums every diagonal with positive slope (see [`diagonal_pos_pos`](crate::diagonal_pos_pos)).
+///
+/// The elements are summed while walking the matrix, so no intermediate vectors of references
+/// are built.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<T>` holding the sum of each diagonal, in the same order as
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos) returns the diagonals.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_sums_pos_pos;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_sums_pos_pos(&matrix);
+/// assert_eq!(result, vec![7, 12, 15, 8, 3]);
+/// ```
+pub fn diagonal_sums_pos_pos<Matrix, Row, T>(matrix: &Matrix) -> Vec<T>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: Copy + Sum,
+{
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+
+    index::pos_pos_cells(rows, cols)
+        .map(|diagonal| diagonal.map(|(x, y)| matrix[x].as_ref()[y]).sum())
+        .collect()
+}
+
+/// Sums every diagonal with positive and negative slope (see
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg)).
+///
+/// The elements are summed while walking the matrix, so no intermediate vectors of references
+/// are built.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<T>` holding the sum of each diagonal, in the same order as
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg) returns the diagonals.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_sums_pos_neg;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_sums_pos_neg(&matrix);
+/// assert_eq!(result, vec![1, 6, 15, 14, 9]);
+/// ```
+pub fn diagonal_sums_pos_neg<Matrix, Row, T>(matrix: &Matrix) -> Vec<T>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: Copy + Sum,
+{
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+
+    index::pos_neg_cells(rows, cols)
+        .map(|diagonal| diagonal.map(|(x, y)| matrix[x].as_ref()[y]).sum())
+        .collect()
+}
+
+/// Finds the smallest element of every diagonal with positive slope (see
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos)).
+///
+/// If a diagonal contains several equal extremes, the one that comes first along the
+/// diagonal is returned. Elements that cannot be compared (such as `NaN`) are never selected
+/// over an earlier element.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<&T>` holding a reference to the smallest element of each diagonal, in the same order as
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos) returns the diagonals.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_min_pos_pos;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_min_pos_pos(&matrix);
+/// assert_eq!(result, vec![&7, &4, &1, &2, &3]);
+/// ```
+pub fn diagonal_min_pos_pos<'a, Matrix, Row, T>(matrix: &'a Matrix) -> Vec<&'a T>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    T: PartialOrd,
+{
+    let matrix = matrix.as_ref();
+    diagonal_argmin_pos_pos(matrix)
+        .into_iter()
+        .map(|(x, y)| &matrix[x].as_ref()[y])
+        .collect()
+}
+
+/// Finds the coordinate of the smallest element of every diagonal with positive slope (see
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos)).
+///
+/// Ties and incomparable elements are handled the same way as in
+/// [`diagonal_min_pos_pos`].
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<(usize, usize)>` holding the `(row, column)` coordinate of the smallest element of each
+/// diagonal, in the same order as [`diagonal_pos_pos`](crate::diagonal_pos_pos) returns the
+/// diagonals.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_argmin_pos_pos;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_argmin_pos_pos(&matrix);
+/// assert_eq!(result, vec![(2, 0), (1, 0), (0, 0), (0, 1), (0, 2)]);
+/// ```
+pub fn diagonal_argmin_pos_pos<Matrix, Row, T>(matrix: &Matrix) -> Vec<(usize, usize)>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: PartialOrd,
+{
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    extremes(
+        matrix,
+        index::pos_pos_cells(rows, cols),
+        |candidate, best| candidate < best,
+    )
+}
+
+/// Finds the largest element of every diagonal with positive slope (see
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos)).
+///
+/// If a diagonal contains several equal extremes, the one that comes first along the
+/// diagonal is returned. Elements that cannot be compared (such as `NaN`) are never selected
+/// over an earlier element.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<&T>` holding a reference to the largest element of each diagonal, in the same order as
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos) returns the diagonals.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_max_pos_pos;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_max_pos_pos(&matrix);
+/// assert_eq!(result, vec![&7, &8, &9, &6, &3]);
+/// ```
+pub fn diagonal_max_pos_pos<'a, Matrix, Row, T>(matrix: &'a Matrix) -> Vec<&'a T>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    T: PartialOrd,
+{
+    let matrix = matrix.as_ref();
+    diagonal_argmax_pos_pos(matrix)
+        .into_iter()
+        .map(|(x, y)| &matrix[x].as_ref()[y])
+        .collect()
+}
+
+/// Finds the coordinate of the largest element of every diagonal with positive slope (see
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos)).
+///
+/// Ties and incomparable elements are handled the same way as in
+/// [`diagonal_max_pos_pos`].
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<(usize, usize)>` holding the `(row, column)` coordinate of the largest element of each
+/// diagonal, in the same order as [`diagonal_pos_pos`](crate::diagonal_pos_pos) returns the
+/// diagonals.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_argmax_pos_pos;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_argmax_pos_pos(&matrix);
+/// assert_eq!(result, vec![(2, 0), (2, 1), (2, 2), (1, 2), (0, 2)]);
+/// ```
+pub fn diagonal_argmax_pos_pos<Matrix, Row, T>(matrix: &Matrix) -> Vec<(usize, usize)>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: PartialOrd,
+{
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    extremes(
+        matrix,
+        index::pos_pos_cells(rows, cols),
+        |candidate, best| candidate > best,
+    )
+}
+
+/// Finds the smallest element of every diagonal with positive and negative slope (see
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg)).
+///
+/// If a diagonal contains several equal extremes, the one that comes first along the
+/// diagonal is returned. Elements that cannot be compared (such as `NaN`) are never selected
+/// over an earlier element.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<&T>` holding a reference to the smallest element of each diagonal, in the same order as
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg) returns the diagonals.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_min_pos_neg;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_min_pos_neg(&matrix);
+/// assert_eq!(result, vec![&1, &2, &3, &6, &9]);
+/// ```
+pub fn diagonal_min_pos_neg<'a, Matrix, Row, T>(matrix: &'a Matrix) -> Vec<&'a T>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    T: PartialOrd,
+{
+    let matrix = matrix.as_ref();
+    diagonal_argmin_pos_neg(matrix)
+        .into_iter()
+        .map(|(x, y)| &matrix[x].as_ref()[y])
+        .collect()
+}
+
+/// Finds the coordinate of the smallest element of every diagonal with positive and negative slope
+/// (see [`diagonal_pos_neg`](crate::diagonal_pos_neg)).
+///
+/// Ties and incomparable elements are handled the same way as in
+/// [`diagonal_min_pos_neg`].
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<(usize, usize)>` holding the `(row, column)` coordinate of the smallest element of each
+/// diagonal, in the same order as [`diagonal_pos_neg`](crate::diagonal_pos_neg) returns the
+/// diagonals.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_argmin_pos_neg;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_argmin_pos_neg(&matrix);
+/// assert_eq!(result, vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)]);
+/// ```
+pub fn diagonal_argmin_pos_neg<Matrix, Row, T>(matrix: &Matrix) -> Vec<(usize, usize)>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: PartialOrd,
+{
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    extremes(
+        matrix,
+        index::pos_neg_cells(rows, cols),
+        |candidate, best| candidate < best,
+    )
+}
+
+/// Finds the largest element of every diagonal with positive and negative slope (see
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg)).
+///
+/// If a diagonal contains several equal extremes, the one that comes first along the
+/// diagonal is returned. Elements that cannot be compared (such as `NaN`) are never selected
+/// over an earlier element.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<&T>` holding a reference to the largest element of each diagonal, in the same order as
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg) returns the diagonals.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_max_pos_neg;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_max_pos_neg(&matrix);
+/// assert_eq!(result, vec![&1, &4, &7, &8, &9]);
+/// ```
+pub fn diagonal_max_pos_neg<'a, Matrix, Row, T>(matrix: &'a Matrix) -> Vec<&'a T>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    T: PartialOrd,
+{
+    let matrix = matrix.as_ref();
+    diagonal_argmax_pos_neg(matrix)
+        .into_iter()
+        .map(|(x, y)| &matrix[x].as_ref()[y])
+        .collect()
+}
+
+/// Finds the coordinate of the largest element of every diagonal with positive and negative slope
+/// (see [`diagonal_pos_neg`](crate::diagonal_pos_neg)).
+///
+/// Ties and incomparable elements are handled the same way as in
+/// [`diagonal_max_pos_neg`].
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<(usize, usize)>` holding the `(row, column)` coordinate of the largest element of each
+/// diagonal, in the same order as [`diagonal_pos_neg`](crate::diagonal_pos_neg) returns the
+/// diagonals.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_argmax_pos_neg;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_argmax_pos_neg(&matrix);
+/// assert_eq!(result, vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)]);
+/// ```
+pub fn diagonal_argmax_pos_neg<Matrix, Row, T>(matrix: &Matrix) -> Vec<(usize, usize)>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: PartialOrd,
+{
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    extremes(
+        matrix,
+        index::pos_neg_cells(rows, cols),
+        |candidate, best| candidate > best,
+    )
+}
+
+/// Computes the absolute difference between the sums of the main diagonal and the anti-diagonal
+/// of a square matrix.
+///
+/// The main diagonal runs from the top-left to the bottom-right corner, the anti-diagonal from
+/// the top-right to the bottom-left corner.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a square matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// The absolute difference between both sums, or the sum of an empty iterator for an empty
+/// matrix.
+///
+/// # Panics
+///
+/// Panics if the matrix is not square.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_difference;
+///
+/// let matrix = vec![
+///     vec![11, 2, 4],
+///     vec![4, 5, 6],
+///     vec![10, 8, -12],
+/// ];
+///
+/// // |(11 + 5 - 12) - (4 + 5 + 10)| = 15
+/// assert_eq!(diagonal_difference(&matrix), 15);
+/// ```
+pub fn diagonal_difference<Matrix, Row, T>(matrix: &Matrix) -> T
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: Copy + Sum + Sub<Output = T> + PartialOrd,
+{
+    let matrix = matrix.as_ref();
+    let n = matrix.len();
+    assert!(
+        matrix.iter().all(|row| row.as_ref().len() == n),
+        "diagonal_difference requires a square matrix"
+    );
+
+    let main: T = (0..n).map(|i| matrix[i].as_ref()[i]).sum();
+    let anti: T = (0..n).map(|i| matrix[i].as_ref()[n - 1 - i]).sum();
+
+    if main > anti {
+        main - anti
+    } else {
+        anti - main
+    }
+}
+
+/// Computes the trace of a matrix, the sum of its main diagonal.
+///
+/// The main diagonal runs from the top-left corner down and to the right. For rectangular
+/// matrices it ends at the last row or column, whichever comes first, so it holds
+/// `min(rows, columns)` elements.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// The sum of the main diagonal, or the sum of an empty iterator for an empty matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::trace;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ];
+///
+/// // 1 + 5
+/// assert_eq!(trace(&matrix), 6);
+/// ```
+pub fn trace<Matrix, Row, T>(matrix: &Matrix) -> T
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: Copy + Sum,
+{
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    (0..rows.min(cols)).map(|i| matrix[i].as_ref()[i]).sum()
+}
+
+/// Computes the anti-trace of a matrix, the sum of its anti-diagonal.
+///
+/// The anti-diagonal runs from the top-right corner down and to the left. For rectangular
+/// matrices it ends at the last row or the first column, whichever comes first, so it holds
+/// `min(rows, columns)` elements.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// The sum of the anti-diagonal, or the sum of an empty iterator for an empty matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::anti_trace;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ];
+///
+/// // 3 + 5
+/// assert_eq!(anti_trace(&matrix), 8);
+/// ```
+pub fn anti_trace<Matrix, Row, T>(matrix: &Matrix) -> T
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: Copy + Sum,
+{
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    (0..rows.min(cols))
+        .map(|i| matrix[i].as_ref()[cols - 1 - i])
+        .sum()
+}
+
+/// Folds every line of a matrix in the given direction with a binary operation.
+///
+/// Each line starts from a clone of `init` and folds its elements in traversal order, so sums,
+/// extrema or bitwise combinations of every line are computed in a single pass without building
+/// the lines.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `direction` - The traversal to perform.
+/// * `init` - The initial value of every line's accumulator.
+/// * `f` - The operation combining an accumulator with the next element of the line.
+///
+/// # Returns
+///
+/// A `Vec<U>` holding the folded value of each line, in the same order as the function named
+/// by `direction` returns the lines.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{reduce_lines, Direction};
+///
+/// let matrix = vec![
+///     vec![0b001, 0b010, 0b100],
+///     vec![0b100, 0b001, 0b010],
+/// ];
+///
+/// let result = reduce_lines(&matrix, Direction::DiagonalPosNeg, 0, |acc, &bits| acc | bits);
+/// assert_eq!(result, vec![0b001, 0b110, 0b101, 0b010]);
+/// ```
+pub fn reduce_lines<'a, Matrix, Row, T, U, F>(
+    matrix: &'a Matrix,
+    direction: Direction,
+    init: U,
+    mut f: F,
+) -> Vec<U>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    T: 'a,
+    U: Clone,
+    F: FnMut(U, &'a T) -> U,
+{
+    lines_iter(matrix, direction)
+        .map(|line| line.fold(init.clone(), &mut f))
+        .collect()
+}
+
+/// Computes the running sums along every diagonal with positive slope (see
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos)).
+///
+/// The `i`-th value of each returned diagonal is the sum of the first `i + 1` elements of that
+/// diagonal, in [`diagonal_pos_pos`](crate::diagonal_pos_pos) order.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<Vec<T>>` where each inner vector holds the prefix sums of one diagonal.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_prefix_sums_pos_pos;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_prefix_sums_pos_pos(&matrix);
+/// assert_eq!(result, vec![vec![7], vec![4, 12], vec![1, 6, 15], vec![2, 8], vec![3]]);
+/// ```
+pub fn diagonal_prefix_sums_pos_pos<Matrix, Row, T>(matrix: &Matrix) -> Vec<Vec<T>>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: Copy + Add<Output = T>,
+{
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+
+    index::pos_pos_cells(rows, cols)
+        .map(|diagonal| running_sums(diagonal.map(|(x, y)| matrix[x].as_ref()[y])).collect())
+        .collect()
+}
+
+/// Computes the running sums along every diagonal with positive slope (see
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos)) and writes them into a matrix of the same shape.
+///
+/// Each cell of `output` receives the sum of all elements on its diagonal up to and including
+/// the cell itself, in [`diagonal_pos_pos`](crate::diagonal_pos_pos) order.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `output` - A mutable reference to a matrix with the same shape as `matrix`.
+///
+/// # Panics
+///
+/// Panics if `output` does not have the same shape as `matrix`.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_prefix_sums_into_pos_pos;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let mut output = vec![vec![0; 3]; 3];
+/// diagonal_prefix_sums_into_pos_pos(&matrix, &mut output);
+/// assert_eq!(output, vec![
+///     vec![1, 2, 3],
+///     vec![4, 6, 8],
+///     vec![7, 12, 15],
+/// ]);
+/// ```
+pub fn diagonal_prefix_sums_into_pos_pos<Matrix, Row, Output, OutputRow, T>(
+    matrix: &Matrix,
+    output: &mut Output,
+) where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    Output: AsMut<[OutputRow]> + ?Sized,
+    OutputRow: AsMut<[T]>,
+    T: Copy + Add<Output = T>,
+{
+    let matrix = matrix.as_ref();
+    let output = output.as_mut();
+    let (rows, cols) = index::shape(matrix);
+    assert_same_shape(rows, cols, output);
+
+    for diagonal in index::pos_pos_cells(rows, cols) {
+        let cells: Vec<_> = diagonal.collect();
+        let sums = running_sums(cells.iter().map(|&(x, y)| matrix[x].as_ref()[y]));
+        for (&(x, y), sum) in cells.iter().zip(sums) {
+            output[x].as_mut()[y] = sum;
+        }
+    }
+}
+
+/// Computes the running sums along every diagonal with positive and negative slope (see
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg)).
+///
+/// The `i`-th value of each returned diagonal is the sum of the first `i + 1` elements of that
+/// diagonal, in [`diagonal_pos_neg`](crate::diagonal_pos_neg) order.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<Vec<T>>` where each inner vector holds the prefix sums of one diagonal.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_prefix_sums_pos_neg;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_prefix_sums_pos_neg(&matrix);
+/// assert_eq!(result, vec![vec![1], vec![2, 6], vec![3, 8, 15], vec![6, 14], vec![9]]);
+/// ```
+pub fn diagonal_prefix_sums_pos_neg<Matrix, Row, T>(matrix: &Matrix) -> Vec<Vec<T>>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: Copy + Add<Output = T>,
+{
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+
+    index::pos_neg_cells(rows, cols)
+        .map(|diagonal| running_sums(diagonal.map(|(x, y)| matrix[x].as_ref()[y])).collect())
+        .collect()
+}
+
+/// Computes the running sums along every diagonal with positive and negative slope (see
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg)) and writes them into a matrix of the same shape.
+///
+/// Each cell of `output` receives the sum of all elements on its diagonal up to and including
+/// the cell itself, in [`diagonal_pos_neg`](crate::diagonal_pos_neg) order.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `output` - A mutable reference to a matrix with the same shape as `matrix`.
+///
+/// # Panics
+///
+/// Panics if `output` does not have the same shape as `matrix`.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_prefix_sums_into_pos_neg;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let mut output = vec![vec![0; 3]; 3];
+/// diagonal_prefix_sums_into_pos_neg(&matrix, &mut output);
+/// assert_eq!(output, vec![
+///     vec![1, 2, 3],
+///     vec![6, 8, 6],
+///     vec![15, 14, 9],
+/// ]);
+/// ```
+pub fn diagonal_prefix_sums_into_pos_neg<Matrix, Row, Output, OutputRow, T>(
+    matrix: &Matrix,
+    output: &mut Output,
+) where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    Output: AsMut<[OutputRow]> + ?Sized,
+    OutputRow: AsMut<[T]>,
+    T: Copy + Add<Output = T>,
+{
+    let matrix = matrix.as_ref();
+    let output = output.as_mut();
+    let (rows, cols) = index::shape(matrix);
+    assert_same_shape(rows, cols, output);
+
+    for diagonal in index::pos_neg_cells(rows, cols) {
+        let cells: Vec<_> = diagonal.collect();
+        let sums = running_sums(cells.iter().map(|&(x, y)| matrix[x].as_ref()[y]));
+        for (&(x, y), sum) in cells.iter().zip(sums) {
+            output[x].as_mut()[y] = sum;
+        }
+    }
+}
+
+/// Convolves every diagonal with positive slope (see [`diagonal_pos_pos`](crate::diagonal_pos_pos))
+/// with a one-dimensional kernel and writes the results into a matrix of the same shape.
+///
+/// Each diagonal is treated as a signal in [`diagonal_pos_pos`](crate::diagonal_pos_pos) order. The
+/// kernel's element at index `(kernel.len() - 1) / 2` is aligned with the output cell, and
+/// values beyond either end of the diagonal count as zero, so every diagonal keeps its length
+/// however short it is, like the `"same"` mode of `numpy.convolve`. As in any convolution, the
+/// kernel is applied reversed: a kernel of `[0, 0, 1]` reads the previous element.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `kernel` - The kernel to convolve the diagonals with.
+/// * `output` - A mutable reference to a matrix with the same shape as `matrix`.
+///
+/// # Panics
+///
+/// Panics if `kernel` is empty or if `output` does not have the same shape as `matrix`.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::convolve_diagonals_pos_pos;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// // Sum every cell with its neighbours on the same diagonal.
+/// let mut output = vec![vec![0; 3]; 3];
+/// convolve_diagonals_pos_pos(&matrix, &[1, 1, 1], &mut output);
+/// assert_eq!(output, vec![
+///     vec![6, 8, 3],
+///     vec![12, 15, 8],
+///     vec![7, 12, 14],
+/// ]);
+/// ```
+pub fn convolve_diagonals_pos_pos<Matrix, Row, Output, OutputRow, T>(
+    matrix: &Matrix,
+    kernel: &[T],
+    output: &mut Output,
+) where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    Output: AsMut<[OutputRow]> + ?Sized,
+    OutputRow: AsMut<[T]>,
+    T: Copy + Sum + Mul<Output = T>,
+{
+    let matrix = matrix.as_ref();
+    let output = output.as_mut();
+    let (rows, cols) = index::shape(matrix);
+    assert_same_shape(rows, cols, output);
+    assert!(!kernel.is_empty(), "kernel must not be empty");
+
+    for diagonal in index::pos_pos_cells(rows, cols) {
+        let cells: Vec<_> = diagonal.collect();
+        let values: Vec<T> = cells.iter().map(|&(x, y)| matrix[x].as_ref()[y]).collect();
+        for (i, &(x, y)) in cells.iter().enumerate() {
+            output[x].as_mut()[y] = convolve_at(&values, kernel, i);
+        }
+    }
+}
+
+/// Convolves every diagonal with positive and negative slope (see
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg)) with a one-dimensional kernel and writes the
+/// results into a matrix of the same shape.
+///
+/// Each diagonal is treated as a signal in [`diagonal_pos_neg`](crate::diagonal_pos_neg) order. The
+/// kernel's element at index `(kernel.len() - 1) / 2` is aligned with the output cell, and
+/// values beyond either end of the diagonal count as zero, so every diagonal keeps its length
+/// however short it is, like the `"same"` mode of `numpy.convolve`. As in any convolution, the
+/// kernel is applied reversed: a kernel of `[0, 0, 1]` reads the previous element.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `kernel` - The kernel to convolve the diagonals with.
+/// * `output` - A mutable reference to a matrix with the same shape as `matrix`.
+///
+/// # Panics
+///
+/// Panics if `kernel` is empty or if `output` does not have the same shape as `matrix`.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::convolve_diagonals_pos_neg;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ];
+///
+/// // Difference of every cell to the previous one on its anti-diagonal.
+/// let mut output = vec![vec![0; 3]; 2];
+/// convolve_diagonals_pos_neg(&matrix, &[1, -1], &mut output);
+/// assert_eq!(output, vec![
+///     vec![1, 2, 3],
+///     vec![2, 2, 6],
+/// ]);
+/// ```
+pub fn convolve_diagonals_pos_neg<Matrix, Row, Output, OutputRow, T>(
+    matrix: &Matrix,
+    kernel: &[T],
+    output: &mut Output,
+) where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    Output: AsMut<[OutputRow]> + ?Sized,
+    OutputRow: AsMut<[T]>,
+    T: Copy + Sum + Mul<Output = T>,
+{
+    let matrix = matrix.as_ref();
+    let output = output.as_mut();
+    let (rows, cols) = index::shape(matrix);
+    assert_same_shape(rows, cols, output);
+    assert!(!kernel.is_empty(), "kernel must not be empty");
+
+    for diagonal in index::pos_neg_cells(rows, cols) {
+        let cells: Vec<_> = diagonal.collect();
+        let values: Vec<T> = cells.iter().map(|&(x, y)| matrix[x].as_ref()[y]).collect();
+        for (i, &(x, y)) in cells.iter().enumerate() {
+            output[x].as_mut()[y] = convolve_at(&values, kernel, i);
+        }
+    }
+}
+
+/// Value of the `same`-sized convolution of `values` and `kernel` at index `i`.
+fn convolve_at<T: Copy + Sum + Mul<Output = T>>(values: &[T], kernel: &[T], i: usize) -> T {
+    let center = (kernel.len() - 1) / 2;
+    kernel
+        .iter()
+        .enumerate()
+        .filter_map(|(j, &weight)| {
+            (i + center)
+                .checked_sub(j)
+                .and_then(|n| values.get(n))
+                .map(|&value| weight * value)
+        })
+        .sum()
+}
+
+/// Reduces every window of `k` consecutive elements along the diagonals with positive slope
+/// (see [`diagonal_pos_pos`](crate::diagonal_pos_pos)).
+///
+/// The windows slide one element at a time along each diagonal in
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos) order, and `f` receives the elements of each
+/// window, so moving averages, maxima or any other window statistic can be computed without
+/// extracting the diagonals first. Every result is paired with the `(row, column)` coordinate
+/// of the window's first element.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `k` - The number of elements in each window.
+/// * `f` - The reduction applied to each window.
+///
+/// # Returns
+///
+/// A `Vec<Vec<((usize, usize), U)>>` with the reduced windows of each diagonal. Diagonals
+/// shorter than `k` have no windows.
+///
+/// # Panics
+///
+/// Panics if `k` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_windowed_pos_pos;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_windowed_pos_pos(&matrix, 2, |window| *window[0] + *window[1]);
+/// assert_eq!(result, vec![
+///     vec![],
+///     vec![((1, 0), 12)],
+///     vec![((0, 0), 6), ((1, 1), 14)],
+///     vec![((0, 1), 8)],
+///     vec![],
+/// ]);
+/// ```
+pub fn diagonal_windowed_pos_pos<'a, Matrix, Row, T, U, F>(
+    matrix: &'a Matrix,
+    k: usize,
+    f: F,
+) -> Vec<Vec<((usize, usize), U)>>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    T: 'a,
+    F: FnMut(&[&'a T]) -> U,
+{
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    windowed(matrix, index::pos_pos_cells(rows, cols), k, f)
+}
+
+/// Reduces every window of `k` consecutive elements along the diagonals with positive and negative
+/// slope (see [`diagonal_pos_neg`](crate::diagonal_pos_neg)).
+///
+/// The windows slide one element at a time along each diagonal in
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg) order, and `f` receives the elements of each
+/// window, so moving averages, maxima or any other window statistic can be computed without
+/// extracting the diagonals first. Every result is paired with the `(row, column)` coordinate
+/// of the window's first element.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `k` - The number of elements in each window.
+/// * `f` - The reduction applied to each window.
+///
+/// # Returns
+///
+/// A `Vec<Vec<((usize, usize), U)>>` with the reduced windows of each diagonal. Diagonals
+/// shorter than `k` have no windows.
+///
+/// # Panics
+///
+/// Panics if `k` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_windowed_pos_neg;
+///
+/// let matrix = vec![
+///     vec![1.0, 2.0, 3.0],
+///     vec![4.0, 5.0, 6.0],
+///     vec![7.0, 8.0, 9.0],
+/// ];
+///
+/// // Moving average over three elements: only the main anti-diagonal is long enough.
+/// let result = diagonal_windowed_pos_neg(&matrix, 3, |window| {
+///     window.iter().copied().sum::<f64>() / 3.0
+/// });
+/// assert_eq!(result[2], vec![((0, 2), 5.0)]);
+/// assert!(result.iter().map(Vec::len).eq([0, 0, 1, 0, 0]));
+/// ```
+pub fn diagonal_windowed_pos_neg<'a, Matrix, Row, T, U, F>(
+    matrix: &'a Matrix,
+    k: usize,
+    f: F,
+) -> Vec<Vec<((usize, usize), U)>>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+    T: 'a,
+    F: FnMut(&[&'a T]) -> U,
+{
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    windowed(matrix, index::pos_neg_cells(rows, cols), k, f)
+}
+
+/// Applies `f` to every window of `k` consecutive cells of every line in `cells`.
+fn windowed<'a, Row, T, U>(
+    matrix: &'a [Row],
+    cells: impl Iterator<Item = impl Iterator<Item = (usize, usize)>>,
+    k: usize,
+    mut f: impl FnMut(&[&'a T]) -> U,
+) -> Vec<Vec<((usize, usize), U)>>
+where
+    Row: AsRef<[T]>,
+    T: 'a,
+{
+    assert!(k > 0, "window size must be positive");
+
+    cells
+        .map(|line| {
+            let cells: Vec<_> = line.collect();
+            let values: Vec<&T> = cells.iter().map(|&(x, y)| &matrix[x].as_ref()[y]).collect();
+            values
+                .windows(k)
+                .zip(&cells)
+                .map(|(window, &start)| (start, f(window)))
+                .collect()
+        })
+        .collect()
+}
+
+/// Running sums of `values`, starting with the first value itself.
+fn running_sums<T: Copy + Add<Output = T>>(
+    values: impl Iterator<Item = T>,
+) -> impl Iterator<Item = T> {
+    values.scan(None, |acc: &mut Option<T>, value| {
+        let sum = acc.map_or(value, |acc| acc + value);
+        *acc = Some(sum);
+        Some(sum)
+    })
+}
+
+/// Panics unless `output` is a `rows` x `cols` matrix.
+fn assert_same_shape<Row: AsMut<[T]>, T>(rows: usize, cols: usize, output: &mut [Row]) {
+    assert!(
+        output.len() == rows && output.iter_mut().all(|row| row.as_mut().len() == cols),
+        "output matrix must have the same shape as the input matrix"
+    );
+}
+
+/// Coordinate of the first element of every diagonal that no later element is `better` than.
+fn extremes<Row, T>(
+    matrix: &[Row],
+    cells: impl Iterator<Item = impl Iterator<Item = (usize, usize)>>,
+    better: impl Fn(&T, &T) -> bool,
+) -> Vec<(usize, usize)>
+where
+    Row: AsRef<[T]>,
+{
+    let value = |(x, y): (usize, usize)| &matrix[x].as_ref()[y];
+
+    cells
+        .filter_map(|diagonal| {
+            diagonal.reduce(|best, candidate| {
+                if better(value(candidate), value(best)) {
+                    candidate
+                } else {
+                    best
+                }
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums_two_by_three() {
+        let matrix = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        assert_eq!(diagonal_sums_pos_pos(&matrix), vec![4, 6, 8, 3]);
+        assert_eq!(diagonal_sums_pos_neg(&matrix), vec![1, 6, 8, 6]);
+
+        let matrix = [[1.5, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        assert_eq!(diagonal_sums_pos_pos(&matrix), vec![4.0, 6.5, 8.0, 3.0]);
+        assert_eq!(diagonal_sums_pos_neg(&matrix), vec![1.5, 6.0, 8.0, 6.0]);
+    }
+
+    #[test]
+    fn sums_match_extraction() {
+        let matrix = [[3, 1, 4, 1], [5, 9, 2, 6], [5, 3, 5, 8]];
+        let expected: Vec<i32> = crate::diagonal_pos_pos(&matrix)
+            .into_iter()
+            .map(|diagonal| diagonal.into_iter().sum())
+            .collect();
+        assert_eq!(diagonal_sums_pos_pos(&matrix), expected);
+
+        let expected: Vec<i32> = crate::diagonal_pos_neg(&matrix)
+            .into_iter()
+            .map(|diagonal| diagonal.into_iter().sum())
+            .collect();
+        assert_eq!(diagonal_sums_pos_neg(&matrix), expected);
+    }
+
+    #[test]
+    fn extremes_two_by_three() {
+        let matrix = vec![vec![3, 1, 2], vec![1, 5, 0]];
+        assert_eq!(diagonal_min_pos_pos(&matrix), vec![&1, &3, &0, &2]);
+        assert_eq!(diagonal_max_pos_pos(&matrix), vec![&1, &5, &1, &2]);
+        assert_eq!(diagonal_min_pos_neg(&matrix), vec![&3, &1, &2, &0]);
+        assert_eq!(diagonal_max_pos_neg(&matrix), vec![&3, &1, &5, &0]);
+
+        assert_eq!(
+            diagonal_argmax_pos_pos(&matrix),
+            vec![(1, 0), (1, 1), (0, 1), (0, 2)]
+        );
+        assert_eq!(
+            diagonal_argmin_pos_neg(&matrix),
+            vec![(0, 0), (0, 1), (0, 2), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn extremes_ties_pick_first() {
+        let matrix = [[1, 1], [1, 1]];
+        assert_eq!(
+            diagonal_argmax_pos_neg(&matrix),
+            vec![(0, 0), (0, 1), (1, 1)]
+        );
+        assert_eq!(
+            diagonal_argmin_pos_pos(&matrix),
+            vec![(1, 0), (0, 0), (0, 1)]
+        );
+    }
+
+    #[test]
+    fn extremes_skip_nan() {
+        let matrix = [[1.0, f64::NAN], [f64::NAN, 2.0]];
+        assert_eq!(
+            diagonal_argmax_pos_pos(&matrix),
+            vec![(1, 0), (1, 1), (0, 1)]
+        );
+        assert_eq!(diagonal_argmax_pos_neg(&matrix)[1], (0, 1));
+    }
+
+    #[test]
+    fn extremes_empty() {
+        let matrix: Vec<Vec<usize>> = vec![];
+        assert_eq!(diagonal_max_pos_pos(&matrix), Vec::<&usize>::new());
+        assert_eq!(
+            diagonal_argmin_pos_neg(&matrix),
+            Vec::<(usize, usize)>::new()
+        );
+    }
+
+    #[test]
+    fn difference_square() {
+        let matrix = vec![vec![1, 2], vec![3, 4]];
+        assert_eq!(diagonal_difference(&matrix), 0);
+
+        let matrix: [[u32; 3]; 3] = [[1, 2, 9], [4, 5, 6], [9, 8, 1]];
+        assert_eq!(diagonal_difference(&matrix), 16);
+
+        let matrix = [[2.5]];
+        assert_eq!(diagonal_difference(&matrix), 0.0);
+    }
+
+    #[test]
+    fn difference_empty() {
+        let matrix: Vec<Vec<i32>> = vec![];
+        assert_eq!(diagonal_difference(&matrix), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn difference_not_square() {
+        let matrix = [[1, 2, 3], [4, 5, 6]];
+        diagonal_difference(&matrix);
+    }
+
+    #[test]
+    fn reduce_matches_traversals() {
+        let matrix: Vec<Vec<i32>> = (0..3)
+            .map(|r| (0..5).map(|c| r * 5 + c).collect())
+            .collect();
+        assert_eq!(
+            reduce_lines(&matrix, Direction::DiagonalPosPos, 0, |acc, &x| acc + x),
+            diagonal_sums_pos_pos(&matrix)
+        );
+        assert_eq!(
+            reduce_lines(&matrix, Direction::DiagonalPosNeg, 0, |acc, &x| acc + x),
+            diagonal_sums_pos_neg(&matrix)
+        );
+        assert_eq!(
+            reduce_lines(&matrix, Direction::StraightY, i32::MIN, |acc, &x| acc
+                .max(x)),
+            vec![10, 11, 12, 13, 14]
+        );
+
+        // The accumulator sees the elements in traversal order.
+        let concatenated = reduce_lines(&matrix, Direction::StraightX, String::new(), |acc, x| {
+            acc + &x.to_string()
+        });
+        assert_eq!(concatenated, ["01234", "56789", "1011121314"]);
+
+        let empty: Vec<Vec<i32>> = vec![];
+        assert!(reduce_lines(&empty, Direction::StraightX, 0, |acc, &x| acc + x).is_empty());
+    }
+
+    #[test]
+    fn traces() {
+        let matrix = [[1, 2, 3], [4, 5, 6], [7, 8, 9]];
+        assert_eq!(trace(&matrix), 15);
+        assert_eq!(anti_trace(&matrix), 15);
+
+        let tall = [[1, 2], [3, 4], [5, 6]];
+        assert_eq!(trace(&tall), 5);
+        assert_eq!(anti_trace(&tall), 5);
+
+        let wide = [[1.5, 2.0, 3.0, 4.0]];
+        assert_eq!(trace(&wide), 1.5);
+        assert_eq!(anti_trace(&wide), 4.0);
+
+        // Both match the corresponding diagonals of the traversals.
+        let matrix: Vec<Vec<i32>> = (0..4)
+            .map(|r| (0..6).map(|c| r * 6 + c).collect())
+            .collect();
+        assert_eq!(trace(&matrix), diagonal_sums_pos_pos(&matrix)[3]);
+        assert_eq!(anti_trace(&matrix), diagonal_sums_pos_neg(&matrix)[5]);
+
+        let empty: Vec<Vec<i32>> = vec![];
+        assert_eq!(trace(&empty), 0);
+        assert_eq!(anti_trace(&[[0u8; 0]]), 0);
+    }
+
+    #[test]
+    fn prefix_sums_two_by_three() {
+        let matrix = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        assert_eq!(
+            diagonal_prefix_sums_pos_pos(&matrix),
+            vec![vec![4], vec![1, 6], vec![2, 8], vec![3]]
+        );
+        assert_eq!(
+            diagonal_prefix_sums_pos_neg(&matrix),
+            vec![vec![1], vec![2, 6], vec![3, 8], vec![6]]
+        );
+    }
+
+    #[test]
+    fn prefix_sums_into_matches_sums() {
+        let matrix = [[3, 1, 4, 1], [5, 9, 2, 6], [5, 3, 5, 8]];
+
+        let mut output = [[0; 4]; 3];
+        diagonal_prefix_sums_into_pos_pos(&matrix, &mut output);
+        assert_eq!(output, [[3, 1, 4, 1], [5, 12, 3, 10], [5, 8, 17, 11]]);
+
+        let mut output = vec![vec![0; 4]; 3];
+        diagonal_prefix_sums_into_pos_neg(&matrix, &mut output);
+        assert_eq!(
+            output,
+            vec![vec![3, 1, 4, 1], vec![6, 13, 3, 6], vec![18, 6, 11, 8]]
+        );
+    }
+
+    #[test]
+    fn convolution_matches_definition() {
+        let matrix: Vec<Vec<i64>> = (0..4)
+            .map(|r| (0..6).map(|c| (r * 7 + c * 3) % 10).collect())
+            .collect();
+        for kernel in [vec![2], vec![1, -1], vec![1, 2, 3], vec![1, 0, 0, 5, 7]] {
+            let mut output = vec![vec![0; 6]; 4];
+            convolve_diagonals_pos_neg(&matrix, &kernel, &mut output);
+
+            // Full convolution of each diagonal, cropped to the diagonal's length.
+            for (diagonal, cells) in crate::diagonal_pos_neg(&matrix)
+                .iter()
+                .zip(index::pos_neg_cells(4, 6))
+            {
+                let mut full = vec![0; diagonal.len() + kernel.len() - 1];
+                for (n, &&value) in diagonal.iter().enumerate() {
+                    for (j, &weight) in kernel.iter().enumerate() {
+                        full[n + j] += weight * value;
+                    }
+                }
+                let center = (kernel.len() - 1) / 2;
+                for (i, (x, y)) in cells.enumerate() {
+                    assert_eq!(output[x][y], full[i + center]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn convolution_with_identity() {
+        let matrix = [[1.5, 2.0], [3.0, 4.0], [5.0, 6.5]];
+        let mut output = [[0.0; 2]; 3];
+        convolve_diagonals_pos_pos(&matrix, &[0.0, 1.0, 0.0], &mut output);
+        assert_eq!(output, matrix);
+
+        // A reversed delta shifts every diagonal by one cell towards its end.
+        let matrix = [[1, 0, 0], [0, 2, 0], [0, 0, 3]];
+        let mut output = [[9; 3]; 3];
+        convolve_diagonals_pos_pos(&matrix, &[0, 0, 1], &mut output);
+        assert_eq!(output, [[0, 0, 0], [0, 1, 0], [0, 0, 2]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn convolution_with_empty_kernel() {
+        let matrix = [[1, 2], [3, 4]];
+        let mut output = [[0; 2]; 2];
+        convolve_diagonals_pos_pos(&matrix, &[], &mut output);
+    }
+
+    #[test]
+    fn windows_match_diagonals() {
+        let matrix: Vec<Vec<usize>> = (0..4)
+            .map(|r| (0..6).map(|c| r * 6 + c).collect())
+            .collect();
+        for k in 1..6 {
+            let windowed = diagonal_windowed_pos_neg(&matrix, k, |window| window.to_vec());
+            let diagonals = crate::diagonal_pos_neg(&matrix);
+            assert_eq!(windowed.len(), diagonals.len());
+            for (windows, diagonal) in windowed.iter().zip(&diagonals) {
+                assert_eq!(windows.len(), (diagonal.len() + 1).saturating_sub(k));
+                for (i, ((x, y), window)) in windows.iter().enumerate() {
+                    assert_eq!(window[..], diagonal[i..i + k]);
+                    assert_eq!(*window[0], x * 6 + y);
+                }
+            }
+        }
+
+        let max = diagonal_windowed_pos_pos(&[[3, 1, 4], [1, 5, 9]], 2, |window| {
+            **window.iter().max().unwrap()
+        });
+        assert_eq!(
+            max,
+            vec![vec![], vec![((0, 0), 5)], vec![((0, 1), 9)], vec![]]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn windows_of_zero_elements() {
+        diagonal_windowed_pos_pos(&[[1]], 0, |window| window.len());
+    }
+
+    #[test]
+    fn prefix_sums_empty() {
+        let matrix: Vec<Vec<usize>> = vec![];
+        assert_eq!(
+            diagonal_prefix_sums_pos_pos(&matrix),
+            Vec::<Vec<usize>>::new()
+        );
+        let mut output: Vec<Vec<usize>> = vec![];
+        diagonal_prefix_sums_into_pos_neg(&matrix, &mut output);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn prefix_sums_into_wrong_shape() {
+        let matrix = [[1, 2], [3, 4]];
+        let mut output = [[0; 3]; 2];
+        diagonal_prefix_sums_into_pos_pos(&matrix, &mut output);
+    }
+
+    #[test]
+    fn sums_empty() {
+        let matrix: Vec<Vec<usize>> = vec![];
+        assert_eq!(diagonal_sums_pos_pos(&matrix), Vec::<usize>::new());
+        assert_eq!(diagonal_sums_pos_neg(&matrix), Vec::<usize>::new());
+    }
+}
@@ -0,0 +1,186 @@
+use crate::index;
+
+/// Rotates a matrix by 45 degrees into its "diagonal coordinates".
+///
+/// The cell at `(row, col)` of a `rows` x `cols` matrix moves to `(rows - 1 - row + col,
+/// row + col)` of a square grid with `rows + cols - 1` rows and columns. Every row of the
+/// rotated grid holds one [`diagonal_pos_pos`](crate::diagonal_pos_pos) diagonal in traversal
+/// order, and every column holds one [`diagonal_pos_neg`](crate::diagonal_pos_neg) diagonal,
+/// read from bottom to top. Neighbouring elements of a diagonal are two columns (or rows)
+/// apart, and the cells in between are padding set to `None`, so ordinary row and column
+/// algorithms can run on the diagonals while cells keep their geometric neighbours.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// The rotated grid as a `Vec<Vec<Option<&T>>>`, which is empty if the matrix has no cells.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::rot45;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ];
+///
+/// assert_eq!(rot45(&matrix), vec![
+///     vec![None,     Some(&4), None,     None    ],
+///     vec![Some(&1), None,     Some(&5), None    ],
+///     vec![None,     Some(&2), None,     Some(&6)],
+///     vec![None,     None,     Some(&3), None    ],
+/// ]);
+/// ```
+pub fn rot45<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Vec<Vec<Option<&'a T>>> {
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    let size = index::diagonal_count(rows, cols);
+
+    let mut rotated = vec![vec![None; size]; size];
+    for (row, elements) in matrix.iter().enumerate() {
+        for (col, element) in elements.as_ref().iter().enumerate().take(cols) {
+            rotated[index::pos_pos_index(rows, row, col)][index::pos_neg_index(row, col)] =
+                Some(element);
+        }
+    }
+    rotated
+}
+
+/// Rotates a grid in diagonal coordinates back into a `rows` x `cols` matrix.
+///
+/// This is the inverse of [`rot45`]: the value at `(rows - 1 - row + col, row + col)` of the
+/// rotated grid becomes the cell at `(row, col)`. Padding cells are ignored.
+///
+/// # Arguments
+///
+/// * `rotated` - The owned rotated grid, with `rows + cols - 1` rows and columns.
+/// * `rows` - The number of rows of the matrix.
+/// * `cols` - The number of columns of the matrix.
+///
+/// # Returns
+///
+/// A `rows` x `cols` matrix as a `Vec<Vec<T>>`.
+///
+/// # Panics
+///
+/// Panics if the rotated grid does not have the size of a rotated `rows` x `cols` matrix, or if
+/// a cell that corresponds to a matrix cell is `None`.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{rot45, unrot45};
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ];
+///
+/// // Reverse every diagonal with positive slope by reversing the rows of the rotated grid.
+/// let rotated: Vec<Vec<Option<i32>>> = rot45(&matrix)
+///     .into_iter()
+///     .map(|row| {
+///         let mut values: Vec<i32> = row.iter().flatten().map(|&&value| value).collect();
+///         row.iter()
+///             .map(|cell| cell.and_then(|_| values.pop()))
+///             .collect()
+///     })
+///     .collect();
+///
+/// assert_eq!(unrot45(rotated, 2, 3), vec![
+///     vec![5, 6, 3],
+///     vec![4, 1, 2],
+/// ]);
+/// ```
+pub fn unrot45<T>(rotated: Vec<Vec<Option<T>>>, rows: usize, cols: usize) -> Vec<Vec<T>> {
+    let size = index::diagonal_count(rows, cols);
+    assert!(
+        rotated.len() == size && rotated.iter().all(|row| row.len() == size),
+        "rotated grid must have rows + cols - 1 rows and columns"
+    );
+
+    let mut rotated = rotated;
+    (0..rows)
+        .map(|row| {
+            (0..cols)
+                .map(|col| {
+                    rotated[index::pos_pos_index(rows, row, col)][index::pos_neg_index(row, col)]
+                        .take()
+                        .expect("rotated grid is missing a matrix cell")
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{diagonal_pos_neg, diagonal_pos_pos};
+
+    fn matrix(rows: usize, cols: usize) -> Vec<Vec<usize>> {
+        (0..rows)
+            .map(|r| (0..cols).map(|c| r * cols + c).collect())
+            .collect()
+    }
+
+    #[test]
+    fn rows_and_columns_are_diagonals() {
+        for rows in 1..5 {
+            for cols in 1..5 {
+                let matrix = matrix(rows, cols);
+                let rotated = rot45(&matrix);
+
+                let by_row: Vec<Vec<&usize>> = rotated
+                    .iter()
+                    .map(|row| row.iter().flatten().copied().collect())
+                    .collect();
+                assert_eq!(by_row, diagonal_pos_pos(&matrix));
+
+                let by_col: Vec<Vec<&usize>> = (0..rotated.len())
+                    .map(|col| rotated.iter().rev().filter_map(|row| row[col]).collect())
+                    .collect();
+                assert_eq!(by_col, diagonal_pos_neg(&matrix));
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for rows in 0..5 {
+            for cols in 0..5 {
+                let matrix = matrix(rows, cols);
+                let back = unrot45(rot45(&matrix), rows, cols);
+                let expected: Vec<Vec<&usize>> =
+                    matrix.iter().map(|row| row.iter().collect()).collect();
+                if rows > 0 && cols > 0 {
+                    assert_eq!(back, expected);
+                } else {
+                    assert_eq!(back.len(), rows);
+                    assert!(back.iter().all(Vec::is_empty));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrong_size() {
+        unrot45(vec![vec![Some(1)]], 2, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn missing_cell() {
+        let mut rotated: Vec<Vec<Option<&usize>>> = rot45(&[[1, 2], [3, 4]]);
+        rotated[1][0] = None;
+        unrot45(rotated, 2, 2);
+    }
+}
@@ -0,0 +1,210 @@
+use crate::search::LineWalk;
+use crate::Direction;
+
+/// Finds the longest strictly increasing run along any row, column or diagonal of a matrix.
+///
+/// Every line is read in both orientations, so a run may ascend towards either end of its line.
+/// This is [`longest_run_by`] with `<` as the condition.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// The `(row, column)` coordinates of the run, from its smallest element to its largest, or an
+/// empty vector for a matrix without cells. Among runs of the same length, the first one found
+/// by [`longest_run_by`] is returned.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::longest_increasing_run;
+///
+/// let matrix = [
+///     [1, 5, 2],
+///     [4, 2, 6],
+///     [3, 8, 3],
+/// ];
+///
+/// assert_eq!(longest_increasing_run(&matrix), vec![(0, 0), (1, 1), (2, 2)]);
+///
+/// // Runs may be read against the direction of their line, too.
+/// assert_eq!(longest_increasing_run(&[[3, 2, 1]]), vec![(0, 2), (0, 1), (0, 0)]);
+/// ```
+pub fn longest_increasing_run<Matrix, Row, T>(matrix: &Matrix) -> Vec<(usize, usize)>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: PartialOrd,
+{
+    longest_run_by(matrix, |a, b| a < b)
+}
+
+/// Finds the longest run along any row, column or diagonal of a matrix in which every element
+/// may follow the one before it.
+///
+/// `follows(a, b)` decides whether `b` may come right after `a` in a run. Every line is read in
+/// both orientations, so the run may head towards either end of its line, and all lines are
+/// scanned once, in the order of [`Direction`]'s variants and then in traversal order.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `follows` - The condition two neighbouring elements of a run have to satisfy.
+///
+/// # Returns
+///
+/// The `(row, column)` coordinates of the run in run order, or an empty vector for a matrix
+/// without cells. Among runs of the same length, the one that ends first in the scan wins, and
+/// a run read in traversal order wins over one ending in the same cell that is read backwards.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::longest_run_by;
+///
+/// let matrix = [
+///     ['a', 'a', 'a'],
+///     ['b', 'c', 'b'],
+///     ['c', 'c', 'b'],
+/// ];
+///
+/// // Runs of equal elements.
+/// assert_eq!(longest_run_by(&matrix, |a, b| a == b), vec![(0, 0), (0, 1), (0, 2)]);
+///
+/// // Runs that climb the alphabet one letter at a time.
+/// let run = longest_run_by(&matrix, |&a, &b| b as u32 == a as u32 + 1);
+/// assert_eq!(run, vec![(0, 0), (1, 0), (2, 0)]);
+/// ```
+pub fn longest_run_by<Matrix, Row, T, F>(matrix: &Matrix, mut follows: F) -> Vec<(usize, usize)>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut best: Option<Run> = None;
+    let mut best_len = 0;
+
+    for line in LineWalk::new(matrix.as_ref(), Direction::ALL) {
+        let (mut forward, mut backward) = (0, 0);
+        for i in 0..line.len {
+            if i > 0 {
+                let (prev, next) = (line.element(i - 1), line.element(i));
+                if !follows(prev, next) {
+                    forward = i;
+                }
+                if !follows(next, prev) {
+                    backward = i;
+                }
+            }
+            for (first, reversed) in [(forward, false), (backward, true)] {
+                if i + 1 - first > best_len {
+                    best_len = i + 1 - first;
+                    best = Some(Run {
+                        direction: line.direction,
+                        start: line.start,
+                        first,
+                        last: i,
+                        reversed,
+                    });
+                }
+            }
+        }
+    }
+
+    best.map_or_else(Vec::new, |run| {
+        let cell = |i| run.direction.cell(run.start, i);
+        if run.reversed {
+            (run.first..=run.last).rev().map(cell).collect()
+        } else {
+            (run.first..=run.last).map(cell).collect()
+        }
+    })
+}
+
+/// A run along a line, as the range of positions it covers and its orientation.
+struct Run {
+    direction: Direction,
+    start: (usize, usize),
+    first: usize,
+    last: usize,
+    reversed: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Length of the longest increasing run, by checking every segment of every line.
+    fn brute_force(matrix: &[Vec<u32>]) -> usize {
+        let mut best = 0;
+        for direction in Direction::ALL {
+            for line in crate::lines_iter(matrix, direction) {
+                let line: Vec<u32> = line.copied().collect();
+                for reversed in [false, true] {
+                    let mut line = line.clone();
+                    if reversed {
+                        line.reverse();
+                    }
+                    for i in 0..line.len() {
+                        let run = 1 + line[i..].windows(2).take_while(|w| w[0] < w[1]).count();
+                        best = best.max(run);
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut seed = 7u32;
+        for rows in 1..6 {
+            for cols in 1..6 {
+                let matrix: Vec<Vec<u32>> = (0..rows)
+                    .map(|_| {
+                        (0..cols)
+                            .map(|_| {
+                                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                                (seed >> 16) % 6
+                            })
+                            .collect()
+                    })
+                    .collect();
+
+                let run = longest_increasing_run(&matrix);
+                assert_eq!(run.len(), brute_force(&matrix));
+                for pair in run.windows(2) {
+                    let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+                    assert!(matrix[x0][y0] < matrix[x1][y1]);
+                    assert!(x0.abs_diff(x1) <= 1 && y0.abs_diff(y1) <= 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn orientations() {
+        assert_eq!(
+            longest_increasing_run(&[[1, 2, 3]]),
+            vec![(0, 0), (0, 1), (0, 2)]
+        );
+        assert_eq!(
+            longest_increasing_run(&[[3, 2, 1]]),
+            vec![(0, 2), (0, 1), (0, 0)]
+        );
+        assert_eq!(
+            longest_increasing_run(&[[0, 0, 3], [0, 2, 0], [1, 0, 0]]),
+            vec![(2, 0), (1, 1), (0, 2)]
+        );
+    }
+
+    #[test]
+    fn degenerate() {
+        assert_eq!(longest_increasing_run(&[[5, 5], [5, 5]]).len(), 1);
+        assert!(longest_increasing_run(&Vec::<Vec<u8>>::new()).is_empty());
+    }
+}
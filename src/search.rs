@@ -0,0 +1,807 @@
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+use crate::{index, Direction, Line};
+
+/// An occurrence of a pattern along a line, as reported by [`find_pattern`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Match {
+    /// The traversal the line belongs to.
+    pub direction: Direction,
+    /// The index of the line in the output of the function named by `direction`.
+    pub line: usize,
+    /// The position within the line of the pattern's first element.
+    pub position: usize,
+    /// The `(row, column)` coordinate of the pattern's first element.
+    pub start: (usize, usize),
+}
+
+/// A maximal run of elements along a line that satisfy a predicate, as reported by
+/// [`segments_matching`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment {
+    /// The traversal the line belongs to.
+    pub direction: Direction,
+    /// The index of the line in the output of the function named by `direction`.
+    pub line: usize,
+    /// The position within the line of the segment's first element.
+    pub position: usize,
+    /// The `(row, column)` coordinate of the segment's first element.
+    pub start: (usize, usize),
+    /// The number of elements in the segment.
+    pub len: usize,
+}
+
+/// An approximate occurrence of a pattern along a line, as reported by [`find_pattern_fuzzy`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FuzzyMatch {
+    /// The traversal the line belongs to.
+    pub direction: Direction,
+    /// The index of the line in the output of the function named by `direction`.
+    pub line: usize,
+    /// The position within the line of the pattern's first element.
+    pub position: usize,
+    /// The `(row, column)` coordinate of the pattern's first element.
+    pub start: (usize, usize),
+    /// The `(row, column)` coordinates of the cells that did not match their pattern element,
+    /// in pattern order.
+    pub mismatches: Vec<(usize, usize)>,
+}
+
+/// Searches every row, column and diagonal of a matrix for a pattern.
+///
+/// Lines are read in the order the traversal functions return them, so a pattern only matches
+/// in that reading direction; search for the reversed pattern as well to find occurrences that
+/// run the other way. Overlapping occurrences are all reported.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `pattern` - The sequence of elements to look for.
+///
+/// # Returns
+///
+/// A `Vec<Match>` sorted by direction (in the order of [`Direction`]'s variants), line and
+/// position. An empty pattern matches nowhere.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{find_pattern, Direction, Match};
+///
+/// let grid = [
+///     ['c', 'a', 't'],
+///     ['x', 'a', 'x'],
+///     ['x', 't', 't'],
+/// ];
+///
+/// assert_eq!(find_pattern(&grid, &['a', 't']), vec![
+///     Match { direction: Direction::DiagonalPosPos, line: 2, position: 1, start: (1, 1) },
+///     Match { direction: Direction::StraightX, line: 0, position: 1, start: (0, 1) },
+///     Match { direction: Direction::StraightY, line: 1, position: 1, start: (1, 1) },
+/// ]);
+/// ```
+pub fn find_pattern<Matrix, Row, T>(matrix: &Matrix, pattern: &[T]) -> Vec<Match>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    T: PartialEq,
+{
+    LineWalk::new(matrix.as_ref(), Direction::ALL)
+        .flat_map(|line| line_matches(line, pattern))
+        .collect()
+}
+
+/// Searches every row, column and diagonal of a matrix for a pattern, in parallel.
+///
+/// This is the parallel counterpart of [`find_pattern`]: the lines are distributed over rayon's
+/// thread pool, and the matches are merged back into exactly the order [`find_pattern`] returns
+/// them, independent of scheduling.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `pattern` - The sequence of elements to look for.
+///
+/// # Returns
+///
+/// A `Vec<Match>` sorted by direction (in the order of [`Direction`]'s variants), line and
+/// position. An empty pattern matches nowhere.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{find_pattern, par_find_pattern};
+///
+/// let grid: Vec<Vec<u8>> = (0..50).map(|r| (0..60).map(|c| ((r * c) % 3) as u8).collect()).collect();
+///
+/// assert_eq!(par_find_pattern(&grid, &[0, 1, 2]), find_pattern(&grid, &[0, 1, 2]));
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_find_pattern<Matrix, Row, T>(matrix: &Matrix, pattern: &[T]) -> Vec<Match>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + Sync,
+    T: PartialEq + Sync,
+{
+    use rayon::prelude::*;
+
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+
+    // Collecting into a `Vec` keeps the sequential order, even after `flat_map`.
+    Direction::ALL
+        .into_par_iter()
+        .flat_map(|direction| {
+            (0..direction.line_count(rows, cols))
+                .into_par_iter()
+                .map(move |line| (direction, line))
+        })
+        .flat_map_iter(|(direction, line)| {
+            line_matches(LineRef::new(matrix, (rows, cols), direction, line), pattern)
+        })
+        .collect()
+}
+
+/// Searches every row, column and diagonal of a matrix for approximate occurrences of a
+/// pattern.
+///
+/// Whether a cell matches its pattern element is decided by `matcher` instead of
+/// [`PartialEq`], so the pattern can be of a different type than the matrix, and an occurrence
+/// may contain up to `max_mismatches` cells that do not match. Lines are read as in
+/// [`find_pattern`], and overlapping occurrences are all reported.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `pattern` - The sequence of needles to look for.
+/// * `max_mismatches` - The number of cells of an occurrence that may fail to match.
+/// * `matcher` - Tells whether a cell matches a needle.
+///
+/// # Returns
+///
+/// A `Vec<FuzzyMatch>` sorted by direction (in the order of [`Direction`]'s variants), line and
+/// position. An empty pattern matches nowhere.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{find_pattern_fuzzy, Direction, FuzzyMatch};
+///
+/// // An OCR scan, in which case varies and an 'o' was read as a '0'.
+/// let scan = [
+///     ['C', 'a', 't'],
+///     ['x', '0', 'x'],
+///     ['x', 't', 'T'],
+/// ];
+///
+/// let matches = find_pattern_fuzzy(&scan, &['c', 'o', 't'], 1, |cell: &char, needle: &char| {
+///     cell.eq_ignore_ascii_case(needle)
+/// });
+/// assert_eq!(matches, vec![
+///     FuzzyMatch {
+///         direction: Direction::DiagonalPosPos,
+///         line: 2,
+///         position: 0,
+///         start: (0, 0),
+///         mismatches: vec![(1, 1)],
+///     },
+///     FuzzyMatch {
+///         direction: Direction::StraightX,
+///         line: 0,
+///         position: 0,
+///         start: (0, 0),
+///         mismatches: vec![(0, 1)],
+///     },
+/// ]);
+/// ```
+pub fn find_pattern_fuzzy<Matrix, Row, T, Needle, F>(
+    matrix: &Matrix,
+    pattern: &[Needle],
+    max_mismatches: usize,
+    matcher: F,
+) -> Vec<FuzzyMatch>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    F: Fn(&T, &Needle) -> bool,
+{
+    LineWalk::new(matrix.as_ref(), Direction::ALL)
+        .flat_map(|line| {
+            window_matches(line, pattern, max_mismatches, &matcher).map(
+                move |(position, mismatches)| FuzzyMatch {
+                    direction: line.direction,
+                    line: line.index,
+                    position,
+                    start: line.cell(position),
+                    mismatches,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Finds every maximal segment of consecutive elements that satisfy a predicate along the rows,
+/// columns and diagonals of a matrix.
+///
+/// A segment is maximal if the elements right before and after it, if any, do not satisfy the
+/// predicate. Every cell that satisfies it therefore lies in exactly one segment per direction.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `pred` - The predicate the elements of a segment satisfy.
+///
+/// # Returns
+///
+/// A `Vec<Segment>` sorted by direction (in the order of [`Direction`]'s variants), line and
+/// position.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{segments_matching, Direction, Segment};
+///
+/// let readings = [
+///     [71, 12, 90],
+///     [15, 88, 93],
+/// ];
+///
+/// let hot: Vec<Segment> = segments_matching(&readings, |&t| t > 50)
+///     .into_iter()
+///     .filter(|segment| segment.len > 1)
+///     .collect();
+/// assert_eq!(hot, vec![
+///     Segment { direction: Direction::DiagonalPosPos, line: 1, position: 0, start: (0, 0), len: 2 },
+///     Segment { direction: Direction::DiagonalPosNeg, line: 2, position: 0, start: (0, 2), len: 2 },
+///     Segment { direction: Direction::StraightX, line: 1, position: 1, start: (1, 1), len: 2 },
+///     Segment { direction: Direction::StraightY, line: 2, position: 0, start: (0, 2), len: 2 },
+/// ]);
+/// ```
+pub fn segments_matching<Matrix, Row, T, P>(matrix: &Matrix, mut pred: P) -> Vec<Segment>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]>,
+    P: FnMut(&T) -> bool,
+{
+    let mut segments = Vec::new();
+    for line in LineWalk::new(matrix.as_ref(), Direction::ALL) {
+        let mut open = None;
+        for i in 0..=line.len {
+            let matches = i < line.len && pred(line.element(i));
+            match (open, matches) {
+                (None, true) => open = Some(i),
+                (Some(position), false) => {
+                    segments.push(Segment {
+                        direction: line.direction,
+                        line: line.index,
+                        position,
+                        start: line.cell(position),
+                        len: i - position,
+                    });
+                    open = None;
+                }
+                _ => {}
+            }
+        }
+    }
+    segments
+}
+
+/// A configurable iterator over the lines of a matrix, tagged with their direction.
+///
+/// `Search` starts out with every line in every direction and is narrowed down with
+/// [`directions`](Search::directions) and [`min_len`](Search::min_len). It then yields each
+/// remaining line as a [`Line`], in the order the directions were given and in traversal order
+/// within each direction, so matchers and reducers can consume rows, columns and diagonals
+/// uniformly instead of calling the four traversals separately. Lines are only collected once
+/// they are reached, and lines that are too short are skipped without being collected.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{Direction, Search};
+///
+/// let matrix = [
+///     [1, 2, 3],
+///     [4, 5, 6],
+/// ];
+///
+/// let diagonals: Vec<Vec<&i32>> = Search::new(&matrix)
+///     .directions([Direction::DiagonalPosPos, Direction::DiagonalPosNeg])
+///     .min_len(2)
+///     .map(|line| line.to_vec())
+///     .collect();
+/// assert_eq!(diagonals, vec![
+///     vec![&1, &5],
+///     vec![&2, &6],
+///     vec![&2, &4],
+///     vec![&3, &5],
+/// ]);
+///
+/// // Palindromes of at least three elements along any line.
+/// let grid = [['a', 'b', 'a'], ['x', 'b', 'y'], ['a', 'y', 'a']];
+/// let palindromes = Search::new(&grid)
+///     .min_len(3)
+///     .filter(|line| line.iter().eq(line.iter().rev()))
+///     .map(|line| (line.direction(), line.index()))
+///     .collect::<Vec<_>>();
+/// assert_eq!(palindromes, vec![
+///     (Direction::DiagonalPosPos, 2),
+///     (Direction::DiagonalPosNeg, 2),
+///     (Direction::StraightX, 0),
+///     (Direction::StraightX, 2),
+///     (Direction::StraightY, 0),
+///     (Direction::StraightY, 2),
+/// ]);
+/// ```
+#[derive(Debug)]
+pub struct Search<'a, Row, T> {
+    lines: LineWalk<'a, Row, T>,
+    min_len: usize,
+}
+
+impl<'a, Row: AsRef<[T]>, T> Search<'a, Row, T> {
+    /// Creates a search over every line of `matrix` in every direction.
+    ///
+    /// # Arguments
+    ///
+    /// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+    ///   is a slice of generic type `T`.
+    pub fn new<Matrix: AsRef<[Row]> + ?Sized>(matrix: &'a Matrix) -> Self {
+        Search {
+            lines: LineWalk::new(matrix.as_ref(), Direction::ALL),
+            min_len: 0,
+        }
+    }
+
+    /// Restricts the search to `directions`, which are visited in the given order.
+    pub fn directions(mut self, directions: impl IntoIterator<Item = Direction>) -> Self {
+        self.lines = LineWalk::new(self.lines.matrix, directions);
+        self
+    }
+
+    /// Skips lines with fewer than `len` elements.
+    pub fn min_len(mut self, len: usize) -> Self {
+        self.min_len = len;
+        self
+    }
+}
+
+impl<'a, Row: AsRef<[T]>, T> Iterator for Search<'a, Row, T> {
+    type Item = Line<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let min_len = self.min_len;
+        let line = self.lines.find(|line| line.len >= min_len)?;
+        Some(Line::new(
+            self.lines.matrix,
+            (self.lines.rows, self.lines.cols),
+            line.direction,
+            line.index,
+        ))
+    }
+}
+
+impl<Row: AsRef<[T]>, T> FusedIterator for Search<'_, Row, T> {}
+
+// Manual impl, since deriving would require `Row: Clone` and `T: Clone`.
+impl<Row, T> Clone for Search<'_, Row, T> {
+    fn clone(&self) -> Self {
+        Search {
+            lines: self.lines.clone(),
+            min_len: self.min_len,
+        }
+    }
+}
+
+/// Walks the lines of a matrix without collecting them, in the order `directions` are given and
+/// in traversal order within each direction.
+///
+/// This is the loop shared by [`Search`], the scans of this module and
+/// [`longest_run_by`](crate::longest_run_by).
+#[derive(Debug)]
+pub(crate) struct LineWalk<'a, Row, T> {
+    matrix: &'a [Row],
+    rows: usize,
+    cols: usize,
+    directions: Vec<Direction>,
+    direction: usize,
+    line: usize,
+    element: PhantomData<&'a T>,
+}
+
+/// One line reached by a [`LineWalk`], whose elements are looked up on demand.
+pub(crate) struct LineRef<'a, Row, T> {
+    matrix: &'a [Row],
+    /// The traversal the line belongs to.
+    pub(crate) direction: Direction,
+    /// The index of the line in the output of the function named by `direction`.
+    pub(crate) index: usize,
+    /// The `(row, column)` coordinate of the line's first element.
+    pub(crate) start: (usize, usize),
+    /// The number of elements of the line.
+    pub(crate) len: usize,
+    element: PhantomData<&'a T>,
+}
+
+impl<'a, Row: AsRef<[T]>, T> LineWalk<'a, Row, T> {
+    pub(crate) fn new(matrix: &'a [Row], directions: impl IntoIterator<Item = Direction>) -> Self {
+        let (rows, cols) = index::shape(matrix);
+        LineWalk {
+            matrix,
+            rows,
+            cols,
+            directions: directions.into_iter().collect(),
+            direction: 0,
+            line: 0,
+            element: PhantomData,
+        }
+    }
+}
+
+impl<'a, Row: AsRef<[T]>, T> Iterator for LineWalk<'a, Row, T> {
+    type Item = LineRef<'a, Row, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(&direction) = self.directions.get(self.direction) {
+            if self.line == direction.line_count(self.rows, self.cols) {
+                self.direction += 1;
+                self.line = 0;
+                continue;
+            }
+            self.line += 1;
+            return Some(LineRef::new(
+                self.matrix,
+                (self.rows, self.cols),
+                direction,
+                self.line - 1,
+            ));
+        }
+        None
+    }
+}
+
+impl<Row: AsRef<[T]>, T> FusedIterator for LineWalk<'_, Row, T> {}
+
+impl<'a, Row: AsRef<[T]>, T> LineRef<'a, Row, T> {
+    /// Locates the `index`-th line in `direction` of a `rows` x `cols` matrix.
+    pub(crate) fn new(
+        matrix: &'a [Row],
+        (rows, cols): (usize, usize),
+        direction: Direction,
+        index: usize,
+    ) -> Self {
+        let (start, len) = direction.line_start(rows, cols, index);
+        LineRef {
+            matrix,
+            direction,
+            index,
+            start,
+            len,
+            element: PhantomData,
+        }
+    }
+
+    /// Coordinate of the `i`-th cell of the line.
+    pub(crate) fn cell(&self, i: usize) -> (usize, usize) {
+        self.direction.cell(self.start, i)
+    }
+
+    /// The `i`-th element of the line.
+    pub(crate) fn element(&self, i: usize) -> &'a T {
+        let (x, y) = self.cell(i);
+        &self.matrix[x].as_ref()[y]
+    }
+}
+
+// Manual impls, since deriving would require `Row: Clone` and `T: Clone`.
+impl<Row, T> Clone for LineWalk<'_, Row, T> {
+    fn clone(&self) -> Self {
+        LineWalk {
+            directions: self.directions.clone(),
+            ..*self
+        }
+    }
+}
+
+impl<Row, T> Clone for LineRef<'_, Row, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Row, T> Copy for LineRef<'_, Row, T> {}
+
+/// Exact matches of `pattern` along `line`.
+fn line_matches<'a, Row, T>(
+    line: LineRef<'a, Row, T>,
+    pattern: &'a [T],
+) -> impl Iterator<Item = Match> + 'a
+where
+    Row: AsRef<[T]>,
+    T: PartialEq,
+{
+    window_matches(line, pattern, 0, |cell: &T, expected: &T| cell == expected).map(
+        move |(position, _)| Match {
+            direction: line.direction,
+            line: line.index,
+            position,
+            start: line.cell(position),
+        },
+    )
+}
+
+/// Positions along `line` at which `pattern` starts, with at most `max_mismatches` cells that
+/// `matcher` rejects, each paired with the coordinates of those cells.
+///
+/// A window is given up as soon as it exceeds the budget, and nothing is allocated for windows
+/// without mismatches.
+fn window_matches<'a, Row, T, Needle, F>(
+    line: LineRef<'a, Row, T>,
+    pattern: &'a [Needle],
+    max_mismatches: usize,
+    matcher: F,
+) -> impl Iterator<Item = (usize, Vec<(usize, usize)>)> + 'a
+where
+    Row: AsRef<[T]>,
+    F: Fn(&T, &Needle) -> bool + 'a,
+{
+    let candidates = if pattern.is_empty() || pattern.len() > line.len {
+        0
+    } else {
+        line.len - pattern.len() + 1
+    };
+    (0..candidates).filter_map(move |position| {
+        let mut mismatches = Vec::new();
+        let fits = pattern.iter().enumerate().all(|(i, needle)| {
+            if matcher(line.element(position + i), needle) {
+                return true;
+            }
+            if mismatches.len() == max_mismatches {
+                return false;
+            }
+            mismatches.push(line.cell(position + i));
+            true
+        });
+        fits.then_some((position, mismatches))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lines_iter;
+
+    /// Finds the matches by collecting every line and comparing windows.
+    fn brute_force(matrix: &[Vec<u8>], pattern: &[u8]) -> Vec<(Direction, usize, usize)> {
+        let mut result = Vec::new();
+        for direction in Direction::ALL {
+            for (line, elements) in lines_iter(matrix, direction).enumerate() {
+                let elements: Vec<u8> = elements.copied().collect();
+                for (position, window) in elements.windows(pattern.len()).enumerate() {
+                    if window == pattern {
+                        result.push((direction, line, position));
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    fn grid() -> Vec<Vec<u8>> {
+        (0..7)
+            .map(|r| (0..9).map(|c| ((r * 5 + c * 3) % 4) as u8).collect())
+            .collect()
+    }
+
+    #[test]
+    fn matches_windows() {
+        let matrix = grid();
+        for pattern in [&[0][..], &[1, 2], &[0, 3, 2], &[3, 3]] {
+            let found = find_pattern(&matrix, pattern);
+            let expected = brute_force(&matrix, pattern);
+            assert_eq!(
+                found
+                    .iter()
+                    .map(|m| (m.direction, m.line, m.position))
+                    .collect::<Vec<_>>(),
+                expected
+            );
+            for m in found {
+                let (x, y) = m.start;
+                assert_eq!(matrix[x][y], pattern[0]);
+            }
+        }
+    }
+
+    #[test]
+    fn overlapping() {
+        let matrix = [[1, 1, 1]];
+        let positions: Vec<usize> = find_pattern(&matrix, &[1, 1])
+            .iter()
+            .filter(|m| m.direction == Direction::StraightX)
+            .map(|m| m.position)
+            .collect();
+        assert_eq!(positions, vec![0, 1]);
+    }
+
+    #[test]
+    fn nothing_to_find() {
+        let matrix = grid();
+        assert!(find_pattern(&matrix, &[]).is_empty());
+        assert!(find_pattern(&matrix, &[0; 10]).is_empty());
+        assert!(find_pattern(&Vec::<Vec<u8>>::new(), &[0]).is_empty());
+    }
+
+    #[test]
+    fn fuzzy_without_mismatches_is_exact() {
+        let matrix = grid();
+        for pattern in [&[0][..], &[1, 2], &[0, 3, 2], &[3, 3]] {
+            let fuzzy = find_pattern_fuzzy(&matrix, pattern, 0, |a, b| a == b);
+            let exact = find_pattern(&matrix, pattern);
+            assert_eq!(fuzzy.len(), exact.len());
+            for (fuzzy, exact) in fuzzy.iter().zip(exact) {
+                assert_eq!(
+                    (fuzzy.direction, fuzzy.line, fuzzy.position, fuzzy.start),
+                    (exact.direction, exact.line, exact.position, exact.start)
+                );
+                assert!(fuzzy.mismatches.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn fuzzy_matches_windows() {
+        let matrix = grid();
+        let pattern = ['0', '1', '2', '3'];
+        let matcher = |&cell: &u8, &needle: &char| char::from(b'0' + cell) == needle;
+        for max_mismatches in 0..=4 {
+            let found = find_pattern_fuzzy(&matrix, &pattern, max_mismatches, matcher);
+
+            let mut expected = Vec::new();
+            for direction in Direction::ALL {
+                for (line, elements) in lines_iter(&matrix, direction).enumerate() {
+                    let elements: Vec<u8> = elements.copied().collect();
+                    for (position, window) in elements.windows(pattern.len()).enumerate() {
+                        let mismatches = window
+                            .iter()
+                            .zip(&pattern)
+                            .filter(|(cell, needle)| !matcher(cell, needle))
+                            .count();
+                        if mismatches <= max_mismatches {
+                            expected.push((direction, line, position, mismatches));
+                        }
+                    }
+                }
+            }
+            assert_eq!(
+                found
+                    .iter()
+                    .map(|m| (m.direction, m.line, m.position, m.mismatches.len()))
+                    .collect::<Vec<_>>(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn fuzzy_mismatch_coordinates() {
+        let matrix = [[1, 9, 3], [4, 5, 6]];
+        let matches = find_pattern_fuzzy(&matrix, &[1, 2, 3], 1, |a, b| a == b);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].start, (0, 0));
+        assert_eq!(matches[0].mismatches, vec![(0, 1)]);
+
+        assert!(find_pattern_fuzzy(&matrix, &[0; 0], 3, |a, b| a == b).is_empty());
+        assert!(find_pattern_fuzzy(&matrix, &[1; 4], 4, |a, b| a == b).is_empty());
+    }
+
+    #[test]
+    fn segments_are_maximal() {
+        let matrix = grid();
+        let segments = segments_matching(&matrix, |&x| x < 2);
+
+        let mut sorted = segments.clone();
+        sorted.sort();
+        assert_eq!(segments, sorted);
+
+        for direction in Direction::ALL {
+            let lines: Vec<Vec<u8>> = lines_iter(&matrix, direction)
+                .map(|line| line.copied().collect())
+                .collect();
+            let mut covered = 0;
+            for segment in segments.iter().filter(|s| s.direction == direction) {
+                let line = &lines[segment.line];
+                let end = segment.position + segment.len;
+                assert!(line[segment.position..end].iter().all(|&x| x < 2));
+                assert!(segment.position == 0 || line[segment.position - 1] >= 2);
+                assert!(end == line.len() || line[end] >= 2);
+                let (x, y) = segment.start;
+                assert_eq!(matrix[x][y], line[segment.position]);
+                covered += segment.len;
+            }
+            let matching = matrix.iter().flatten().filter(|&&x| x < 2).count();
+            assert_eq!(covered, matching);
+        }
+    }
+
+    #[test]
+    fn segments_of_whole_lines() {
+        let matrix = [[true; 3]; 2];
+        let segments = segments_matching(&matrix, |&x| x);
+        assert_eq!(segments.len(), 4 + 4 + 2 + 3);
+        assert!(segments_matching(&matrix, |&x| !x).is_empty());
+        assert!(segments_matching(&Vec::<Vec<bool>>::new(), |&x| x).is_empty());
+    }
+
+    #[test]
+    fn search_matches_traversals() {
+        let matrix = grid();
+        let all: Vec<Line<u8>> = Search::new(&matrix).collect();
+        let expected: Vec<Line<u8>> = Direction::ALL
+            .into_iter()
+            .flat_map(|direction| crate::lines(&matrix, direction))
+            .collect();
+        assert_eq!(all, expected);
+
+        for min_len in [0, 1, 5, 7, 8, 9, 10] {
+            let found: Vec<Line<u8>> = Search::new(&matrix)
+                .directions([Direction::StraightY, Direction::DiagonalPosNeg])
+                .min_len(min_len)
+                .collect();
+            let expected: Vec<Line<u8>> = [Direction::StraightY, Direction::DiagonalPosNeg]
+                .into_iter()
+                .flat_map(|direction| crate::lines(&matrix, direction))
+                .filter(|line| line.len() >= min_len)
+                .collect();
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn search_without_lines() {
+        let matrix = grid();
+        assert_eq!(Search::new(&matrix).directions([]).count(), 0);
+        assert_eq!(Search::new(&Vec::<Vec<u8>>::new()).count(), 0);
+
+        let mut search = Search::new(&[[1]]).directions([Direction::StraightX]);
+        assert!(search.next().is_some());
+        assert!(search.next().is_none());
+        assert!(search.next().is_none());
+    }
+
+    #[test]
+    fn search_clones_without_clone_elements() {
+        #[derive(Debug, PartialEq)]
+        struct Cell(u8);
+
+        let matrix = [[Cell(1), Cell(2)], [Cell(3), Cell(4)]];
+        let mut search = Search::new(&matrix).min_len(2);
+        search.next();
+        let rest: Vec<Line<Cell>> = search.clone().collect();
+        assert_eq!(rest, search.collect::<Vec<_>>());
+        assert_eq!(rest.len(), 5);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_matches_sequential() {
+        let matrix: Vec<Vec<u8>> = (0..120)
+            .map(|r| (0..90).map(|c| ((r * 7 + c * c) % 3) as u8).collect())
+            .collect();
+        for pattern in [&[0][..], &[1, 2], &[2, 2, 0]] {
+            assert_eq!(
+                par_find_pattern(&matrix, pattern),
+                find_pattern(&matrix, pattern)
+            );
+        }
+    }
+}
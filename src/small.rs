@@ -0,0 +1,168 @@
+use smallvec::SmallVec;
+
+use crate::{lines_into, Direction};
+
+/// Traverses the lines of a matrix in the given direction and stores every line inline in a
+/// [`SmallVec`].
+///
+/// Lines of up to `N` elements do not allocate at all, so only the outer vector is put on the
+/// heap. Pick `N` as the longest line of the matrix, such as the side length of a square
+/// board, and no line spills. This is [`lines_into`] with the containers fixed.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `direction` - The traversal to perform.
+///
+/// # Returns
+///
+/// The lines, in the same order as the function named by `direction` returns them.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{lines_small, Direction};
+///
+/// let matrix = [[1, 2], [3, 4]];
+///
+/// let result = lines_small::<2, _, _, _>(&matrix, Direction::StraightY);
+/// assert_eq!(result[0][..], [&1, &3]);
+/// assert_eq!(result[1][..], [&2, &4]);
+/// assert!(!result[0].spilled());
+/// ```
+pub fn lines_small<'a, const N: usize, Matrix, Row, T>(
+    matrix: &'a Matrix,
+    direction: Direction,
+) -> Vec<SmallVec<[&'a T; N]>>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+{
+    lines_into(matrix, direction)
+}
+
+/// Extracts diagonals with positive slope, like [`diagonal_pos_pos`](crate::diagonal_pos_pos),
+/// storing them inline (see [`lines_small`]).
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_pos_pos_small;
+/// use smallvec::SmallVec;
+///
+/// let board = [[0u8; 15]; 15]; // A gomoku board: no diagonal is longer than 15 cells.
+///
+/// let diagonals: Vec<SmallVec<[&u8; 15]>> = diagonal_pos_pos_small(&board);
+/// assert_eq!(diagonals.len(), 29);
+/// assert!(diagonals.iter().all(|diagonal| !diagonal.spilled()));
+/// ```
+pub fn diagonal_pos_pos_small<'a, const N: usize, Matrix, Row, T>(
+    matrix: &'a Matrix,
+) -> Vec<SmallVec<[&'a T; N]>>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+{
+    lines_small(matrix, Direction::DiagonalPosPos)
+}
+
+/// Extracts diagonals with positive and negative slope, like
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg), storing them inline (see [`lines_small`]).
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_pos_neg_small;
+///
+/// let matrix = [[1, 2], [3, 4]];
+///
+/// let diagonals = diagonal_pos_neg_small::<2, _, _, _>(&matrix);
+/// assert_eq!(diagonals.len(), 3);
+/// assert_eq!(diagonals[1][..], [&2, &3]);
+/// ```
+pub fn diagonal_pos_neg_small<'a, const N: usize, Matrix, Row, T>(
+    matrix: &'a Matrix,
+) -> Vec<SmallVec<[&'a T; N]>>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+{
+    lines_small(matrix, Direction::DiagonalPosNeg)
+}
+
+/// Extracts the rows, like [`straight_x`](crate::straight_x), storing them inline (see
+/// [`lines_small`]).
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::straight_x_small;
+///
+/// let matrix = [[1, 2], [3, 4]];
+/// assert_eq!(straight_x_small::<2, _, _, _>(&matrix)[1][..], [&3, &4]);
+/// ```
+pub fn straight_x_small<'a, const N: usize, Matrix, Row, T>(
+    matrix: &'a Matrix,
+) -> Vec<SmallVec<[&'a T; N]>>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+{
+    lines_small(matrix, Direction::StraightX)
+}
+
+/// Extracts the columns, like [`straight_y`](crate::straight_y), storing them inline (see
+/// [`lines_small`]).
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::straight_y_small;
+///
+/// let matrix = [[1, 2], [3, 4]];
+/// assert_eq!(straight_y_small::<2, _, _, _>(&matrix)[1][..], [&2, &4]);
+/// ```
+pub fn straight_y_small<'a, const N: usize, Matrix, Row, T>(
+    matrix: &'a Matrix,
+) -> Vec<SmallVec<[&'a T; N]>>
+where
+    Matrix: AsRef<[Row]> + ?Sized,
+    Row: AsRef<[T]> + 'a,
+{
+    lines_small(matrix, Direction::StraightY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{diagonal_pos_pos_into, lines_iter};
+
+    #[test]
+    fn matches_eager_traversals() {
+        let matrix: Vec<Vec<i32>> = (0..3)
+            .map(|r| (0..4).map(|c| r * 4 + c).collect())
+            .collect();
+        for direction in Direction::ALL {
+            let small = lines_small::<4, _, _, _>(&matrix, direction);
+            let eager = lines_iter(&matrix, direction).map(|line| line.collect::<Vec<_>>());
+            assert!(small.iter().map(|line| line.to_vec()).eq(eager));
+            assert!(small.iter().all(|line| !line.spilled()));
+        }
+    }
+
+    #[test]
+    fn gomoku_board_stays_inline() {
+        let board = [[0u8; 15]; 15];
+        let rising: Vec<SmallVec<[&u8; 15]>> = diagonal_pos_pos_into(&board);
+        let falling: Vec<SmallVec<[&u8; 15]>> = diagonal_pos_neg_small(&board);
+        assert_eq!(rising.len(), 29);
+        assert!(rising.iter().chain(&falling).all(|line| !line.spilled()));
+
+        let narrow: Vec<SmallVec<[&u8; 4]>> = diagonal_pos_pos_small(&board);
+        assert_eq!(
+            narrow.iter().filter(|line| line.spilled()).count(),
+            29 - 2 * 4
+        );
+    }
+}
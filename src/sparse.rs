@@ -0,0 +1,192 @@
+use crate::index;
+
+/// A single entry of a sparse matrix in coordinate format: `(row, column, value)`.
+pub type Triplet<T> = (usize, usize, T);
+
+/// The entries of a sparse matrix that lie on one diagonal, tagged with the diagonal's index.
+pub type SparseDiagonal<'a, T> = (usize, Vec<&'a Triplet<T>>);
+
+/// Groups the entries of a sparse matrix by their diagonal with positive slope (see
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos)).
+///
+/// The matrix is given in coordinate (COO) format as `(row, column, value)` triplets and is
+/// never densified, so the cost only depends on the number of entries. Only diagonals that hold
+/// at least one entry are returned.
+///
+/// # Arguments
+///
+/// * `entries` - The non-empty cells of the matrix as `(row, column, value)` triplets.
+/// * `rows` - The number of rows of the matrix.
+/// * `cols` - The number of columns of the matrix.
+///
+/// # Returns
+///
+/// A vector of `(diagonal index, entries)` pairs. The diagonal index is the position the
+/// diagonal would have in the output of [`diagonal_pos_pos`](crate::diagonal_pos_pos); the pairs
+/// are sorted by it and the entries of each diagonal are in traversal order. Entries with the
+/// same coordinate keep their relative order.
+///
+/// # Panics
+///
+/// Panics if an entry lies outside of a `rows` x `cols` matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonals_from_triplets_pos_pos;
+///
+/// let entries = [(0, 0, 'a'), (2, 1, 'b'), (1, 0, 'c'), (0, 2, 'd')];
+///
+/// let result = diagonals_from_triplets_pos_pos(&entries, 3, 3);
+/// assert_eq!(result, vec![
+///     (1, vec![&(1, 0, 'c'), &(2, 1, 'b')]),
+///     (2, vec![&(0, 0, 'a')]),
+///     (4, vec![&(0, 2, 'd')]),
+/// ]);
+/// ```
+pub fn diagonals_from_triplets_pos_pos<T>(
+    entries: &[Triplet<T>],
+    rows: usize,
+    cols: usize,
+) -> Vec<SparseDiagonal<'_, T>> {
+    group_by_diagonal(entries, rows, cols, |row, col| {
+        index::pos_pos_index(rows, row, col)
+    })
+}
+
+/// Groups the entries of a sparse matrix by their diagonal with positive and negative slope (see
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg)).
+///
+/// The matrix is given in coordinate (COO) format as `(row, column, value)` triplets and is
+/// never densified, so the cost only depends on the number of entries. Only diagonals that hold
+/// at least one entry are returned.
+///
+/// # Arguments
+///
+/// * `entries` - The non-empty cells of the matrix as `(row, column, value)` triplets.
+/// * `rows` - The number of rows of the matrix.
+/// * `cols` - The number of columns of the matrix.
+///
+/// # Returns
+///
+/// A vector of `(diagonal index, entries)` pairs. The diagonal index is the position the
+/// diagonal would have in the output of [`diagonal_pos_neg`](crate::diagonal_pos_neg); the pairs
+/// are sorted by it and the entries of each diagonal are in traversal order. Entries with the
+/// same coordinate keep their relative order.
+///
+/// # Panics
+///
+/// Panics if an entry lies outside of a `rows` x `cols` matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonals_from_triplets_pos_neg;
+///
+/// let entries = [(0, 0, 'a'), (2, 1, 'b'), (1, 0, 'c'), (0, 2, 'd')];
+///
+/// let result = diagonals_from_triplets_pos_neg(&entries, 3, 3);
+/// assert_eq!(result, vec![
+///     (0, vec![&(0, 0, 'a')]),
+///     (1, vec![&(1, 0, 'c')]),
+///     (2, vec![&(0, 2, 'd')]),
+///     (3, vec![&(2, 1, 'b')]),
+/// ]);
+/// ```
+pub fn diagonals_from_triplets_pos_neg<T>(
+    entries: &[Triplet<T>],
+    rows: usize,
+    cols: usize,
+) -> Vec<SparseDiagonal<'_, T>> {
+    group_by_diagonal(entries, rows, cols, index::pos_neg_index)
+}
+
+/// Sorts `entries` by diagonal and row, then splits them into one group per diagonal.
+///
+/// Both traversals visit the cells of a diagonal in increasing row order, so the row is enough
+/// to order the entries within a diagonal.
+fn group_by_diagonal<T>(
+    entries: &[Triplet<T>],
+    rows: usize,
+    cols: usize,
+    index_of: impl Fn(usize, usize) -> usize,
+) -> Vec<SparseDiagonal<'_, T>> {
+    let mut keyed: Vec<(usize, &Triplet<T>)> = entries
+        .iter()
+        .map(|entry| {
+            let (row, col, _) = *entry;
+            assert!(
+                row < rows && col < cols,
+                "entry ({row}, {col}) is outside of a {rows}x{cols} matrix"
+            );
+            (index_of(row, col), entry)
+        })
+        .collect();
+    keyed.sort_by_key(|&(diagonal, &(row, _, _))| (diagonal, row));
+
+    let mut result: Vec<SparseDiagonal<T>> = Vec::new();
+    for (diagonal, entry) in keyed {
+        match result.last_mut() {
+            Some((last, group)) if *last == diagonal => group.push(entry),
+            _ => result.push((diagonal, vec![entry])),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_dense_traversal() {
+        let matrix = [[1, 0, 2, 0], [0, 3, 0, 4], [5, 0, 0, 6]];
+        let entries: Vec<(usize, usize, i32)> = (0..3)
+            .flat_map(|r| (0..4).map(move |c| (r, c)))
+            .filter(|&(r, c)| matrix[r][c] != 0)
+            .map(|(r, c)| (r, c, matrix[r][c]))
+            .rev()
+            .collect();
+
+        let dense = crate::diagonal_pos_pos(&matrix);
+        for (index, group) in diagonals_from_triplets_pos_pos(&entries, 3, 4) {
+            let values: Vec<&i32> = group.iter().map(|(_, _, value)| value).collect();
+            let expected: Vec<&i32> = dense[index].iter().copied().filter(|&&v| v != 0).collect();
+            assert_eq!(values, expected);
+        }
+
+        let dense = crate::diagonal_pos_neg(&matrix);
+        for (index, group) in diagonals_from_triplets_pos_neg(&entries, 3, 4) {
+            let values: Vec<&i32> = group.iter().map(|(_, _, value)| value).collect();
+            let expected: Vec<&i32> = dense[index].iter().copied().filter(|&&v| v != 0).collect();
+            assert_eq!(values, expected);
+        }
+    }
+
+    #[test]
+    fn huge_dimensions() {
+        let n = 1_000_000;
+        let entries = [(n - 1, 0, 1), (0, n - 1, 2), (5, 5, 3), (7, 7, 4)];
+        assert_eq!(
+            diagonals_from_triplets_pos_pos(&entries, n, n),
+            vec![
+                (0, vec![&(n - 1, 0, 1)]),
+                (n - 1, vec![&(5, 5, 3), &(7, 7, 4)]),
+                (2 * n - 2, vec![&(0, n - 1, 2)])
+            ]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let entries: [(usize, usize, u8); 0] = [];
+        assert!(diagonals_from_triplets_pos_pos(&entries, 3, 3).is_empty());
+        assert!(diagonals_from_triplets_pos_neg(&entries, 0, 0).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        diagonals_from_triplets_pos_neg(&[(0, 3, ())], 3, 3);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::index;
+
+/// Summary of the diagonal lengths of a matrix shape, as computed by [`diagonal_stats`].
+///
+/// Both diagonal directions have the same lengths, in mirrored order, so one summary describes
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos) and [`diagonal_pos_neg`](crate::diagonal_pos_neg)
+/// alike.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiagonalStats {
+    /// The number of diagonals.
+    pub count: usize,
+    /// The length of the shortest diagonal, or zero if there are none.
+    pub min_len: usize,
+    /// The length of the longest diagonal, or zero if there are none.
+    pub max_len: usize,
+    /// The average diagonal length, or zero if there are none.
+    pub mean_len: f64,
+    /// The number of diagonals of every length: `histogram[len]` diagonals have `len` elements.
+    /// It has `max_len + 1` entries.
+    pub histogram: Vec<usize>,
+}
+
+impl DiagonalStats {
+    /// Computes the diagonal statistics of an actual matrix, using the first row for the column
+    /// count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use diagonal::{diagonal_stats, DiagonalStats};
+    ///
+    /// let matrix = vec![vec![0; 5]; 2];
+    /// assert_eq!(DiagonalStats::of(&matrix), diagonal_stats(2, 5));
+    /// ```
+    pub fn of<Matrix: AsRef<[Row]> + ?Sized, Row: AsRef<[T]>, T>(matrix: &Matrix) -> Self {
+        let (rows, cols) = index::shape(matrix.as_ref());
+        diagonal_stats(rows, cols)
+    }
+}
+
+/// Computes statistics about the diagonals of a `rows` x `cols` matrix without traversing it.
+///
+/// # Arguments
+///
+/// * `rows` - The number of rows of the matrix.
+/// * `cols` - The number of columns of the matrix.
+///
+/// # Returns
+///
+/// A [`DiagonalStats`] with the number of diagonals, their minimum, maximum and mean length and
+/// a histogram of their lengths.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_stats;
+///
+/// // Diagonal lengths 1, 2, 3, 3, 2, 1.
+/// let stats = diagonal_stats(3, 4);
+/// assert_eq!(stats.count, 6);
+/// assert_eq!((stats.min_len, stats.max_len), (1, 3));
+/// assert_eq!(stats.mean_len, 2.0);
+/// assert_eq!(stats.histogram, vec![0, 2, 2, 2]);
+/// ```
+pub fn diagonal_stats(rows: usize, cols: usize) -> DiagonalStats {
+    let count = index::diagonal_count(rows, cols);
+    if count == 0 {
+        return DiagonalStats {
+            count,
+            min_len: 0,
+            max_len: 0,
+            mean_len: 0.0,
+            histogram: vec![0],
+        };
+    }
+
+    // Lengths rise by one from both ends up to the shorter side, with a plateau in between.
+    let (short, long) = (rows.min(cols), rows.max(cols));
+    let mut histogram = vec![2; short + 1];
+    histogram[0] = 0;
+    histogram[short] = long - short + 1;
+
+    DiagonalStats {
+        count,
+        min_len: 1,
+        max_len: short,
+        mean_len: (rows as f64 * cols as f64) / count as f64,
+        histogram,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_traversal() {
+        for rows in 1..6 {
+            for cols in 1..6 {
+                let matrix = vec![vec![0u8; cols]; rows];
+                let lengths: Vec<usize> = crate::diagonal_pos_neg(&matrix)
+                    .iter()
+                    .map(Vec::len)
+                    .collect();
+                let stats = diagonal_stats(rows, cols);
+
+                assert_eq!(stats.count, lengths.len());
+                assert_eq!(stats.min_len, *lengths.iter().min().unwrap());
+                assert_eq!(stats.max_len, *lengths.iter().max().unwrap());
+                let total: usize = lengths.iter().sum();
+                assert_eq!(stats.mean_len, total as f64 / lengths.len() as f64);
+                for (len, &count) in stats.histogram.iter().enumerate() {
+                    assert_eq!(lengths.iter().filter(|&&l| l == len).count(), count);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn huge_shape() {
+        let stats = diagonal_stats(1_000_000, 3);
+        assert_eq!(stats.count, 1_000_002);
+        assert_eq!(stats.histogram, vec![0, 2, 2, 999_998]);
+    }
+
+    #[test]
+    fn empty() {
+        let stats = diagonal_stats(0, 7);
+        assert_eq!(stats.count, 0);
+        assert_eq!(stats.histogram, vec![0]);
+        assert_eq!(DiagonalStats::of(&Vec::<Vec<u8>>::new()), stats);
+    }
+}
@@ -0,0 +1,243 @@
+//! # Streaming Module
+//!
+//! This module extracts diagonals from matrices that are too large to hold in memory at once.
+//! The matrix is fed to a [`DiagonalStream`] in horizontal strips of any height, and every
+//! diagonal is handed back as soon as its last element has been seen; only the diagonals that
+//! cross the boundary of the current strip are kept in between.
+//!
+//! After row `r` has been pushed, the diagonal with key `r` is complete, so diagonals come out
+//! in increasing key order:
+//!
+//! * [`DiagonalStream::pos_neg`] yields the diagonals of
+//!   [`diagonal_pos_neg`](crate::diagonal_pos_neg) in the same order, keyed by their index.
+//! * [`DiagonalStream::pos_pos`] yields the diagonals of
+//!   [`diagonal_pos_pos`](crate::diagonal_pos_pos) last to first, because the order of that
+//!   function depends on the total number of rows. The key is the index counted from the last
+//!   diagonal.
+//!
+//! In both cases the elements of each diagonal are in the same order as the slice-based
+//! traversals return them.
+//!
+//! ### Example
+//!
+//! ```rust
+//! use diagonal::stream::DiagonalStream;
+//!
+//! let mut stream = DiagonalStream::pos_neg(3);
+//!
+//! let mut diagonals = stream.push_rows(&[[1, 2, 3], [4, 5, 6]]);
+//! assert_eq!(diagonals, vec![(0, vec![1]), (1, vec![2, 4])]);
+//!
+//! diagonals.extend(stream.push_rows(&[[7, 8, 9]]));
+//! diagonals.extend(stream.finish());
+//! assert_eq!(diagonals, vec![
+//!     (0, vec![1]),
+//!     (1, vec![2, 4]),
+//!     (2, vec![3, 5, 7]),
+//!     (3, vec![6, 8]),
+//!     (4, vec![9]),
+//! ]);
+//! ```
+
+use std::collections::VecDeque;
+
+/// Incrementally extracts the diagonals of a matrix that is pushed in horizontal strips.
+#[derive(Debug, Clone)]
+pub struct DiagonalStream<T> {
+    cols: usize,
+    pos_pos: bool,
+    rows: usize,
+    open: VecDeque<Vec<T>>,
+}
+
+impl<T: Clone> DiagonalStream<T> {
+    /// Creates a stream for diagonals with positive slope of a matrix with `cols` columns.
+    ///
+    /// The diagonals are yielded in the reverse order of
+    /// [`diagonal_pos_pos`](crate::diagonal_pos_pos), starting with the top-right one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use diagonal::stream::DiagonalStream;
+    ///
+    /// let mut stream = DiagonalStream::pos_pos(2);
+    ///
+    /// let mut diagonals = stream.push_rows(&[['a', 'b'], ['c', 'd']]);
+    /// diagonals.extend(stream.finish());
+    /// assert_eq!(diagonals, vec![
+    ///     (0, vec!['b']),
+    ///     (1, vec!['a', 'd']),
+    ///     (2, vec!['c']),
+    /// ]);
+    /// ```
+    pub fn pos_pos(cols: usize) -> Self {
+        Self::new(cols, true)
+    }
+
+    /// Creates a stream for diagonals with positive and negative slope of a matrix with `cols`
+    /// columns.
+    ///
+    /// The diagonals are yielded in the same order as
+    /// [`diagonal_pos_neg`](crate::diagonal_pos_neg).
+    pub fn pos_neg(cols: usize) -> Self {
+        Self::new(cols, false)
+    }
+
+    fn new(cols: usize, pos_pos: bool) -> Self {
+        DiagonalStream {
+            cols,
+            pos_pos,
+            rows: 0,
+            open: VecDeque::new(),
+        }
+    }
+
+    /// Returns the number of rows pushed so far.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Pushes the next strip of rows and returns the diagonals it completed.
+    ///
+    /// # Arguments
+    ///
+    /// * `strip` - The next rows of the matrix, each of which must have as many elements as the
+    ///   stream has columns. The strip may be empty.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `(key, diagonal)` pairs, in increasing key order, for every diagonal whose
+    /// last element lies in `strip`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a row has the wrong length.
+    pub fn push_rows<Strip, Row>(&mut self, strip: &Strip) -> Vec<(usize, Vec<T>)>
+    where
+        Strip: AsRef<[Row]> + ?Sized,
+        Row: AsRef<[T]>,
+    {
+        let mut completed = Vec::new();
+        if self.cols == 0 {
+            self.rows += strip.as_ref().len();
+            return completed;
+        }
+
+        for row in strip.as_ref() {
+            let row = row.as_ref();
+            assert_eq!(
+                row.len(),
+                self.cols,
+                "every row of the stream must have {} elements",
+                self.cols
+            );
+
+            // The open diagonals are keyed `rows..rows + cols`; the cell in column `c` belongs
+            // to the `c`-th of them, counted from the back for positive slopes.
+            self.open.resize_with(self.cols, Vec::new);
+            for (col, value) in row.iter().enumerate() {
+                let slot = if self.pos_pos {
+                    self.cols - 1 - col
+                } else {
+                    col
+                };
+                self.open[slot].push(value.clone());
+            }
+
+            let diagonal = self.open.pop_front().unwrap();
+            completed.push((self.rows, diagonal));
+            self.rows += 1;
+        }
+        completed
+    }
+
+    /// Ends the stream and returns the diagonals that were still open.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `(key, diagonal)` pairs, in increasing key order, for every diagonal that
+    /// reaches the last row pushed. It is empty if no rows were pushed.
+    pub fn finish(self) -> Vec<(usize, Vec<T>)> {
+        (self.rows..).zip(self.open).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::owned;
+
+    fn matrix() -> Vec<Vec<i32>> {
+        (0..7)
+            .map(|r| (0..4).map(|c| r * 4 + c).collect())
+            .collect()
+    }
+
+    fn streamed(
+        mut stream: DiagonalStream<i32>,
+        matrix: &[Vec<i32>],
+        height: usize,
+    ) -> Vec<Vec<i32>> {
+        let mut diagonals = Vec::new();
+        for strip in matrix.chunks(height) {
+            diagonals.extend(stream.push_rows(strip));
+        }
+        diagonals.extend(stream.finish());
+
+        let keys: Vec<usize> = diagonals.iter().map(|(key, _)| *key).collect();
+        assert_eq!(keys, (0..diagonals.len()).collect::<Vec<_>>());
+        diagonals
+            .into_iter()
+            .map(|(_, diagonal)| diagonal)
+            .collect()
+    }
+
+    #[test]
+    fn matches_slice_traversals_for_any_strip_height() {
+        let matrix = matrix();
+        let pos_neg = owned(crate::diagonal_pos_neg(&matrix));
+        let mut pos_pos = owned(crate::diagonal_pos_pos(&matrix));
+        pos_pos.reverse();
+
+        for height in 1..=8 {
+            assert_eq!(
+                streamed(DiagonalStream::pos_neg(4), &matrix, height),
+                pos_neg
+            );
+            assert_eq!(
+                streamed(DiagonalStream::pos_pos(4), &matrix, height),
+                pos_pos
+            );
+        }
+    }
+
+    #[test]
+    fn only_boundary_diagonals_stay_open() {
+        let matrix = matrix();
+        let mut stream = DiagonalStream::pos_pos(4);
+        for row in &matrix {
+            assert_eq!(stream.push_rows(&[row]).len(), 1);
+            assert_eq!(stream.open.len(), 3);
+        }
+        assert_eq!(stream.rows(), 7);
+        assert_eq!(stream.finish().len(), 3);
+    }
+
+    #[test]
+    fn empty() {
+        let stream = DiagonalStream::<u8>::pos_neg(3);
+        assert!(stream.finish().is_empty());
+
+        let mut stream = DiagonalStream::<u8>::pos_pos(0);
+        assert!(stream.push_rows(&[[], []]).is_empty());
+        assert_eq!(stream.rows(), 2);
+        assert!(stream.finish().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrong_row_length() {
+        DiagonalStream::pos_neg(3).push_rows(&[[1, 2]]);
+    }
+}
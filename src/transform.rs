@@ -0,0 +1,638 @@
+use std::cmp::Ordering;
+
+use crate::{index, Direction};
+
+/// Maps every diagonal with positive slope (see [`diagonal_pos_pos`](crate::diagonal_pos_pos))
+/// and reassembles the results into a matrix of the original shape.
+///
+/// The closure receives the diagonals in the same order and with the same element order as
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos) returns them and must produce exactly one new
+/// value per element.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `f` - A closure that receives a diagonal and returns its new values.
+///
+/// # Returns
+///
+/// A `Vec<Vec<U>>` with the same shape as `matrix`, where each diagonal holds the values
+/// returned by `f` for it.
+///
+/// # Panics
+///
+/// Panics if `f` returns a different number of values than the diagonal it was given.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::map_diagonals_pos_pos;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// // Subtract the diagonal's first element from every element.
+/// let result = map_diagonals_pos_pos(&matrix, |diagonal| {
+///     diagonal.iter().map(|&&value| value - *diagonal[0]).collect()
+/// });
+/// assert_eq!(result, vec![
+///     vec![0, 0, 0],
+///     vec![0, 4, 4],
+///     vec![0, 4, 8],
+/// ]);
+/// ```
+pub fn map_diagonals_pos_pos<'a, Matrix, Row, T, U, F>(matrix: &'a Matrix, f: F) -> Vec<Vec<U>>
+where
+    Matrix: AsRef<[Row]> + 'a,
+    Row: AsRef<[T]> + 'a,
+    T: 'a,
+    F: FnMut(&[&'a T]) -> Vec<U>,
+{
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    let diagonals = map_cells(matrix, index::pos_pos_cells(rows, cols), f);
+
+    index::reassemble(rows, cols, diagonals, |row, col| {
+        index::pos_pos_index(rows, row, col)
+    })
+}
+
+/// Maps every diagonal with positive and negative slope (see
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg)) and reassembles the results into a matrix of
+/// the original shape.
+///
+/// The closure receives the diagonals in the same order and with the same element order as
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg) returns them and must produce exactly one new
+/// value per element.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `f` - A closure that receives a diagonal and returns its new values.
+///
+/// # Returns
+///
+/// A `Vec<Vec<U>>` with the same shape as `matrix`, where each diagonal holds the values
+/// returned by `f` for it.
+///
+/// # Panics
+///
+/// Panics if `f` returns a different number of values than the diagonal it was given.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::map_diagonals_pos_neg;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// // Replace every element with the length of its diagonal.
+/// let result = map_diagonals_pos_neg(&matrix, |diagonal| vec![diagonal.len(); diagonal.len()]);
+/// assert_eq!(result, vec![
+///     vec![1, 2, 3],
+///     vec![2, 3, 2],
+///     vec![3, 2, 1],
+/// ]);
+/// ```
+pub fn map_diagonals_pos_neg<'a, Matrix, Row, T, U, F>(matrix: &'a Matrix, f: F) -> Vec<Vec<U>>
+where
+    Matrix: AsRef<[Row]> + 'a,
+    Row: AsRef<[T]> + 'a,
+    T: 'a,
+    F: FnMut(&[&'a T]) -> Vec<U>,
+{
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    let diagonals = map_cells(matrix, index::pos_neg_cells(rows, cols), f);
+
+    index::reassemble(rows, cols, diagonals, index::pos_neg_index)
+}
+
+/// Sorts every diagonal with positive and negative slope (see
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg)) in place.
+///
+/// After sorting, reading any diagonal in [`diagonal_pos_neg`](crate::diagonal_pos_neg) order
+/// (from its top-right end towards its bottom-left end) yields its elements in ascending order.
+/// The sort is stable.
+///
+/// # Arguments
+///
+/// * `matrix` - A mutable reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::sort_diagonals;
+///
+/// let mut matrix = vec![
+///     vec![9, 8, 7],
+///     vec![6, 5, 4],
+///     vec![3, 2, 1],
+/// ];
+///
+/// sort_diagonals(&mut matrix);
+/// assert_eq!(matrix, vec![
+///     vec![9, 6, 3],
+///     vec![8, 5, 2],
+///     vec![7, 4, 1],
+/// ]);
+/// ```
+pub fn sort_diagonals<Matrix, Row, T>(matrix: &mut Matrix)
+where
+    Matrix: AsMut<[Row]> + ?Sized,
+    Row: AsMut<[T]>,
+    T: Ord,
+{
+    sort_diagonals_by(matrix, T::cmp);
+}
+
+/// Sorts every diagonal with positive and negative slope (see
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg)) in place using a comparator function.
+///
+/// This is the comparator-based counterpart of [`sort_diagonals`]. The sort is stable.
+///
+/// # Arguments
+///
+/// * `matrix` - A mutable reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `compare` - A comparator that defines the order of the elements along each diagonal.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::sort_diagonals_by;
+///
+/// let mut matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// // Sort every diagonal in descending order.
+/// sort_diagonals_by(&mut matrix, |a, b| b.cmp(a));
+/// assert_eq!(matrix, vec![
+///     vec![1, 4, 7],
+///     vec![2, 5, 8],
+///     vec![3, 6, 9],
+/// ]);
+/// ```
+pub fn sort_diagonals_by<Matrix, Row, T, F>(matrix: &mut Matrix, mut compare: F)
+where
+    Matrix: AsMut<[Row]> + ?Sized,
+    Row: AsMut<[T]>,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let matrix = matrix.as_mut();
+    let (rows, cols) = shape_mut(matrix);
+
+    for index in 0..index::diagonal_count(rows, cols) {
+        let mut cells = diagonal_mut(matrix, Direction::DiagonalPosNeg, index);
+
+        let mut order: Vec<usize> = (0..cells.len()).collect();
+        order.sort_by(|&a, &b| compare(cells[a], cells[b]));
+
+        // Position `i` receives the element that started at `order[i]`; earlier swaps may have
+        // moved that element, so follow the permutation until it points at or after `i`.
+        for i in 0..order.len() {
+            let mut source = order[i];
+            while source < i {
+                source = order[source];
+            }
+            swap_cells(&mut cells, i, source);
+        }
+    }
+}
+
+/// Rotates every diagonal with positive slope (see [`diagonal_pos_pos`](crate::diagonal_pos_pos))
+/// cyclically by `k` positions in place.
+///
+/// The element at position `i` of a diagonal moves to position `(i + k) mod len`, so a positive
+/// `k` moves elements towards the bottom-right and a negative `k` towards the top-left.
+/// Single-element diagonals are left untouched.
+///
+/// # Arguments
+///
+/// * `matrix` - A mutable reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `k` - The number of positions to rotate by.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::shift_diagonals_pos_pos;
+///
+/// let mut matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// shift_diagonals_pos_pos(&mut matrix, 1);
+/// assert_eq!(matrix, vec![
+///     vec![9, 6, 3],
+///     vec![8, 1, 2],
+///     vec![7, 4, 5],
+/// ]);
+/// ```
+pub fn shift_diagonals_pos_pos<Matrix, Row, T>(matrix: &mut Matrix, k: isize)
+where
+    Matrix: AsMut<[Row]> + ?Sized,
+    Row: AsMut<[T]>,
+{
+    shift(matrix.as_mut(), Direction::DiagonalPosPos, k);
+}
+
+/// Rotates every diagonal with positive and negative slope (see
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg)) cyclically by `k` positions in place.
+///
+/// The element at position `i` of a diagonal moves to position `(i + k) mod len`, so a positive
+/// `k` moves elements towards the bottom-left and a negative `k` towards the top-right.
+/// Single-element diagonals are left untouched.
+///
+/// # Arguments
+///
+/// * `matrix` - A mutable reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `k` - The number of positions to rotate by.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::shift_diagonals_pos_neg;
+///
+/// let mut matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// shift_diagonals_pos_neg(&mut matrix, -1);
+/// assert_eq!(matrix, vec![
+///     vec![1, 4, 5],
+///     vec![2, 7, 8],
+///     vec![3, 6, 9],
+/// ]);
+/// ```
+pub fn shift_diagonals_pos_neg<Matrix, Row, T>(matrix: &mut Matrix, k: isize)
+where
+    Matrix: AsMut<[Row]> + ?Sized,
+    Row: AsMut<[T]>,
+{
+    shift(matrix.as_mut(), Direction::DiagonalPosNeg, k);
+}
+
+/// Rotates every diagonal in `direction` by `k` positions.
+fn shift<Row: AsMut<[T]>, T>(matrix: &mut [Row], direction: Direction, k: isize) {
+    let (rows, cols) = shape_mut(matrix);
+
+    for index in 0..direction.line_count(rows, cols) {
+        let mut cells = diagonal_mut(matrix, direction, index);
+        let len = cells.len();
+        let right = index::wrap(k, len);
+
+        // Rotating right by `r` is reversing the whole diagonal, then both of its parts.
+        reverse_cells(&mut cells, 0, len);
+        reverse_cells(&mut cells, 0, right);
+        reverse_cells(&mut cells, right, len);
+    }
+}
+
+/// Reverses the values behind `cells[start..end]`.
+fn reverse_cells<T>(cells: &mut [&mut T], start: usize, end: usize) {
+    for i in 0..(end - start) / 2 {
+        swap_cells(cells, start + i, end - 1 - i);
+    }
+}
+
+/// Visits every element mutably in [`diagonal_pos_pos`](crate::diagonal_pos_pos) order, together
+/// with its position.
+///
+/// Diagonals are visited one after another, and the elements of each diagonal from its first to
+/// its last cell, so every element is visited after its upper-left neighbor.
+///
+/// # Arguments
+///
+/// * `matrix` - A mutable reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `f` - A closure that receives the diagonal's index, the element's position within the
+///   diagonal, its `(row, column)` coordinate and a mutable reference to it.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::apply_diagonal_pos_pos;
+///
+/// let mut matrix = vec![vec![0; 3]; 2];
+///
+/// apply_diagonal_pos_pos(&mut matrix, |diagonal, position, _, value| {
+///     *value = diagonal * 10 + position;
+/// });
+/// assert_eq!(matrix, vec![
+///     vec![10, 20, 30],
+///     vec![0, 11, 21],
+/// ]);
+/// ```
+pub fn apply_diagonal_pos_pos<Matrix, Row, T, F>(matrix: &mut Matrix, f: F)
+where
+    Matrix: AsMut<[Row]> + ?Sized,
+    Row: AsMut<[T]>,
+    F: FnMut(usize, usize, (usize, usize), &mut T),
+{
+    apply(matrix.as_mut(), Direction::DiagonalPosPos, f);
+}
+
+/// Visits every element mutably in [`diagonal_pos_neg`](crate::diagonal_pos_neg) order, together
+/// with its position.
+///
+/// Diagonals are visited one after another, and the elements of each diagonal from its
+/// top-right to its bottom-left end. Every element is therefore visited after its upper, left
+/// and upper-left neighbors, which makes this suitable for filling dynamic programming tables
+/// (edit distance, sequence alignment) in wavefront order.
+///
+/// # Arguments
+///
+/// * `matrix` - A mutable reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `f` - A closure that receives the diagonal's index, the element's position within the
+///   diagonal, its `(row, column)` coordinate and a mutable reference to it.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::apply_diagonal_pos_neg;
+///
+/// let mut order = vec![vec![0; 3]; 3];
+/// let mut step = 0;
+///
+/// apply_diagonal_pos_neg(&mut order, |diagonal, _, (row, col), value| {
+///     assert_eq!(diagonal, row + col);
+///     *value = step;
+///     step += 1;
+/// });
+/// assert_eq!(order, vec![
+///     vec![0, 1, 3],
+///     vec![2, 4, 6],
+///     vec![5, 7, 8],
+/// ]);
+/// ```
+pub fn apply_diagonal_pos_neg<Matrix, Row, T, F>(matrix: &mut Matrix, f: F)
+where
+    Matrix: AsMut<[Row]> + ?Sized,
+    Row: AsMut<[T]>,
+    F: FnMut(usize, usize, (usize, usize), &mut T),
+{
+    apply(matrix.as_mut(), Direction::DiagonalPosNeg, f);
+}
+
+/// Calls `f` on every element in the order of `direction`, with its line, position and
+/// coordinate.
+fn apply<Row, T, F>(matrix: &mut [Row], direction: Direction, mut f: F)
+where
+    Row: AsMut<[T]>,
+    F: FnMut(usize, usize, (usize, usize), &mut T),
+{
+    let (rows, cols) = shape_mut(matrix);
+    for index in 0..direction.line_count(rows, cols) {
+        let (start, len) = direction.line_start(rows, cols, index);
+        for position in 0..len {
+            let (x, y) = direction.cell(start, position);
+            f(index, position, (x, y), &mut matrix[x].as_mut()[y]);
+        }
+    }
+}
+
+/// Returns the `(rows, columns)` shape of a mutable matrix, using the first row for the column
+/// count.
+fn shape_mut<Row: AsMut<[T]>, T>(matrix: &mut [Row]) -> (usize, usize) {
+    let cols = matrix.first_mut().map_or(0, |row| row.as_mut().len());
+    (matrix.len(), cols)
+}
+
+/// Mutable references to the elements of the `index`-th diagonal in `direction`.
+///
+/// Every diagonal holds at most one cell per row, so the rows can be borrowed one by one.
+fn diagonal_mut<Row: AsMut<[T]>, T>(
+    matrix: &mut [Row],
+    direction: Direction,
+    index: usize,
+) -> Vec<&mut T> {
+    let (rows, cols) = shape_mut(matrix);
+    let (start, len) = direction.line_start(rows, cols, index);
+
+    matrix[start.0..start.0 + len]
+        .iter_mut()
+        .enumerate()
+        .map(|(i, row)| &mut row.as_mut()[direction.cell(start, i).1])
+        .collect()
+}
+
+/// Swaps the values behind two of the references returned by [`diagonal_mut`].
+fn swap_cells<T>(cells: &mut [&mut T], a: usize, b: usize) {
+    if a == b {
+        return;
+    }
+    let (low, high) = (a.min(b), a.max(b));
+    let (head, tail) = cells.split_at_mut(high);
+    std::mem::swap(&mut *head[low], &mut *tail[0]);
+}
+
+fn map_cells<'a, Row, T, U, F>(
+    matrix: &'a [Row],
+    cells: impl Iterator<Item = impl Iterator<Item = (usize, usize)>>,
+    mut f: F,
+) -> Vec<Vec<U>>
+where
+    Row: AsRef<[T]>,
+    T: 'a,
+    F: FnMut(&[&'a T]) -> Vec<U>,
+{
+    cells
+        .map(|diagonal| {
+            let diagonal: Vec<&T> = diagonal.map(|(x, y)| &matrix[x].as_ref()[y]).collect();
+            let mapped = f(&diagonal);
+            assert_eq!(
+                mapped.len(),
+                diagonal.len(),
+                "mapped diagonal must have the same length as the original"
+            );
+            mapped
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_pos_pos_two_by_three() {
+        let matrix = [[1, 2, 3], [4, 5, 6]];
+        let result = map_diagonals_pos_pos(&matrix, |diagonal| {
+            let sum: i32 = diagonal.iter().copied().sum();
+            vec![sum; diagonal.len()]
+        });
+        assert_eq!(result, vec![vec![6, 8, 3], vec![4, 6, 8]]);
+    }
+
+    #[test]
+    fn map_pos_neg_reverses_diagonals() {
+        let matrix = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let result = map_diagonals_pos_neg(&matrix, |diagonal| {
+            diagonal.iter().rev().map(|&&value| value).collect()
+        });
+        assert_eq!(result, vec![vec![1, 4, 5], vec![2, 3, 6]]);
+    }
+
+    #[test]
+    fn map_empty() {
+        let matrix: Vec<Vec<usize>> = vec![];
+        assert_eq!(
+            map_diagonals_pos_pos(&matrix, |d| d.to_vec()),
+            Vec::<Vec<&usize>>::new()
+        );
+        assert_eq!(
+            map_diagonals_pos_neg(&matrix, |d| d.to_vec()),
+            Vec::<Vec<&usize>>::new()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn map_wrong_length() {
+        let matrix = [[1, 2], [3, 4]];
+        map_diagonals_pos_pos(&matrix, |_| vec![0]);
+    }
+
+    #[test]
+    fn sort_two_by_three() {
+        let mut matrix = vec![vec![6, 5, 4], vec![3, 2, 1]];
+        sort_diagonals(&mut matrix);
+        assert_eq!(matrix, vec![vec![6, 3, 2], vec![5, 4, 1]]);
+
+        let mut matrix = [[6, 5, 4], [3, 2, 1]];
+        sort_diagonals(&mut matrix);
+        assert_eq!(matrix, [[6, 3, 2], [5, 4, 1]]);
+    }
+
+    #[test]
+    fn sort_by_is_stable() {
+        let mut matrix = [
+            [(0, 'a'), (1, 'b'), (1, 'c')],
+            [(0, 'd'), (1, 'e'), (0, 'f')],
+        ];
+        sort_diagonals_by(&mut matrix, |a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            matrix,
+            [
+                [(0, 'a'), (0, 'd'), (1, 'c')],
+                [(1, 'b'), (1, 'e'), (0, 'f')]
+            ]
+        );
+    }
+
+    #[test]
+    fn sort_longer_diagonal() {
+        let mut matrix = [[3, 1, 4, 1, 5]; 5];
+        sort_diagonals(&mut matrix);
+        let diagonals = crate::diagonal_pos_neg(&matrix);
+        for diagonal in diagonals {
+            assert!(diagonal.windows(2).all(|pair| pair[0] <= pair[1]));
+        }
+    }
+
+    #[test]
+    fn apply_visits_in_traversal_order() {
+        let mut matrix = [[0; 4]; 3];
+        let mut seen = Vec::new();
+        apply_diagonal_pos_pos(&mut matrix, |diagonal, position, coord, value| {
+            *value = seen.len();
+            seen.push((diagonal, position, coord));
+        });
+
+        let expected: Vec<_> = index::pos_pos_cells(3, 4)
+            .enumerate()
+            .flat_map(|(diagonal, cells)| {
+                cells
+                    .enumerate()
+                    .map(move |(position, coord)| (diagonal, position, coord))
+            })
+            .collect();
+        assert_eq!(seen, expected);
+        for (step, &(_, _, (x, y))) in seen.iter().enumerate() {
+            assert_eq!(matrix[x][y], step);
+        }
+    }
+
+    #[test]
+    fn apply_pos_neg_is_a_wavefront() {
+        let mut step = [[0; 5]; 4];
+        let mut next = 0;
+        apply_diagonal_pos_neg(&mut step, |diagonal, _, (x, y), value| {
+            assert_eq!(diagonal, x + y);
+            *value = next;
+            next += 1;
+        });
+
+        for x in 1..4 {
+            for y in 1..5 {
+                assert!(step[x - 1][y] < step[x][y]);
+                assert!(step[x][y - 1] < step[x][y]);
+                assert!(step[x - 1][y - 1] < step[x][y]);
+            }
+        }
+    }
+
+    #[test]
+    fn shift_matches_rotated_traversal() {
+        let original = [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]];
+        for k in [-7, -4, -1, 0, 1, 2, 3, 5, isize::MIN, isize::MAX] {
+            let mut matrix = original;
+            shift_diagonals_pos_pos(&mut matrix, k);
+            for (shifted, diagonal) in crate::diagonal_pos_pos(&matrix)
+                .into_iter()
+                .zip(crate::diagonal_pos_pos(&original))
+            {
+                let len = diagonal.len() as i128;
+                let right = (k as i128).rem_euclid(len) as usize;
+                let mut expected = diagonal;
+                expected.rotate_right(right);
+                assert_eq!(shifted, expected, "k = {k}");
+            }
+
+            if let Some(back) = k.checked_neg() {
+                let mut matrix = original;
+                shift_diagonals_pos_neg(&mut matrix, k);
+                shift_diagonals_pos_neg(&mut matrix, back);
+                assert_eq!(matrix, original, "k = {k}");
+            }
+        }
+    }
+
+    #[test]
+    fn shift_empty() {
+        let mut matrix: Vec<Vec<u8>> = vec![vec![], vec![]];
+        shift_diagonals_pos_pos(&mut matrix, 3);
+        assert_eq!(matrix, vec![Vec::<u8>::new(); 2]);
+    }
+
+    #[test]
+    fn sort_empty() {
+        let mut matrix: Vec<Vec<usize>> = vec![];
+        sort_diagonals(&mut matrix);
+        assert!(matrix.is_empty());
+
+        let mut matrix: Vec<Vec<usize>> = vec![vec![]];
+        sort_diagonals(&mut matrix);
+        assert_eq!(matrix, vec![Vec::<usize>::new()]);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::index;
+
+/// Extracts the diagonals of a staircase (triangular) matrix.
+///
+/// A staircase is a jagged, left-aligned matrix in which every row is exactly one element longer
+/// than the previous one, like Pascal's triangle, or exactly one element shorter. A single row
+/// counts as a staircase of either kind.
+///
+/// The diagonals returned are the ones parallel to the staircase's slanted edge, which is the
+/// only diagonal direction in which the steps never cut a diagonal short:
+///
+/// * If the rows grow, the diagonals run down and to the right, like those of
+///   [`diagonal_pos_pos`](crate::diagonal_pos_pos), and are ordered the same way: the first one
+///   is the single cell at the start of the last row and the last one is the slanted edge. For a
+///   triangle with rows of lengths `1, 2, ..., n`, the `i`-th diagonal has `i + 1` elements.
+/// * If the rows shrink, the diagonals run down and to the left, like those of
+///   [`diagonal_pos_neg`](crate::diagonal_pos_neg), and are ordered the same way: the first one
+///   is the first cell of the first row and the last one is the slanted edge. For a triangle
+///   with rows of lengths `n, ..., 2, 1`, the `i`-th diagonal has `i + 1` elements.
+///
+/// In both cases the elements of every diagonal are ordered from the top row down.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a staircase represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A vector of vectors containing references to the elements along the diagonals.
+///
+/// # Panics
+///
+/// Panics if consecutive rows do not all differ in length by one in the same direction.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::triangle_diagonals;
+///
+/// let pascal = vec![
+///     vec![1],
+///     vec![1, 1],
+///     vec![1, 2, 1],
+///     vec![1, 3, 3, 1],
+/// ];
+///
+/// assert_eq!(triangle_diagonals(&pascal), vec![
+///     vec![&1],
+///     vec![&1, &3],
+///     vec![&1, &2, &3],
+///     vec![&1, &1, &1, &1],
+/// ]);
+///
+/// let shrinking = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5],
+///     vec![6],
+/// ];
+///
+/// assert_eq!(triangle_diagonals(&shrinking), vec![
+///     vec![&1],
+///     vec![&2, &4],
+///     vec![&3, &5, &6],
+/// ]);
+/// ```
+pub fn triangle_diagonals<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Vec<Vec<&'a T>> {
+    let matrix = matrix.as_ref();
+    let (rows, first) = index::shape(matrix);
+    let lengths: Vec<usize> = matrix.iter().map(|row| row.as_ref().len()).collect();
+    let growing = lengths
+        .windows(2)
+        .all(|pair| pair[1].checked_sub(pair[0]) == Some(1));
+    assert!(
+        growing
+            || lengths
+                .windows(2)
+                .all(|pair| pair[0].checked_sub(pair[1]) == Some(1)),
+        "rows must form a staircase"
+    );
+
+    // Both kinds number their diagonals without gaps, so every cell can be dropped into its
+    // diagonal's bucket while walking the rows top to bottom.
+    let count = match (growing, rows) {
+        (_, 0) => 0,
+        (true, _) => (rows + first).saturating_sub(1),
+        (false, _) => first,
+    };
+    let mut result: Vec<Vec<&T>> = vec![vec![]; count];
+    for (row, elements) in matrix.iter().enumerate() {
+        for (col, element) in elements.as_ref().iter().enumerate() {
+            let diagonal = if growing {
+                index::pos_pos_index(rows, row, col)
+            } else {
+                index::pos_neg_index(row, col)
+            };
+            result[diagonal].push(element);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn staircase(lengths: impl Iterator<Item = usize>) -> Vec<Vec<(usize, usize)>> {
+        lengths
+            .enumerate()
+            .map(|(row, len)| (0..len).map(|col| (row, col)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn growing_diagonals_run_to_the_last_row() {
+        for first in 0..4 {
+            let tri = staircase(first..first + 5);
+            let diagonals = triangle_diagonals(&tri);
+            assert_eq!(
+                diagonals.iter().map(|d| d.len()).sum::<usize>(),
+                tri.iter().map(Vec::len).sum::<usize>()
+            );
+            for diagonal in &diagonals {
+                assert!(!diagonal.is_empty());
+                assert_eq!(diagonal.last().unwrap().0, 4);
+                for pair in diagonal.windows(2) {
+                    assert_eq!((pair[1].0, pair[1].1), (pair[0].0 + 1, pair[0].1 + 1));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shrinking_diagonals_start_in_the_first_row() {
+        for last in 0..4 {
+            let tri = staircase((last..last + 5).rev());
+            let diagonals = triangle_diagonals(&tri);
+            assert_eq!(diagonals.len(), tri[0].len());
+            for (i, diagonal) in diagonals.iter().enumerate() {
+                assert_eq!(*diagonal[0], (0, i));
+                for pair in diagonal.windows(2) {
+                    assert_eq!((pair[1].0, pair[1].1 + 1), (pair[0].0 + 1, pair[0].1));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn single_row_and_empty() {
+        let row = [[1, 2, 3]];
+        assert_eq!(triangle_diagonals(&row), vec![vec![&1], vec![&2], vec![&3]]);
+        assert!(triangle_diagonals(&[[0u8; 0]]).is_empty());
+        assert!(triangle_diagonals(&Vec::<Vec<u8>>::new()).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn rectangular() {
+        triangle_diagonals(&[[1, 2], [3, 4]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mixed_steps() {
+        triangle_diagonals(&vec![vec![1], vec![2, 3], vec![4]]);
+    }
+}
@@ -0,0 +1,694 @@
+use crate::{index, lines_iter, straight_y, Direction};
+
+/// Transposes a matrix into a view of references.
+///
+/// The `i`-th row of the result is the `i`-th column of `matrix`. This is the same traversal as
+/// [`straight_y`](crate::straight_y), exposed under the name most code looks for.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<Vec<&T>>` containing references to the elements of the transposed matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::transpose;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ];
+///
+/// let result = transpose(&matrix);
+/// assert_eq!(result, vec![
+///     vec![&1, &4],
+///     vec![&2, &5],
+///     vec![&3, &6],
+/// ]);
+/// ```
+pub fn transpose<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Vec<Vec<&'a T>> {
+    straight_y(matrix)
+}
+
+/// Transposes a matrix into a new owned matrix.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<Vec<T>>` containing clones of the elements of the transposed matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::transposed;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ];
+///
+/// let result = transposed(&matrix);
+/// assert_eq!(result, vec![
+///     vec![1, 4],
+///     vec![2, 5],
+///     vec![3, 6],
+/// ]);
+/// ```
+pub fn transposed<Matrix: AsRef<[Row]>, Row: AsRef<[T]>, T: Clone>(matrix: &Matrix) -> Vec<Vec<T>> {
+    cloned(transpose(matrix))
+}
+
+/// Rotates a matrix by 90 degrees clockwise into a view of references.
+///
+/// The `i`-th row of the result is the `i`-th column of `matrix`, read from bottom to top.
+/// No elements are copied, so the view can be searched or passed to the other traversals
+/// without cloning the matrix.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<Vec<&T>>` containing references to the elements of the rotated matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::rotate90;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ];
+///
+/// let result = rotate90(&matrix);
+/// assert_eq!(result, vec![
+///     vec![&4, &1],
+///     vec![&5, &2],
+///     vec![&6, &3],
+/// ]);
+/// ```
+pub fn rotate90<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Vec<Vec<&'a T>> {
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    remap(matrix, cols, rows, move |i, j| (rows - 1 - j, i))
+}
+
+/// Rotates a matrix by 180 degrees into a view of references.
+///
+/// The result holds the rows of `matrix` from bottom to top, each read from right to left.
+/// No elements are copied, so the view can be searched or passed to the other traversals
+/// without cloning the matrix.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<Vec<&T>>` containing references to the elements of the rotated matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::rotate180;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ];
+///
+/// let result = rotate180(&matrix);
+/// assert_eq!(result, vec![
+///     vec![&6, &5, &4],
+///     vec![&3, &2, &1],
+/// ]);
+/// ```
+pub fn rotate180<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Vec<Vec<&'a T>> {
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    remap(matrix, rows, cols, move |i, j| (rows - 1 - i, cols - 1 - j))
+}
+
+/// Rotates a matrix by 270 degrees clockwise (90 degrees counterclockwise) into a view of
+/// references.
+///
+/// The `i`-th row of the result is the `i`-th column of `matrix` counted from the right, read
+/// from top to bottom.
+/// No elements are copied, so the view can be searched or passed to the other traversals
+/// without cloning the matrix.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<Vec<&T>>` containing references to the elements of the rotated matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::rotate270;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ];
+///
+/// let result = rotate270(&matrix);
+/// assert_eq!(result, vec![
+///     vec![&3, &6],
+///     vec![&2, &5],
+///     vec![&1, &4],
+/// ]);
+/// ```
+pub fn rotate270<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Vec<Vec<&'a T>> {
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    remap(matrix, cols, rows, move |i, j| (j, cols - 1 - i))
+}
+
+/// Mirrors a matrix horizontally (left to right) into a view of references.
+///
+/// Every row of the result is the corresponding row of `matrix` read from right to left.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<Vec<&T>>` containing references to the elements of the mirrored matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::flip_horizontal;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ];
+///
+/// let result = flip_horizontal(&matrix);
+/// assert_eq!(result, vec![
+///     vec![&3, &2, &1],
+///     vec![&6, &5, &4],
+/// ]);
+/// ```
+pub fn flip_horizontal<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Vec<Vec<&'a T>> {
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    remap(matrix, rows, cols, move |i, j| (i, cols - 1 - j))
+}
+
+/// Mirrors a matrix vertically (top to bottom) into a view of references.
+///
+/// The result holds the rows of `matrix` from bottom to top.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A `Vec<Vec<&T>>` containing references to the elements of the mirrored matrix.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::flip_vertical;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+/// ];
+///
+/// let result = flip_vertical(&matrix);
+/// assert_eq!(result, vec![
+///     vec![&4, &5, &6],
+///     vec![&1, &2, &3],
+/// ]);
+/// ```
+pub fn flip_vertical<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Vec<Vec<&'a T>> {
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    remap(matrix, rows, cols, move |i, j| (rows - 1 - i, j))
+}
+
+/// Extracts the [`diagonal_pos_pos`](crate::diagonal_pos_pos) diagonals of the horizontally mirrored
+/// matrix.
+///
+/// This returns the same elements in the same order as calling
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos) on [`flip_horizontal`], but without building the
+/// intermediate view and with references straight into `matrix`.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A vector of vectors containing references to the elements of the mirrored diagonals.
+/// Each inner vector represents a diagonal.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_pos_pos_flip_horizontal;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_pos_pos_flip_horizontal(&matrix);
+/// assert_eq!(result, vec![vec![&9], vec![&6, &8], vec![&3, &5, &7], vec![&2, &4], vec![&1]]);
+/// ```
+pub fn diagonal_pos_pos_flip_horizontal<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Vec<Vec<&'a T>> {
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    mirrored(matrix, index::pos_pos_cells(rows, cols), |(x, y)| {
+        (x, cols - 1 - y)
+    })
+}
+
+/// Extracts the [`diagonal_pos_pos`](crate::diagonal_pos_pos) diagonals of the vertically mirrored
+/// matrix.
+///
+/// This returns the same elements in the same order as calling
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos) on [`flip_vertical`], but without building the
+/// intermediate view and with references straight into `matrix`.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A vector of vectors containing references to the elements of the mirrored diagonals.
+/// Each inner vector represents a diagonal.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_pos_pos_flip_vertical;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_pos_pos_flip_vertical(&matrix);
+/// assert_eq!(result, vec![vec![&1], vec![&4, &2], vec![&7, &5, &3], vec![&8, &6], vec![&9]]);
+/// ```
+pub fn diagonal_pos_pos_flip_vertical<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Vec<Vec<&'a T>> {
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    mirrored(matrix, index::pos_pos_cells(rows, cols), |(x, y)| {
+        (rows - 1 - x, y)
+    })
+}
+
+/// Extracts the [`diagonal_pos_neg`](crate::diagonal_pos_neg) diagonals of the horizontally mirrored
+/// matrix.
+///
+/// This returns the same elements in the same order as calling
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg) on [`flip_horizontal`], but without building the
+/// intermediate view and with references straight into `matrix`.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A vector of vectors containing references to the elements of the mirrored diagonals.
+/// Each inner vector represents a diagonal.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_pos_neg_flip_horizontal;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_pos_neg_flip_horizontal(&matrix);
+/// assert_eq!(result, vec![vec![&3], vec![&2, &6], vec![&1, &5, &9], vec![&4, &8], vec![&7]]);
+/// ```
+pub fn diagonal_pos_neg_flip_horizontal<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Vec<Vec<&'a T>> {
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    mirrored(matrix, index::pos_neg_cells(rows, cols), |(x, y)| {
+        (x, cols - 1 - y)
+    })
+}
+
+/// Extracts the [`diagonal_pos_neg`](crate::diagonal_pos_neg) diagonals of the vertically mirrored
+/// matrix.
+///
+/// This returns the same elements in the same order as calling
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg) on [`flip_vertical`], but without building the
+/// intermediate view and with references straight into `matrix`.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A vector of vectors containing references to the elements of the mirrored diagonals.
+/// Each inner vector represents a diagonal.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_pos_neg_flip_vertical;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_pos_neg_flip_vertical(&matrix);
+/// assert_eq!(result, vec![vec![&7], vec![&8, &4], vec![&9, &5, &1], vec![&6, &2], vec![&3]]);
+/// ```
+pub fn diagonal_pos_neg_flip_vertical<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Vec<Vec<&'a T>> {
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    mirrored(matrix, index::pos_neg_cells(rows, cols), |(x, y)| {
+        (rows - 1 - x, y)
+    })
+}
+
+/// Extracts diagonals with positive slope like [`diagonal_pos_pos`](crate::diagonal_pos_pos), but
+/// with the elements of every diagonal in the opposite order.
+///
+/// The diagonals keep their order; each one is read from its top-right end towards its
+/// bottom-left end. This is the same as reversing every inner vector of
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos), without the extra pass.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A vector of vectors containing references to the elements in diagonals with positive slope.
+/// Each inner vector represents a diagonal, read from top-right to bottom-left.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_pos_pos_reversed;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_pos_pos_reversed(&matrix);
+/// assert_eq!(result, vec![vec![&7], vec![&8, &4], vec![&9, &5, &1], vec![&6, &2], vec![&3]]);
+/// ```
+pub fn diagonal_pos_pos_reversed<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Vec<Vec<&'a T>> {
+    reversed(matrix.as_ref(), Direction::DiagonalPosPos)
+}
+
+/// Extracts diagonals with positive and negative slope like
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg), but with the elements of every diagonal in the
+/// opposite order.
+///
+/// The diagonals keep their order; each one is read from its bottom-left end towards its
+/// top-right end. This is the same as reversing every inner vector of
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg), without the extra pass.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+///
+/// # Returns
+///
+/// A vector of vectors containing references to the elements in diagonals with positive and
+/// negative slope. Each inner vector represents a diagonal, read from bottom-left to top-right.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::diagonal_pos_neg_reversed;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3],
+///     vec![4, 5, 6],
+///     vec![7, 8, 9],
+/// ];
+///
+/// let result = diagonal_pos_neg_reversed(&matrix);
+/// assert_eq!(result, vec![vec![&1], vec![&4, &2], vec![&7, &5, &3], vec![&8, &6], vec![&9]]);
+/// ```
+pub fn diagonal_pos_neg_reversed<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+) -> Vec<Vec<&'a T>> {
+    reversed(matrix.as_ref(), Direction::DiagonalPosNeg)
+}
+
+/// Collects the lines in `direction`, each walked from its last element to its first.
+fn reversed<Row: AsRef<[T]>, T>(matrix: &[Row], direction: Direction) -> Vec<Vec<&T>> {
+    lines_iter(matrix, direction)
+        .map(|line| line.rev().collect())
+        .collect()
+}
+
+/// Collects the lines of `cells`, with every cell replaced by its mirror image.
+fn mirrored<Row: AsRef<[T]>, T>(
+    matrix: &[Row],
+    cells: impl Iterator<Item = impl Iterator<Item = (usize, usize)>>,
+    mirror: impl Fn((usize, usize)) -> (usize, usize),
+) -> Vec<Vec<&T>> {
+    cells
+        .map(|line| {
+            line.map(|cell| {
+                let (x, y) = mirror(cell);
+                &matrix[x].as_ref()[y]
+            })
+            .collect()
+        })
+        .collect()
+}
+
+/// Builds an `out_rows` x `out_cols` view whose cell `(i, j)` refers to `matrix[source(i, j)]`.
+fn remap<Row: AsRef<[T]>, T>(
+    matrix: &[Row],
+    out_rows: usize,
+    out_cols: usize,
+    source: impl Fn(usize, usize) -> (usize, usize),
+) -> Vec<Vec<&T>> {
+    (0..out_rows)
+        .map(|i| {
+            (0..out_cols)
+                .map(|j| {
+                    let (x, y) = source(i, j);
+                    &matrix[x].as_ref()[y]
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Clones every element of a view into an owned matrix.
+fn cloned<T: Clone>(view: Vec<Vec<&T>>) -> Vec<Vec<T>> {
+    view.into_iter()
+        .map(|row| row.into_iter().cloned().collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transpose_two_by_three() {
+        let matrix = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        assert_eq!(
+            transpose(&matrix),
+            vec![vec![&1, &4], vec![&2, &5], vec![&3, &6]]
+        );
+
+        let matrix = [[1, 2, 3], [4, 5, 6]];
+        assert_eq!(
+            transposed(&matrix),
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]]
+        );
+    }
+
+    #[test]
+    fn transposed_twice_is_identity() {
+        let matrix = vec![vec!['a', 'b'], vec!['c', 'd'], vec!['e', 'f']];
+        assert_eq!(transposed(&transposed(&matrix)), matrix);
+    }
+
+    #[test]
+    fn rotate_three_by_two() {
+        let matrix = [[1, 2], [3, 4], [5, 6]];
+        assert_eq!(rotate90(&matrix), vec![vec![&5, &3, &1], vec![&6, &4, &2]]);
+        assert_eq!(
+            rotate180(&matrix),
+            vec![vec![&6, &5], vec![&4, &3], vec![&2, &1]]
+        );
+        assert_eq!(rotate270(&matrix), vec![vec![&2, &4, &6], vec![&1, &3, &5]]);
+    }
+
+    #[test]
+    fn rotate_compose() {
+        let matrix = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let once: Vec<Vec<i32>> = cloned(rotate90(&matrix));
+        assert_eq!(cloned(rotate90(&once)), cloned(rotate180(&matrix)));
+        assert_eq!(cloned(rotate270(&once)), matrix);
+    }
+
+    #[test]
+    fn rotate_empty() {
+        let matrix: Vec<Vec<usize>> = vec![];
+        assert_eq!(rotate90(&matrix), Vec::<Vec<&usize>>::new());
+        assert_eq!(rotate180(&matrix), Vec::<Vec<&usize>>::new());
+        assert_eq!(rotate270(&matrix), Vec::<Vec<&usize>>::new());
+    }
+
+    #[test]
+    fn flip_three_by_two() {
+        let matrix = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
+        assert_eq!(
+            flip_horizontal(&matrix),
+            vec![vec![&2, &1], vec![&4, &3], vec![&6, &5]]
+        );
+        assert_eq!(
+            flip_vertical(&matrix),
+            vec![vec![&5, &6], vec![&3, &4], vec![&1, &2]]
+        );
+    }
+
+    #[test]
+    fn flipped_diagonals_match_views() {
+        use crate::{diagonal_pos_neg, diagonal_pos_pos};
+
+        let matrix = [[1, 2, 3, 4], [5, 6, 7, 8]];
+        let deref = |diagonals: Vec<Vec<&&i32>>| -> Vec<Vec<i32>> {
+            diagonals
+                .into_iter()
+                .map(|diagonal| diagonal.into_iter().map(|&&value| value).collect())
+                .collect()
+        };
+
+        assert_eq!(
+            cloned(diagonal_pos_pos_flip_horizontal(&matrix)),
+            deref(diagonal_pos_pos(&flip_horizontal(&matrix)))
+        );
+        assert_eq!(
+            cloned(diagonal_pos_pos_flip_vertical(&matrix)),
+            deref(diagonal_pos_pos(&flip_vertical(&matrix)))
+        );
+        assert_eq!(
+            cloned(diagonal_pos_neg_flip_horizontal(&matrix)),
+            deref(diagonal_pos_neg(&flip_horizontal(&matrix)))
+        );
+        assert_eq!(
+            cloned(diagonal_pos_neg_flip_vertical(&matrix)),
+            deref(diagonal_pos_neg(&flip_vertical(&matrix)))
+        );
+    }
+
+    #[test]
+    fn flip_empty() {
+        let matrix: Vec<Vec<usize>> = vec![];
+        assert_eq!(flip_horizontal(&matrix), Vec::<Vec<&usize>>::new());
+        assert_eq!(flip_vertical(&matrix), Vec::<Vec<&usize>>::new());
+        assert_eq!(
+            diagonal_pos_pos_flip_horizontal(&matrix),
+            Vec::<Vec<&usize>>::new()
+        );
+        assert_eq!(
+            diagonal_pos_neg_flip_vertical(&matrix),
+            Vec::<Vec<&usize>>::new()
+        );
+    }
+
+    #[test]
+    fn reversed_within_diagonals() {
+        use crate::{diagonal_pos_neg, diagonal_pos_pos};
+
+        let matrix = [[1, 2, 3, 4], [5, 6, 7, 8]];
+        fn reverse_each(mut lines: Vec<Vec<&i32>>) -> Vec<Vec<&i32>> {
+            lines.iter_mut().for_each(|line| line.reverse());
+            lines
+        }
+        assert_eq!(
+            diagonal_pos_pos_reversed(&matrix),
+            reverse_each(diagonal_pos_pos(&matrix))
+        );
+        assert_eq!(
+            diagonal_pos_neg_reversed(&matrix),
+            reverse_each(diagonal_pos_neg(&matrix))
+        );
+
+        let matrix: Vec<Vec<usize>> = vec![];
+        assert!(diagonal_pos_pos_reversed(&matrix).is_empty());
+    }
+
+    #[test]
+    fn transpose_empty() {
+        let matrix: Vec<Vec<usize>> = vec![];
+        assert_eq!(transpose(&matrix), Vec::<Vec<&usize>>::new());
+        assert_eq!(transposed(&matrix), Vec::<Vec<usize>>::new());
+    }
+}
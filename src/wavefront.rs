@@ -0,0 +1,240 @@
+use crate::index;
+
+/// The cells a [`wavefront`] has computed so far.
+///
+/// While the cells of one anti-diagonal are computed, every cell on an earlier anti-diagonal,
+/// that is every cell above or to the left of the current one, can be read.
+#[derive(Debug)]
+pub struct Frontier<'a, U> {
+    rows: usize,
+    cols: usize,
+    diagonals: &'a [Vec<U>],
+}
+
+impl<U> Clone for Frontier<'_, U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<U> Copy for Frontier<'_, U> {}
+
+impl<'a, U> Frontier<'a, U> {
+    /// Returns the value of the cell at `(row, col)`, or `None` if the cell is outside of the
+    /// matrix or lies on the current anti-diagonal or a later one.
+    pub fn get(&self, row: usize, col: usize) -> Option<&'a U> {
+        if row >= self.rows || col >= self.cols {
+            return None;
+        }
+        let diagonal = self.diagonals.get(index::pos_neg_index(row, col))?;
+        let ((first_row, _), _) = index::pos_neg_start(self.rows, self.cols, row + col);
+        Some(&diagonal[row - first_row])
+    }
+}
+
+/// Computes a `rows` x `cols` table cell by cell in anti-diagonal order.
+///
+/// The anti-diagonals are the ones of [`diagonal_pos_neg`](crate::diagonal_pos_neg), and they
+/// are computed one after another, so `f` can read every cell above and to the left of the one
+/// it computes through the [`Frontier`]. This is the evaluation order of dynamic programs such
+/// as edit distance or sequence alignment, in which cells depend on their top, left and
+/// top-left neighbours.
+///
+/// # Arguments
+///
+/// * `rows` - The number of rows of the table.
+/// * `cols` - The number of columns of the table.
+/// * `f` - Computes the value of a `(row, column)` cell from the cells computed before it.
+///
+/// # Returns
+///
+/// The table as a `Vec<Vec<U>>`.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::wavefront;
+///
+/// let (a, b) = (b"kitten", b"sitting");
+///
+/// let distances = wavefront(a.len() + 1, b.len() + 1, |done, (i, j)| {
+///     if i == 0 || j == 0 {
+///         return i + j;
+///     }
+///     let replace = done.get(i - 1, j - 1).unwrap() + usize::from(a[i - 1] != b[j - 1]);
+///     let delete = done.get(i - 1, j).unwrap() + 1;
+///     let insert = done.get(i, j - 1).unwrap() + 1;
+///     replace.min(delete).min(insert)
+/// });
+/// assert_eq!(distances[a.len()][b.len()], 3);
+/// ```
+pub fn wavefront<U, F>(rows: usize, cols: usize, mut f: F) -> Vec<Vec<U>>
+where
+    F: FnMut(&Frontier<U>, (usize, usize)) -> U,
+{
+    run(rows, cols, |frontier, (x, y), len| {
+        (0..len).map(|i| f(frontier, (x + i, y - i))).collect()
+    })
+}
+
+/// Computes a `rows` x `cols` table cell by cell in anti-diagonal order, computing the cells of
+/// each anti-diagonal in parallel.
+///
+/// This is the parallel counterpart of [`wavefront`]. The cells on one anti-diagonal only
+/// depend on earlier anti-diagonals, so they are spread over rayon's thread pool, and the
+/// anti-diagonals themselves are still computed one after another.
+///
+/// # Arguments
+///
+/// * `rows` - The number of rows of the table.
+/// * `cols` - The number of columns of the table.
+/// * `f` - Computes the value of a `(row, column)` cell from the cells computed before it.
+///
+/// # Returns
+///
+/// The table as a `Vec<Vec<U>>`, equal to the one [`wavefront`] returns.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::{par_wavefront, wavefront};
+///
+/// // The number of monotone lattice paths from the top-left corner to every cell.
+/// let paths = |done: &diagonal::Frontier<u64>, (i, j): (usize, usize)| {
+///     if i == 0 || j == 0 {
+///         1
+///     } else {
+///         done.get(i - 1, j).unwrap() + done.get(i, j - 1).unwrap()
+///     }
+/// };
+///
+/// let table = par_wavefront(30, 30, paths);
+/// assert_eq!(table[29][29], 30_067_266_499_541_040);
+/// assert_eq!(table, wavefront(30, 30, paths));
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_wavefront<U, F>(rows: usize, cols: usize, f: F) -> Vec<Vec<U>>
+where
+    U: Send + Sync,
+    F: Fn(&Frontier<U>, (usize, usize)) -> U + Sync,
+{
+    use rayon::prelude::*;
+
+    run(rows, cols, |frontier, (x, y), len| {
+        (0..len)
+            .into_par_iter()
+            .map(|i| f(frontier, (x + i, y - i)))
+            .collect()
+    })
+}
+
+/// Computes the anti-diagonals one after another with `diagonal` and reassembles the table.
+///
+/// `diagonal` receives the cells computed so far, and the start and length of the
+/// anti-diagonal to compute.
+fn run<U>(
+    rows: usize,
+    cols: usize,
+    mut diagonal: impl FnMut(&Frontier<U>, (usize, usize), usize) -> Vec<U>,
+) -> Vec<Vec<U>> {
+    let count = index::diagonal_count(rows, cols);
+    let mut diagonals = Vec::with_capacity(count);
+    for i in 0..count {
+        let (start, len) = index::pos_neg_start(rows, cols, i);
+        let frontier = Frontier {
+            rows,
+            cols,
+            diagonals: &diagonals,
+        };
+        let values = diagonal(&frontier, start, len);
+        diagonals.push(values);
+    }
+    index::reassemble(rows, cols, diagonals, index::pos_neg_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit_distance_table(a: &[u8], b: &[u8]) -> Vec<Vec<usize>> {
+        let mut table = vec![vec![0; b.len() + 1]; a.len() + 1];
+        for i in 0..=a.len() {
+            for j in 0..=b.len() {
+                table[i][j] = if i == 0 || j == 0 {
+                    i + j
+                } else {
+                    (table[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]))
+                        .min(table[i - 1][j] + 1)
+                        .min(table[i][j - 1] + 1)
+                };
+            }
+        }
+        table
+    }
+
+    fn edit_distance(done: &Frontier<usize>, (i, j): (usize, usize), a: &[u8], b: &[u8]) -> usize {
+        if i == 0 || j == 0 {
+            return i + j;
+        }
+        (done.get(i - 1, j - 1).unwrap() + usize::from(a[i - 1] != b[j - 1]))
+            .min(done.get(i - 1, j).unwrap() + 1)
+            .min(done.get(i, j - 1).unwrap() + 1)
+    }
+
+    #[test]
+    fn matches_row_by_row_dynamic_program() {
+        for (a, b) in [
+            (&b"intention"[..], &b"execution"[..]),
+            (b"", b"abc"),
+            (b"abc", b""),
+            (b"a", b"a"),
+        ] {
+            let table = wavefront(a.len() + 1, b.len() + 1, |done, cell| {
+                edit_distance(done, cell, a, b)
+            });
+            assert_eq!(table, edit_distance_table(a, b));
+        }
+    }
+
+    #[test]
+    fn frontier_only_exposes_earlier_diagonals() {
+        let mut order = Vec::new();
+        let table = wavefront(3, 4, |done, (x, y)| {
+            for row in 0..4 {
+                for col in 0..5 {
+                    let expected =
+                        (row < 3 && col < 4 && row + col < x + y).then_some(row * 4 + col);
+                    assert_eq!(done.get(row, col).copied(), expected);
+                }
+            }
+            order.push((x, y));
+            x * 4 + y
+        });
+
+        assert_eq!(
+            table,
+            vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![8, 9, 10, 11]]
+        );
+        let expected: Vec<(usize, usize)> = index::pos_neg_cells(3, 4).flatten().collect();
+        assert_eq!(order, expected);
+    }
+
+    #[test]
+    fn empty_tables() {
+        assert!(wavefront(0, 3, |_, _| 0).is_empty());
+        assert_eq!(wavefront(2, 0, |_, _| 0), vec![Vec::<i32>::new(); 2]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_matches_sequential() {
+        let (a, b) = (&b"the quick brown fox"[..], &b"jumps over the lazy dog"[..]);
+        let sequential = wavefront(a.len() + 1, b.len() + 1, |done, cell| {
+            edit_distance(done, cell, a, b)
+        });
+        let parallel = par_wavefront(a.len() + 1, b.len() + 1, |done, cell| {
+            edit_distance(done, cell, a, b)
+        });
+        assert_eq!(parallel, sequential);
+    }
+}
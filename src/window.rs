@@ -0,0 +1,133 @@
+use crate::index;
+
+/// The two diagonals of one square window of a matrix, as reported by [`window_diagonals`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WindowDiagonals<'a, T> {
+    /// The `(row, column)` coordinate of the window's top-left cell.
+    pub top_left: (usize, usize),
+    /// The main diagonal of the window, from its top-left to its bottom-right cell.
+    pub main: Vec<&'a T>,
+    /// The anti-diagonal of the window, from its top-right to its bottom-left cell.
+    pub anti: Vec<&'a T>,
+}
+
+/// Extracts the main and anti-diagonal of every `k` x `k` window of a matrix.
+///
+/// The windows are all the `k` x `k` squares that fit inside the matrix, overlapping ones
+/// included. Both diagonals of a window run from its top row down, like those of
+/// [`diagonal_pos_pos`](crate::diagonal_pos_pos) and
+/// [`diagonal_pos_neg`](crate::diagonal_pos_neg), and refer to the matrix directly instead of a
+/// copy of the window.
+///
+/// # Arguments
+///
+/// * `matrix` - A reference to a matrix represented as a slice of rows, where each row
+///   is a slice of generic type `T`.
+/// * `k` - The side length of the windows.
+///
+/// # Returns
+///
+/// A vector with one [`WindowDiagonals`] per window, ordered by the window's top-left
+/// coordinate in row-major order. It is empty if `k` exceeds either side of the matrix.
+///
+/// # Panics
+///
+/// Panics if `k` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use diagonal::window_diagonals;
+///
+/// // Finding the "X-MAS" crosses: two "MAS" diagonals crossing in a 3x3 window.
+/// let grid = [
+///     ['M', '.', 'S', '.'],
+///     ['.', 'A', '.', '.'],
+///     ['M', '.', 'S', '.'],
+/// ];
+///
+/// let is_mas = |diagonal: &[&char]| {
+///     let word: String = diagonal.iter().copied().collect();
+///     word == "MAS" || word == "SAM"
+/// };
+/// let crosses: Vec<(usize, usize)> = window_diagonals(&grid, 3)
+///     .into_iter()
+///     .filter(|window| is_mas(&window.main) && is_mas(&window.anti))
+///     .map(|window| window.top_left)
+///     .collect();
+///
+/// assert_eq!(crosses, vec![(0, 0)]);
+/// ```
+pub fn window_diagonals<'a, Matrix: AsRef<[Row]> + 'a, Row: AsRef<[T]> + 'a, T>(
+    matrix: &'a Matrix,
+    k: usize,
+) -> Vec<WindowDiagonals<'a, T>> {
+    assert!(k > 0, "k must not be zero");
+    let matrix = matrix.as_ref();
+    let (rows, cols) = index::shape(matrix);
+    if k > rows || k > cols {
+        return vec![];
+    }
+
+    (0..=rows - k)
+        .flat_map(|row| (0..=cols - k).map(move |col| (row, col)))
+        .map(|(row, col)| WindowDiagonals {
+            top_left: (row, col),
+            main: (0..k).map(|i| &matrix[row + i].as_ref()[col + i]).collect(),
+            anti: (0..k)
+                .map(|i| &matrix[row + i].as_ref()[col + k - 1 - i])
+                .collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagonals_of_every_window() {
+        let matrix: Vec<Vec<(usize, usize)>> =
+            (0..4).map(|r| (0..5).map(|c| (r, c)).collect()).collect();
+
+        for k in 1..=4 {
+            let windows = window_diagonals(&matrix, k);
+            assert_eq!(windows.len(), (6 - k) * (5 - k));
+            for window in &windows {
+                let (row, col) = window.top_left;
+                let main: Vec<(usize, usize)> = (0..k).map(|i| (row + i, col + i)).collect();
+                let anti: Vec<(usize, usize)> =
+                    (0..k).map(|i| (row + i, col + k - 1 - i)).collect();
+                assert_eq!(
+                    window.main.iter().copied().copied().collect::<Vec<_>>(),
+                    main
+                );
+                assert_eq!(
+                    window.anti.iter().copied().copied().collect::<Vec<_>>(),
+                    anti
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn window_matches_diagonals_of_the_matrix() {
+        let matrix = [[1, 2], [3, 4]];
+        let windows = window_diagonals(&matrix, 2);
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].main, crate::diagonal_pos_pos(&matrix)[1]);
+        assert_eq!(windows[0].anti, crate::diagonal_pos_neg(&matrix)[1]);
+    }
+
+    #[test]
+    fn too_large() {
+        assert!(window_diagonals(&[[1, 2, 3]], 2).is_empty());
+        assert!(window_diagonals(&Vec::<Vec<u8>>::new(), 1).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_size() {
+        window_diagonals(&[[1]], 0);
+    }
+}